@@ -19,6 +19,17 @@ fn bench_sudoku(c: &mut Criterion) {
         })
     });
 
+    // 같은 시드 목록을 반복하므로 커밋 간의 성능 비교에 사용함
+    c.bench_function("sudoku_9x9_fixed_seeds", |b| {
+        let mut seed = 0;
+        b.iter(|| {
+            seed = (seed + 1) % 100;
+            let mut t = Table::new_default_9();
+            let mut solver = Solver::new_with_seed(&mut t, black_box(seed));
+            black_box(solver.fill_puzzle_with_timeout(std::time::Duration::MAX))
+        })
+    });
+
     c.bench_function("sudoku_16x16", |b| {
         b.iter(|| {
             let mut t = Table::new_default_16();
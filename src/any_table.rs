@@ -0,0 +1,373 @@
+use crate::{
+    error::SudokuError,
+    io::{ParseError, TextFormat, json::TableDocument, svg::SvgOptions},
+    model::{max_num::MaxNum, table::Table, table_lock::TableLock},
+    rating::{Rating, solve_with_rating},
+    solver::{Solver, random_seed},
+    verify::Violation,
+};
+use std::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+/// AnyTable이 지원하는 퍼즐 크기입니다.
+pub const SUPPORTED_SIZES: [usize; 14] = [4, 6, 8, 9, 10, 12, 16, 25, 32, 36, 49, 64, 81, 100];
+
+/// 실행 중에 크기가 정해지는 테이블입니다.
+///
+/// 파일에서 읽은 퍼즐처럼 컴파일 시점에 N을 정할 수 없는 경우 사용합니다.
+/// 각 기능은 크기에 맞는 TableLock<N>의 기능을 그대로 호출합니다.
+pub enum AnyTable {
+    S4(TableLock<4>),
+    S6(TableLock<6>),
+    S8(TableLock<8>),
+    S9(TableLock<9>),
+    S10(TableLock<10>),
+    S12(TableLock<12>),
+    S16(TableLock<16>),
+    S25(TableLock<25>),
+    S32(TableLock<32>),
+    S36(TableLock<36>),
+    S49(TableLock<49>),
+    S64(TableLock<64>),
+    S81(TableLock<81>),
+    S100(TableLock<100>),
+}
+
+/// size에 맞는 N을 정의하고 body를 실행하여 AnyTable을 만듭니다. body는 Result<TableLock<N>, ParseError>여야 합니다.
+macro_rules! new_sized {
+    ($size:expr, $n:ident => $body:expr) => {
+        match $size {
+            4 => {
+                const $n: usize = 4;
+                $body.map(AnyTable::S4)
+            }
+            6 => {
+                const $n: usize = 6;
+                $body.map(AnyTable::S6)
+            }
+            8 => {
+                const $n: usize = 8;
+                $body.map(AnyTable::S8)
+            }
+            9 => {
+                const $n: usize = 9;
+                $body.map(AnyTable::S9)
+            }
+            10 => {
+                const $n: usize = 10;
+                $body.map(AnyTable::S10)
+            }
+            12 => {
+                const $n: usize = 12;
+                $body.map(AnyTable::S12)
+            }
+            16 => {
+                const $n: usize = 16;
+                $body.map(AnyTable::S16)
+            }
+            25 => {
+                const $n: usize = 25;
+                $body.map(AnyTable::S25)
+            }
+            32 => {
+                const $n: usize = 32;
+                $body.map(AnyTable::S32)
+            }
+            36 => {
+                const $n: usize = 36;
+                $body.map(AnyTable::S36)
+            }
+            49 => {
+                const $n: usize = 49;
+                $body.map(AnyTable::S49)
+            }
+            64 => {
+                const $n: usize = 64;
+                $body.map(AnyTable::S64)
+            }
+            81 => {
+                const $n: usize = 81;
+                $body.map(AnyTable::S81)
+            }
+            100 => {
+                const $n: usize = 100;
+                $body.map(AnyTable::S100)
+            }
+            size => Err(ParseError::UnsupportedSize { size }),
+        }
+    };
+}
+
+/// 크기에 관계없이 내부의 TableLock으로 body를 실행합니다.
+macro_rules! with_table {
+    ($self:expr, $t:ident => $body:expr) => {
+        match $self {
+            AnyTable::S4($t) => $body,
+            AnyTable::S6($t) => $body,
+            AnyTable::S8($t) => $body,
+            AnyTable::S9($t) => $body,
+            AnyTable::S10($t) => $body,
+            AnyTable::S12($t) => $body,
+            AnyTable::S16($t) => $body,
+            AnyTable::S25($t) => $body,
+            AnyTable::S32($t) => $body,
+            AnyTable::S36($t) => $body,
+            AnyTable::S49($t) => $body,
+            AnyTable::S64($t) => $body,
+            AnyTable::S81($t) => $body,
+            AnyTable::S100($t) => $body,
+        }
+    };
+}
+
+impl AnyTable {
+    /// 기본 박스 모양을 가진 빈 테이블을 만듭니다.
+    pub fn new_default(size: usize) -> Result<Self, ParseError> {
+        new_sized!(size, N => Ok::<_, ParseError>(Table::<N>::new_default()))
+    }
+
+    /// 문서의 size에 맞는 테이블을 만듭니다.
+    pub fn from_document(doc: &TableDocument) -> Result<Self, ParseError> {
+        new_sized!(doc.size, N => doc.to_table::<N>())
+    }
+
+    /// 형식과 크기를 내용으로부터 알아내어 퍼즐을 읽습니다.
+    ///
+    /// 구조 정보가 없는 형식(한 줄, 여러 줄, pencilmark)은 기본 박스 모양을 사용합니다.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        Self::parse_with_format(text, TextFormat::detect(text))
+    }
+
+    /// 정해진 형식으로 퍼즐을 읽습니다. 크기는 내용으로부터 알아냅니다.
+    pub fn parse_with_format(text: &str, format: TextFormat) -> Result<Self, ParseError> {
+        let size = match format {
+            // 잘못된 공유 코드는 크기를 알 수 없다고 하는 대신 오류를 알려줌
+            TextFormat::ShareCode => crate::io::share_code::decode_size(text)?,
+            _ => format
+                .detect_size(text)
+                .ok_or(ParseError::UnsupportedSize { size: 0 })?,
+        };
+
+        new_sized!(size, N => parse_sized::<N>(text, format))
+    }
+
+    /// 퍼즐의 크기 N
+    #[must_use]
+    pub fn size(&self) -> usize {
+        with_table!(self, t => size_of(t))
+    }
+
+    /// 제한시간 안에 테이블을 풀거나, 빈 테이블인 경우 채웁니다. 테이블에는 풀이 결과가 남습니다.
+    ///
+    /// seed가 None인 경우 무작위 seed를 사용합니다.
+    pub fn solve(&mut self, seed: Option<u64>, timeout: Duration) -> Rating {
+        with_table!(self, t => solve_with_rating(t, seed, timeout))
+    }
+
+    /// 완성된 테이블에 구멍을 뚫어 문제를 만듭니다. 남은 cell이 given이 됩니다.
+    ///
+    /// 완성되지 않았거나 규칙에 어긋나는 테이블인 경우 변경하지 않고 false를 반환합니다.
+    ///
+    /// # Panics
+    ///
+    /// seed가 None이고 무작위 seed를 얻지 못한 경우
+    pub fn punch(&mut self, seed: Option<u64>) -> bool {
+        self.try_punch(seed).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// punch와 같으나, 무작위 seed를 얻지 못한 경우 오류를 반환합니다.
+    pub fn try_punch(&mut self, seed: Option<u64>) -> Result<bool, SudokuError> {
+        let seed = match seed {
+            Some(seed) => seed,
+            None => random_seed()?,
+        };
+        Ok(with_table!(self, t => punch_sized(t, seed)))
+    }
+
+    /// 확정된 값이 zone의 규칙을 지키는지 검사하여 모든 위반을 반환합니다.
+    #[must_use]
+    pub fn verify(&self) -> Vec<Violation> {
+        with_table!(self, t => t.verify())
+    }
+
+    /// 확정된 값을 1부터 시작하는 숫자로 반환합니다. 확정되지 않은 cell은 None입니다.
+    #[must_use]
+    pub fn values(&self) -> Vec<Option<usize>> {
+        with_table!(self, t => {
+            let read = t.read_lock();
+            read.into_iter()
+                .map(|(_, chk)| chk.final_num().map(|num| num.get_value() + 1))
+                .collect()
+        })
+    }
+
+    #[must_use]
+    pub fn to_line(&self) -> String {
+        with_table!(self, t => t.to_line())
+    }
+
+    #[must_use]
+    pub fn to_grid_string(&self) -> String {
+        with_table!(self, t => t.to_grid_string())
+    }
+
+    #[must_use]
+    pub fn to_pencilmark_string(&self) -> String {
+        with_table!(self, t => t.to_pencilmark_string())
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        with_table!(self, t => t.to_json())
+    }
+
+    pub fn to_fpuzzles(&self) -> Result<String, ParseError> {
+        with_table!(self, t => t.to_fpuzzles())
+    }
+
+    pub fn to_share_code(&self) -> Result<String, ParseError> {
+        with_table!(self, t => t.to_share_code())
+    }
+
+    #[must_use]
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        with_table!(self, t => t.to_svg(options))
+    }
+}
+
+impl Display for AnyTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        with_table!(self, t => write!(f, "{t}"))
+    }
+}
+
+impl<const N: usize> TryFrom<TableLock<N>> for AnyTable {
+    type Error = ParseError;
+
+    /// 지원하지 않는 크기인 경우 UnsupportedSize 오류를 반환합니다.
+    fn try_from(table: TableLock<N>) -> Result<Self, Self::Error> {
+        let table: Box<dyn std::any::Any> = Box::new(table);
+        new_sized!(N, M => table
+            .downcast::<TableLock<M>>()
+            .map(|t| *t)
+            .map_err(|_| ParseError::UnsupportedSize { size: N }))
+    }
+}
+
+fn size_of<const N: usize>(_: &TableLock<N>) -> usize {
+    N
+}
+
+fn parse_sized<const N: usize>(text: &str, format: TextFormat) -> Result<TableLock<N>, ParseError> {
+    match format {
+        TextFormat::Line => {
+            let table = Table::<N>::new_default();
+            table.set_givens_from_line(text.trim())?;
+            Ok(table)
+        }
+        TextFormat::Grid => {
+            let table = Table::<N>::new_default();
+            table.set_givens_from_grid(text)?;
+            Ok(table)
+        }
+        TextFormat::Pencilmark => {
+            let table = Table::<N>::new_default();
+            table.set_notes_from_pencilmark(text)?;
+            Ok(table)
+        }
+        TextFormat::Json => Table::new_from_json(text),
+        TextFormat::FPuzzles => Table::new_from_fpuzzles(text).map(|import| import.table),
+        TextFormat::ShareCode => Table::new_from_share_code(text).map(|shared| shared.table),
+    }
+}
+
+fn punch_sized<const N: usize>(table: &mut TableLock<N>, seed: u64) -> bool {
+    if !table.verify().is_empty() {
+        return false;
+    }
+
+    let givens: Vec<Option<MaxNum<N>>> = {
+        let mut punch = Solver::new_with_seed(table, seed).into_punch();
+        punch.punch_all();
+        let read = punch.get_table().read_lock();
+        read.into_iter()
+            .map(|(_, chk)| chk.fixed_final_num())
+            .collect()
+    };
+
+    // 풀 수 있는 cell의 값이 남지 않도록 given만 남기고, given은 fixed_final_num으로 표시함
+    if table.set_givens(&givens).is_err() {
+        return false;
+    }
+    let mut write = table.write_lock();
+    for (_, chk) in &mut write {
+        if chk.final_num().is_some() {
+            chk.fixed_final_num_set_dup();
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model::max_num::CHAR_ARR, rating::SolveStatus};
+
+    #[test]
+    fn any_table_round_trip() {
+        // 큰 퍼즐은 debug 빌드에서 오래 걸리므로 작은 크기만 확인함
+        for size in [4, 6, 8, 9, 10, 12] {
+            let mut table = AnyTable::new_default(size).unwrap();
+            assert_eq!(table.size(), size);
+
+            let rating = table.solve(Some(0), Duration::MAX);
+            assert_eq!(rating.status, SolveStatus::Solved);
+            assert!(table.verify().is_empty());
+
+            assert!(table.punch(Some(0)));
+            let puzzle = AnyTable::parse(&table.to_line()).unwrap();
+            assert_eq!(puzzle.size(), size);
+            assert_eq!(puzzle.values(), table.values());
+
+            // 12X12의 공유 코드는 100글자처럼 길이가 제곱수일 수 있으므로 형식을 추측하게 함
+            let code = table.to_share_code().unwrap();
+            assert_eq!(TextFormat::detect(&code), TextFormat::ShareCode);
+            let shared = AnyTable::parse(&code).unwrap();
+            assert_eq!(shared.to_line(), table.to_line());
+        }
+
+        assert!(matches!(
+            AnyTable::new_default(7),
+            Err(ParseError::UnsupportedSize { size: 7 })
+        ));
+
+        let table = AnyTable::try_from(Table::new_default_16()).unwrap();
+        assert_eq!(table.size(), 16);
+    }
+
+    #[test]
+    fn any_table_giant_sizes() {
+        // 큰 퍼즐은 풀지 않고 읽기와 출력만 확인함
+        for size in [36, 49, 64, 81, 100] {
+            let last = if size <= CHAR_ARR.len() {
+                CHAR_ARR[size - 1].to_string()
+            } else {
+                size.to_string()
+            };
+            let mut tokens = vec![".".to_string(); size * size];
+            tokens[0] = last;
+            let line = if size <= CHAR_ARR.len() {
+                tokens.concat()
+            } else {
+                tokens.join(" ")
+            };
+
+            let table = AnyTable::parse(&line).unwrap();
+            assert_eq!(table.size(), size);
+            assert_eq!(table.to_line(), line);
+            assert_eq!(table.values()[0], Some(size));
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use crate::{
+    io::ParseError,
+    model::{max_num::MaxNum, table_lock::TableLock},
+    rating::{Difficulty, Rating, SolveStatus, solve_with_rating},
+    solver::{GeneralSolve, Solver, random_seed},
+};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::{ops::RangeInclusive, time::Duration};
+
+/// 여러 퍼즐을 한꺼번에 풀 때의 설정입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// 퍼즐 하나의 제한시간
+    pub timeout: Duration,
+    /// None인 경우 퍼즐마다 무작위 seed를 사용합니다.
+    pub seed: Option<u64>,
+    /// 한 번에 읽어 병렬로 푸는 퍼즐 수. 결과는 묶음 단위로 입력 순서대로 전달됩니다.
+    pub chunk_size: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::MAX,
+            seed: None,
+            chunk_size: 1024,
+        }
+    }
+}
+
+/// 퍼즐 하나를 푼 결과입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchSolved {
+    pub rating: Rating,
+    /// 푼 경우 한 줄 형식의 정답
+    pub solution: Option<String>,
+}
+
+/// 입력의 한 줄에 대한 결과입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResult {
+    /// 입력에서의 줄 번호 (1부터 시작)
+    pub line: usize,
+    /// 한 줄 형식으로 읽지 못한 경우 오류
+    pub result: Result<BatchSolved, ParseError>,
+}
+
+impl BatchResult {
+    #[must_use]
+    pub fn status(&self) -> Option<SolveStatus> {
+        self.result.as_ref().ok().map(|solved| solved.rating.status)
+    }
+}
+
+/// 한 줄에 하나씩 적힌 퍼즐을 읽으며, 퍼즐 단위로 병렬로 풉니다.
+///
+/// 빈 줄과 '#'으로 시작하는 줄은 건너뜁니다.
+/// new_table은 given을 채울 빈 테이블을 만드는 함수이며, 퍼즐마다 호출됩니다.
+/// 결과는 입력 순서대로 on_result에 전달됩니다. 입력을 읽는 중 오류가 발생하면 즉시 반환합니다.
+pub fn solve_lines<const N: usize>(
+    lines: impl IntoIterator<Item = std::io::Result<String>>,
+    new_table: impl Fn() -> TableLock<N> + Sync,
+    options: &BatchOptions,
+    mut on_result: impl FnMut(BatchResult),
+) -> std::io::Result<()> {
+    let mut lines = lines.into_iter().enumerate();
+    let mut chunk: Vec<(usize, String)> = Vec::with_capacity(options.chunk_size.max(1));
+
+    loop {
+        chunk.clear();
+        for (index, line) in lines.by_ref() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            chunk.push((index + 1, line));
+            if chunk.len() >= options.chunk_size.max(1) {
+                break;
+            }
+        }
+
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        let results: Vec<BatchResult> = std::mem::take(&mut chunk)
+            .into_par_iter()
+            .map(|(line, text)| BatchResult {
+                line,
+                result: solve_one(&text, &new_table, options),
+            })
+            .collect();
+
+        results.into_iter().for_each(&mut on_result);
+    }
+}
+
+fn solve_one<const N: usize>(
+    text: &str,
+    new_table: &impl Fn() -> TableLock<N>,
+    options: &BatchOptions,
+) -> Result<BatchSolved, ParseError> {
+    let mut table = new_table();
+    table.set_givens_from_line(text)?;
+
+    let rating = solve_with_rating(&mut table, options.seed, options.timeout);
+    let solution = (rating.status == SolveStatus::Solved).then(|| table.to_line());
+    Ok(BatchSolved { rating, solution })
+}
+
+/// 여러 퍼즐을 한꺼번에 만들 때의 설정입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateOptions {
+    /// 만들 퍼즐 수
+    pub count: usize,
+    /// 허용하는 given 개수의 범위
+    pub clues: RangeInclusive<usize>,
+    /// 허용하는 난이도의 범위
+    pub difficulty: RangeInclusive<Difficulty>,
+    /// 시도마다 seed, seed + 1, seed + 2, ... 를 사용합니다. None인 경우 무작위로 시작합니다.
+    pub seed: Option<u64>,
+    /// 퍼즐 하나를 채우거나 풀 때의 제한시간
+    pub timeout: Duration,
+    /// 조건에 맞는 퍼즐을 찾지 못하더라도 이 횟수만큼 시도하면 멈춥니다.
+    pub max_attempts: usize,
+    /// 한 번에 병렬로 시도하는 횟수
+    pub chunk_size: usize,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            clues: 0..=usize::MAX,
+            difficulty: Difficulty::Single..=Difficulty::Guess,
+            seed: None,
+            timeout: Duration::MAX,
+            max_attempts: usize::MAX,
+            chunk_size: 64,
+        }
+    }
+}
+
+/// 조건을 통과하여 만들어진 퍼즐입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedPuzzle {
+    /// 같은 퍼즐을 다시 만들 수 있는 seed
+    pub seed: u64,
+    /// given 개수
+    pub clues: usize,
+    /// 한 줄 형식의 퍼즐
+    pub puzzle: String,
+    /// 한 줄 형식의 정답
+    pub solution: String,
+    /// 만든 퍼즐을 다시 풀어 얻은 기법별 횟수
+    pub rating: Rating,
+}
+
+impl GeneratedPuzzle {
+    /// 한 줄짜리 JSON으로 출력합니다.
+    #[must_use]
+    pub fn to_json_line(&self) -> String {
+        serde_json::json!({
+            "seed": self.seed,
+            "clues": self.clues,
+            "difficulty": self.rating.difficulty().to_string(),
+            "single": self.rating.single_cnt,
+            "naked": self.rating.naked_cnt,
+            "box_line_reduction": self.rating.box_line_reduction_cnt,
+            "cage": self.rating.cage_cnt,
+            "guess": self.rating.guess_cnt,
+            "puzzle": self.puzzle,
+            "solution": self.solution,
+        })
+        .to_string()
+    }
+}
+
+/// 퍼즐을 채우고 구멍을 뚫는 것을 병렬로 반복하여, 조건에 맞는 퍼즐을 count개 만듭니다.
+///
+/// new_table은 퍼즐 구조를 가진 빈 테이블을 만드는 함수이며, 시도마다 호출됩니다.
+/// 결과는 seed 순서대로 on_puzzle에 전달되므로 같은 seed에서는 항상 같은 결과가 나옵니다.
+/// 만든 퍼즐의 개수를 반환하며, max_attempts에 도달한 경우 count보다 적을 수 있습니다.
+///
+/// # Panics
+///
+/// options.seed가 None이고 무작위 seed를 얻지 못한 경우
+pub fn generate_puzzles<const N: usize>(
+    new_table: impl Fn() -> TableLock<N> + Sync,
+    options: &GenerateOptions,
+    mut on_puzzle: impl FnMut(GeneratedPuzzle),
+) -> usize {
+    let base_seed = match options.seed {
+        Some(seed) => seed,
+        None => random_seed().unwrap_or_else(|err| panic!("{err}")),
+    };
+    let mut generated = 0;
+    let mut attempt = 0;
+
+    while generated < options.count && attempt < options.max_attempts {
+        let chunk = options
+            .chunk_size
+            .max(1)
+            .min(options.max_attempts - attempt);
+
+        let results: Vec<Option<GeneratedPuzzle>> = (attempt..attempt + chunk)
+            .into_par_iter()
+            .map(|i| generate_one(base_seed.wrapping_add(i as u64), &new_table, options))
+            .collect();
+        attempt += chunk;
+
+        for puzzle in results.into_iter().flatten() {
+            if generated == options.count {
+                break;
+            }
+            on_puzzle(puzzle);
+            generated += 1;
+        }
+    }
+
+    generated
+}
+
+fn generate_one<const N: usize>(
+    seed: u64,
+    new_table: &impl Fn() -> TableLock<N>,
+    options: &GenerateOptions,
+) -> Option<GeneratedPuzzle> {
+    let mut table = new_table();
+    let (givens, solution): (Vec<Option<MaxNum<N>>>, Vec<Option<MaxNum<N>>>) = {
+        let mut solver = Solver::new_with_seed(&mut table, seed);
+        if solver.fill_puzzle_with_timeout(options.timeout) != 0 {
+            return None;
+        }
+
+        let mut punch = solver.into_punch();
+        punch.punch_all();
+        let read = punch.get_table().read_lock();
+        let givens = read
+            .into_iter()
+            .map(|(_, chk)| chk.fixed_final_num())
+            .collect();
+        (givens, punch.get_solution().to_vec())
+    };
+
+    let clues = givens.iter().flatten().count();
+    if !options.clues.contains(&clues) {
+        return None;
+    }
+
+    table.set_givens(&givens).ok()?;
+    let puzzle = table.to_line();
+    let rating = solve_with_rating(&mut table, Some(seed), options.timeout);
+    if rating.status != SolveStatus::Solved || !options.difficulty.contains(&rating.difficulty()) {
+        return None;
+    }
+
+    table.set_givens(&solution).ok()?;
+    Some(GeneratedPuzzle {
+        seed,
+        clues,
+        puzzle,
+        solution: table.to_line(),
+        rating,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::table::Table;
+
+    #[test]
+    fn batch_solve() {
+        let input = "\
+# 주석
+53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79
+
+53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..7X
+553..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..7
+";
+        let mut results = Vec::new();
+        solve_lines(
+            input.lines().map(|l| Ok(l.to_string())),
+            Table::new_default_9,
+            &BatchOptions {
+                seed: Some(0),
+                chunk_size: 2,
+                ..BatchOptions::default()
+            },
+            |result| results.push(result),
+        )
+        .unwrap();
+
+        assert_eq!(
+            results.iter().map(|r| r.line).collect::<Vec<_>>(),
+            [2, 4, 5]
+        );
+        assert_eq!(results[0].status(), Some(SolveStatus::Solved));
+        assert_eq!(
+            results[0].result.as_ref().unwrap().solution.as_deref(),
+            Some(
+                "534678912672195348198342567859761423426853791713924856961537284287419635345286179"
+            )
+        );
+        assert!(matches!(
+            &results[1].result,
+            Err(ParseError::InvalidSymbol { symbol, .. }) if symbol == "X"
+        ));
+        assert!(matches!(
+            results[2].result,
+            Err(ParseError::ContradictoryGivens { .. })
+        ));
+    }
+
+    #[test]
+    fn batch_solve_samurai() {
+        let mut t = Table::new_samurai();
+        Solver::new_with_seed(&mut t, 0).fill_puzzle_with_timeout(Duration::MAX);
+        let solution = t.to_line();
+        // 369칸 중 두 칸을 비운 퍼즐. 한 줄의 길이가 N * N이 아니어도 테이블의 cell 순서로 읽어야 함
+        let mut puzzle: Vec<char> = solution.chars().collect();
+        puzzle[0] = '.';
+        puzzle[200] = '.';
+        let puzzle: String = puzzle.into_iter().collect();
+
+        let mut results = Vec::new();
+        solve_lines(
+            [Ok(puzzle)],
+            Table::new_samurai,
+            &BatchOptions {
+                seed: Some(0),
+                ..BatchOptions::default()
+            },
+            |result| results.push(result),
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status(), Some(SolveStatus::Solved));
+        assert_eq!(
+            results[0].result.as_ref().unwrap().solution.as_deref(),
+            Some(solution.as_str())
+        );
+    }
+
+    #[test]
+    fn batch_generate() {
+        let options = GenerateOptions {
+            count: 3,
+            clues: 35..=50,
+            difficulty: Difficulty::Single..=Difficulty::BoxLineReduction,
+            seed: Some(0),
+            chunk_size: 4,
+            ..GenerateOptions::default()
+        };
+        let mut puzzles = Vec::new();
+        let generated = generate_puzzles(Table::new_default_9, &options, |p| puzzles.push(p));
+        assert_eq!(generated, 3);
+
+        for puzzle in &puzzles {
+            assert!(options.clues.contains(&puzzle.clues));
+            assert!(options.difficulty.contains(&puzzle.rating.difficulty()));
+            assert_eq!(
+                puzzle.puzzle.chars().filter(|&c| c != '.').count(),
+                puzzle.clues
+            );
+
+            let mut t = Table::new_default_9_from_line(&puzzle.puzzle).unwrap();
+            solve_with_rating(&mut t, None, Duration::MAX);
+            assert_eq!(t.to_line(), puzzle.solution);
+        }
+
+        // 같은 seed에서는 같은 결과
+        let mut again = Vec::new();
+        generate_puzzles(Table::new_default_9, &options, |p| again.push(p));
+        assert_eq!(puzzles, again);
+
+        let json: serde_json::Value = serde_json::from_str(&puzzles[0].to_json_line()).unwrap();
+        assert_eq!(json["seed"], puzzles[0].seed);
+    }
+}
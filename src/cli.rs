@@ -0,0 +1,515 @@
+pub mod args;
+mod input;
+mod output;
+mod play;
+
+pub use args::{Command, Options};
+
+use input::{
+    detect_size, input_error, layout_document, load_table, load_values, open_lines, read_input,
+};
+use output::{output_error, render};
+use std::{
+    fmt::Display,
+    io::{BufWriter, Write},
+    process::ExitCode,
+};
+use sudoku_solver_lib::{
+    any_table::SUPPORTED_SIZES,
+    batch::{BatchOptions, GenerateOptions, generate_puzzles, solve_lines},
+    model::{max_num::MaxNum, table_lock::TableLock},
+    play::Play,
+    punch::Punch,
+    rating::{SolveStatus, solve_with_rating},
+    solver::{GeneralSolve, Solver},
+    verify::Violation,
+};
+
+/// 프로그램 종료 코드입니다. 스크립트에서 결과를 구분할 수 있도록 경우마다 다른 값을 사용합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Success = 0,
+    /// 풀 수 없는 퍼즐이거나 검증에 실패함
+    Failure = 1,
+    /// 잘못된 명령줄 인자. 사용법을 함께 출력함
+    Usage = 2,
+    /// 입력 파일을 읽지 못했거나, 형식이 잘못됐거나, 지원하지 않는 크기이거나, given이 서로 충돌하는 퍼즐
+    Input = 3,
+    /// 제한시간 초과
+    Timeout = 4,
+}
+
+impl From<Exit> for ExitCode {
+    fn from(exit: Exit) -> Self {
+        ExitCode::from(exit as u8)
+    }
+}
+
+/// 종료 코드와 함께 표준 에러로 출력할 메시지입니다.
+#[derive(Debug)]
+pub struct CliError {
+    pub exit: Exit,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(exit: Exit, message: impl Display) -> Self {
+        CliError {
+            exit,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<SolveStatus> for CliError {
+    fn from(status: SolveStatus) -> Self {
+        match status {
+            SolveStatus::Timeout => CliError::new(Exit::Timeout, "제한시간 초과"),
+            _ => CliError::new(Exit::Failure, "풀 수 없는 퍼즐"),
+        }
+    }
+}
+
+pub type CliResult = Result<(), CliError>;
+
+/// 지원하는 퍼즐 크기마다 함수를 단형화하여 호출합니다.
+/// --size는 인자를 읽을 때 검사하므로, 지원하지 않는 크기는 입력에서 알아낸 크기입니다.
+macro_rules! dispatch_size {
+    ($size:expr, $f:ident ( $($arg:expr),* )) => {
+        match $size {
+            4 => $f::<4>($($arg),*),
+            6 => $f::<6>($($arg),*),
+            8 => $f::<8>($($arg),*),
+            9 => $f::<9>($($arg),*),
+            10 => $f::<10>($($arg),*),
+            12 => $f::<12>($($arg),*),
+            16 => $f::<16>($($arg),*),
+            25 => $f::<25>($($arg),*),
+            32 => $f::<32>($($arg),*),
+            36 => $f::<36>($($arg),*),
+            49 => $f::<49>($($arg),*),
+            64 => $f::<64>($($arg),*),
+            81 => $f::<81>($($arg),*),
+            100 => $f::<100>($($arg),*),
+            size => Err(CliError::new(
+                Exit::Input,
+                format!("지원하지 않는 퍼즐 크기: {size} ({SUPPORTED_SIZES:?})"),
+            )),
+        }
+    };
+}
+
+pub fn run(options: &Options) -> CliResult {
+    match options.command {
+        Command::Help => {
+            print!("{}", args::USAGE);
+            Ok(())
+        }
+        Command::Generate => {
+            let size = match options.size {
+                Some(size) => size,
+                None => detect_size(options, None)?.unwrap_or(9),
+            };
+            dispatch_size!(size, run_sized(options, None))
+        }
+        Command::Solve | Command::Punch | Command::Rate | Command::Verify => {
+            let text = read_input(options.input.as_deref())?;
+            let size = match options.size {
+                Some(size) => size,
+                None => detect_size(options, Some(&text))?.unwrap_or(9),
+            };
+            dispatch_size!(size, run_sized(options, Some(&text)))
+        }
+        Command::Batch => {
+            let mut lines = open_lines(options.input.as_deref())?;
+
+            // 크기를 알 수 없는 경우 첫 퍼즐의 길이로 정함
+            let mut head: Vec<std::io::Result<String>> = Vec::new();
+            let size = match (options.size, detect_size(options, None)?) {
+                (Some(size), _) | (None, Some(size)) => size,
+                (None, None) => loop {
+                    let Some(line) = lines.next() else {
+                        break 9;
+                    };
+                    let line = line.map_err(|err| CliError::new(Exit::Input, err))?;
+                    let trimmed = line.trim();
+                    let len = (!trimmed.is_empty() && !trimmed.starts_with('#'))
+                        .then(|| trimmed.chars().count());
+                    head.push(Ok(line));
+
+                    if let Some(len) = len {
+                        break (1..=len).find(|n| n * n == len).unwrap_or(9);
+                    }
+                },
+            };
+
+            dispatch_size!(size, batch(options, head.into_iter().chain(lines)))
+        }
+        Command::Play => {
+            // 표준 입력은 명령을 읽는 데 사용하므로 퍼즐은 파일에서만 읽음
+            let text = match &options.input {
+                Some(path) => Some(read_input(Some(path))?),
+                None => None,
+            };
+            let size = match options.size {
+                Some(size) => size,
+                None => detect_size(options, text.as_deref())?.unwrap_or(9),
+            };
+            dispatch_size!(size, run_sized(options, text.as_deref()))
+        }
+    }
+}
+
+fn run_sized<const N: usize>(options: &Options, text: Option<&str>) -> CliResult {
+    match options.command {
+        Command::Generate => generate::<N>(options),
+        Command::Solve => solve::<N>(options, text.unwrap_or_default()),
+        Command::Punch => punch::<N>(options, text.unwrap_or_default()),
+        Command::Rate => rate::<N>(options, text.unwrap_or_default()),
+        Command::Verify => verify::<N>(options, text.unwrap_or_default()),
+        Command::Play => start_play::<N>(options, text),
+        Command::Batch | Command::Help => unreachable!(),
+    }
+}
+
+/// 현재 확정된 값 목록
+fn final_nums<const N: usize>(table: &TableLock<N>) -> Vec<Option<MaxNum<N>>> {
+    let read = table.read_lock();
+    read.into_iter().map(|(_, chk)| chk.final_num()).collect()
+}
+
+fn generate<const N: usize>(options: &Options) -> CliResult {
+    if let Some(count) = options.count {
+        return generate_many::<N>(options, count);
+    }
+
+    let (mut table, _) = load_table::<N>(options, None)?;
+    let mut punch = fill_and_punch(options, &mut table)?;
+    punch.punch_all();
+    print_punched(options, &punch)
+}
+
+/// 조건에 맞는 퍼즐을 count개 만들어 한 줄에 하나씩 JSON으로 출력합니다.
+fn generate_many<const N: usize>(options: &Options, count: usize) -> CliResult {
+    let doc = layout_document::<N>(options)?;
+    doc.to_table::<N>().map_err(input_error)?;
+
+    let generate_options = GenerateOptions {
+        count,
+        clues: options.clues.clone(),
+        difficulty: options.difficulty.clone(),
+        seed: options.seed,
+        timeout: options.timeout,
+        max_attempts: options.attempts.unwrap_or(count.saturating_mul(1000)),
+        ..GenerateOptions::default()
+    };
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    let mut write_err: Option<std::io::Error> = None;
+
+    let generated = generate_puzzles(
+        || doc.to_table::<N>().expect("검증된 구조"),
+        &generate_options,
+        |puzzle| {
+            // 결과를 바로 볼 수 있도록 퍼즐마다 flush
+            if let Err(err) = writeln!(out, "{}", puzzle.to_json_line()).and_then(|_| out.flush()) {
+                write_err.get_or_insert(err);
+            }
+        },
+    );
+
+    if let Some(err) = write_err {
+        return Err(CliError::new(Exit::Failure, err));
+    }
+
+    eprintln!("generated: {generated}");
+    if generated < count {
+        return Err(CliError::new(
+            Exit::Failure,
+            format!("조건에 맞는 퍼즐을 {generated}개만 만듦"),
+        ));
+    }
+    Ok(())
+}
+
+/// 빈 테이블을 채운 뒤 구멍을 뚫을 준비를 합니다.
+fn fill_and_punch<'a, const N: usize>(
+    options: &Options,
+    table: &'a mut TableLock<N>,
+) -> Result<Punch<'a, N>, CliError> {
+    let mut solver = match options.seed {
+        Some(seed) => Solver::new_with_seed(table, seed),
+        None => Solver::new(table),
+    };
+    eprintln!("seed: {}", solver.get_random_seed());
+
+    if solver.fill_puzzle_with_timeout(options.timeout) != 0 {
+        return Err(CliError::new(Exit::Timeout, "퍼즐을 채우지 못함"));
+    }
+
+    Ok(solver.into_punch())
+}
+
+fn punch<const N: usize>(options: &Options, text: &str) -> CliResult {
+    let (mut table, _) = load_table::<N>(options, Some(text))?;
+    if final_nums(&table).iter().any(Option::is_none) {
+        return Err(CliError::new(Exit::Input, "완성된 퍼즐이 아님"));
+    }
+
+    let solver = match options.seed {
+        Some(seed) => Solver::new_with_seed(&mut table, seed),
+        None => Solver::new(&mut table),
+    };
+    if solver.validater().is_some() {
+        return Err(CliError::new(Exit::Input, "올바르지 않은 퍼즐"));
+    }
+
+    let mut punch = solver.into_punch();
+    punch.punch_all();
+    print_punched(options, &punch)
+}
+
+/// 구멍을 뚫은 퍼즐을 출력하고, 설정된 경우 정답도 출력합니다.
+fn print_punched<const N: usize>(options: &Options, punch: &Punch<'_, N>) -> CliResult {
+    let table = punch.get_table();
+    let givens: Vec<Option<MaxNum<N>>> = {
+        let read = table.read_lock();
+        read.into_iter()
+            .map(|(_, chk)| chk.fixed_final_num())
+            .collect()
+    };
+    let solution: Vec<MaxNum<N>> = punch.get_solution().iter().flatten().copied().collect();
+    let solution = (solution.len() == table.len() && options.solution).then_some(solution);
+
+    // 풀 수 있는 cell의 값이 남지 않도록 given만 남김
+    table.set_givens(&givens).map_err(input_error)?;
+    print!(
+        "{}",
+        render(table, &givens, solution.as_deref(), options.format).map_err(output_error)?
+    );
+
+    if let Some(solution) = solution {
+        let solution: Vec<Option<MaxNum<N>>> = solution.into_iter().map(Some).collect();
+        table.set_givens(&solution).map_err(input_error)?;
+        println!();
+        print!(
+            "{}",
+            render(table, &givens, None, options.format).map_err(output_error)?
+        );
+    }
+
+    Ok(())
+}
+
+fn start_play<const N: usize>(options: &Options, text: Option<&str>) -> CliResult {
+    let (mut table, _) = load_table::<N>(options, text)?;
+
+    if text.is_none() {
+        let givens: Vec<Option<MaxNum<N>>> = {
+            let mut punch = fill_and_punch(options, &mut table)?;
+            punch.punch_all();
+            let read = punch.get_table().read_lock();
+            read.into_iter()
+                .map(|(_, chk)| chk.fixed_final_num())
+                .collect()
+        };
+        table.set_givens(&givens).map_err(input_error)?;
+    }
+
+    let play = Play::new(table, options.timeout).map_err(input_error)?;
+    play::play_loop(play)
+}
+
+fn solve<const N: usize>(options: &Options, text: &str) -> CliResult {
+    let (mut table, givens) = load_table::<N>(options, Some(text))?;
+    let rating = solve_with_rating(&mut table, options.seed, options.timeout);
+    if rating.status != SolveStatus::Solved {
+        return Err(rating.status.into());
+    }
+
+    print!(
+        "{}",
+        render(&table, &givens, None, options.format).map_err(output_error)?
+    );
+    Ok(())
+}
+
+fn rate<const N: usize>(options: &Options, text: &str) -> CliResult {
+    let (mut table, _) = load_table::<N>(options, Some(text))?;
+    let rating = solve_with_rating(&mut table, options.seed, options.timeout);
+
+    println!("status: {}", rating.status);
+    println!("difficulty: {}", rating.difficulty());
+    println!("single: {}", rating.single_cnt);
+    println!("naked: {}", rating.naked_cnt);
+    println!("box_line_reduction: {}", rating.box_line_reduction_cnt);
+    println!("cage: {}", rating.cage_cnt);
+    println!("guess: {}", rating.guess_cnt);
+    println!("seed: {}", rating.seed);
+
+    match rating.status {
+        SolveStatus::Solved => Ok(()),
+        status => Err(status.into()),
+    }
+}
+
+fn verify<const N: usize>(options: &Options, text: &str) -> CliResult {
+    let (mut table, values) = load_values::<N>(options, text)?;
+    let givens = match &options.puzzle {
+        Some(path) => {
+            let puzzle = read_input(Some(path))?;
+            Some(load_table::<N>(options, Some(&puzzle))?.1)
+        }
+        None => None,
+    };
+
+    let is_complete = values.iter().all(Option::is_some);
+    let violations: Vec<Violation> = table
+        .verify_values(&values, givens.as_deref())
+        .map_err(input_error)?
+        .into_iter()
+        // 원래 퍼즐이 없고 빈 칸이 있는 경우는 풀 수 있는지만 검사함
+        .filter(|v| givens.is_some() || !matches!(v, Violation::EmptyCell { .. }))
+        .collect();
+
+    if !violations.is_empty() {
+        for violation in &violations {
+            println!("{violation}");
+        }
+        return Err(CliError::new(
+            Exit::Failure,
+            format!("규칙 위반 {}개", violations.len()),
+        ));
+    }
+
+    if !is_complete {
+        table.set_givens(&values).map_err(input_error)?;
+        let rating = solve_with_rating(&mut table, options.seed, options.timeout);
+        if rating.status != SolveStatus::Solved {
+            return Err(rating.status.into());
+        }
+    }
+
+    println!("ok");
+    Ok(())
+}
+
+fn batch<const N: usize>(
+    options: &Options,
+    lines: impl Iterator<Item = std::io::Result<String>>,
+) -> CliResult {
+    let doc = layout_document::<N>(options)?;
+    doc.to_table::<N>().map_err(input_error)?;
+
+    let batch_options = BatchOptions {
+        timeout: options.timeout,
+        seed: options.seed,
+        ..BatchOptions::default()
+    };
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    let (mut solved, mut unsolvable, mut timeout, mut error) = (0usize, 0usize, 0usize, 0usize);
+    let mut write_err: Option<std::io::Error> = None;
+
+    solve_lines(
+        lines,
+        || doc.to_table::<N>().expect("검증된 구조"),
+        &batch_options,
+        |result| {
+            let row = match &result.result {
+                Ok(ret) => {
+                    match ret.rating.status {
+                        SolveStatus::Solved => solved += 1,
+                        SolveStatus::Unsolvable => unsolvable += 1,
+                        SolveStatus::Timeout => timeout += 1,
+                    }
+                    format!(
+                        "{}\t{}\t{}\t{}",
+                        result.line,
+                        ret.rating.status,
+                        ret.rating.guess_cnt,
+                        ret.solution.as_deref().unwrap_or("-")
+                    )
+                }
+                Err(err) => {
+                    error += 1;
+                    eprintln!("line {}: {err}", result.line);
+                    format!("{}\terror\t-\t-", result.line)
+                }
+            };
+
+            if let Err(err) = writeln!(out, "{row}") {
+                write_err.get_or_insert(err);
+            }
+        },
+    )
+    .map_err(|err| CliError::new(Exit::Input, err))?;
+
+    if let Some(err) = write_err.or(out.flush().err()) {
+        return Err(CliError::new(Exit::Failure, err));
+    }
+
+    eprintln!("solved: {solved}, unsolvable: {unsolvable}, timeout: {timeout}, error: {error}");
+    if error > 0 {
+        Err(CliError::new(
+            Exit::Input,
+            format!("읽지 못한 퍼즐 {error}개"),
+        ))
+    } else if timeout > 0 {
+        Err(CliError::new(
+            Exit::Timeout,
+            format!("제한시간 초과 {timeout}개"),
+        ))
+    } else if unsolvable > 0 {
+        Err(CliError::new(
+            Exit::Failure,
+            format!("풀 수 없는 퍼즐 {unsolvable}개"),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// text를 임시 파일에 저장하고 args 뒤에 파일 경로를 붙여 실행한 결과의 종료 코드
+    fn run_exit(args: &str, name: &str, text: &str) -> Exit {
+        let path = std::env::temp_dir().join(format!("sudoku_cli_{}_{name}", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        let args = args
+            .split_whitespace()
+            .map(str::to_string)
+            .chain([path.display().to_string()]);
+        let result = Options::parse(args).and_then(|options| run(&options));
+        std::fs::remove_file(&path).unwrap();
+        result.err().map_or(Exit::Success, |err| err.exit)
+    }
+
+    #[test]
+    fn exit_codes() {
+        // 입력에서 알아낸 크기가 지원하지 않는 크기이면 인자가 아니라 입력의 오류
+        assert_eq!(
+            run_exit("solve", "size.json", r#"{"size":200}"#),
+            Exit::Input
+        );
+        assert_eq!(run_exit("solve", "one.txt", "1"), Exit::Input);
+
+        // 같은 행에 1이 두 번 있는 퍼즐
+        let contradictory = format!("11{}", ".".repeat(79));
+        for command in ["solve", "rate", "punch"] {
+            assert_eq!(
+                run_exit(command, "dup.txt", &contradictory),
+                Exit::Input,
+                "{command}"
+            );
+        }
+        assert_eq!(run_exit("punch", "open.txt", &".".repeat(81)), Exit::Input);
+
+        // 형식은 올바르지만 답이 없는 퍼즐: r1c9에 들어갈 수 있는 값이 없음
+        let unsolvable = format!("12345678.{}9", ".".repeat(71));
+        assert_eq!(
+            run_exit("solve", "unsolvable.txt", &unsolvable),
+            Exit::Failure
+        );
+    }
+}
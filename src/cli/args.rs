@@ -0,0 +1,262 @@
+use super::{CliError, Exit, output::Format};
+use std::{ops::RangeInclusive, path::PathBuf, str::FromStr, time::Duration};
+use sudoku_solver_lib::{any_table::SUPPORTED_SIZES, rating::Difficulty};
+
+pub const USAGE: &str = "\
+사용법: sudoku_solver <명령> [옵션] [파일]
+
+명령:
+  generate    새 퍼즐을 만듭니다 (채우기 + 구멍 뚫기)
+              --count를 지정하면 조건에 맞는 퍼즐을 병렬로 만들어 JSON lines로 출력합니다
+  solve       퍼즐을 풉니다
+  punch       완성된 퍼즐에 구멍을 뚫습니다
+  rate        퍼즐을 풀고 사용한 기법과 난이도를 출력합니다
+  verify      규칙 위반(중복, 합, given 변경)을 모두 출력하고, 빈 칸이 있으면 풀 수 있는지 검사합니다
+  batch       한 줄에 하나씩 적힌 퍼즐을 병렬로 풉니다
+              출력: 줄 번호, 상태(solved, unsolvable, timeout, error), guess 횟수, 정답 (탭으로 구분)
+  play        터미널에서 퍼즐을 풉니다. 파일이 없으면 새 퍼즐을 만듭니다
+              숫자와 노트 입력, 되돌리기, 정답 확인, 힌트를 지원합니다
+  help        이 도움말을 출력합니다
+
+파일을 생략하거나 '-'를 지정하면 표준 입력에서 읽습니다.
+
+옵션:
+  -s, --size <N>            퍼즐 크기 (4, 6, 8, 9, 10, 12, 16, 25, 32, 36, 49, 64, 81, 100). 입력에서 알 수 있으면 생략 가능, 기본 9
+  -l, --layout <파일>       구조 파일 (TableDocument JSON 또는 f-puzzles)
+  -f, --format <형식>       출력 형식: pretty, line, grid, pencilmark, json, fpuzzles, share, svg, dimacs (기본 pretty)
+  -i, --input-format <형식> 입력 형식: auto, line, grid, pencilmark, json, fpuzzles, share (기본 auto)
+      --seed <N>            random seed
+  -t, --timeout <초>        제한시간 (batch는 퍼즐 하나의 제한시간)
+      --solution            generate, punch: 정답도 출력
+      --puzzle <파일>       verify: 답안의 given이 바뀌지 않았는지 비교할 원래 퍼즐. 빈 칸도 오류로 처리
+  -n, --count <N>           generate: 만들 퍼즐 수
+      --clues <범위>        generate: given 개수 (예: 40, 35..45, 35.., ..45)
+      --difficulty <범위>   generate: 난이도 single, naked, blr, cage, guess (예: naked, single..blr)
+      --attempts <N>        generate: 최대 시도 횟수 (기본: 퍼즐 수의 1000배)
+  -h, --help                이 도움말을 출력합니다
+
+종료 코드:
+  0 성공
+  1 풀 수 없는 퍼즐, 검증 실패
+  2 잘못된 인자 (사용법을 함께 출력)
+  3 입력 오류: 읽을 수 없는 파일, 잘못된 형식, 지원하지 않는 크기, 서로 충돌하는 given
+  4 제한시간 초과
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Generate,
+    Solve,
+    Punch,
+    Rate,
+    Verify,
+    Batch,
+    Play,
+    Help,
+}
+
+/// 입력 형식입니다. Auto인 경우 내용을 보고 정합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    Auto,
+    Line,
+    Grid,
+    Pencilmark,
+    Json,
+    FPuzzles,
+    Share,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub command: Command,
+    pub size: Option<usize>,
+    pub layout: Option<PathBuf>,
+    pub format: Format,
+    pub input_format: InputFormat,
+    pub seed: Option<u64>,
+    pub timeout: Duration,
+    pub solution: bool,
+    /// verify: 답안과 비교할 원래 퍼즐
+    pub puzzle: Option<PathBuf>,
+    /// generate: 지정된 경우 여러 퍼즐을 JSON lines로 출력
+    pub count: Option<usize>,
+    pub clues: RangeInclusive<usize>,
+    pub difficulty: RangeInclusive<Difficulty>,
+    /// generate: None인 경우 퍼즐 수의 1000배
+    pub attempts: Option<usize>,
+    /// None인 경우 표준 입력
+    pub input: Option<PathBuf>,
+}
+
+impl Options {
+    /// 명령줄 인자를 읽습니다. 첫 번째 인자(프로그램 이름)는 제외해야 합니다.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut args = args.into_iter();
+        let usage = |message: String| CliError::new(Exit::Usage, message);
+
+        let command = match args.next().as_deref() {
+            Some("generate") => Command::Generate,
+            Some("solve") => Command::Solve,
+            Some("punch") => Command::Punch,
+            Some("rate") => Command::Rate,
+            Some("verify") => Command::Verify,
+            Some("batch") => Command::Batch,
+            Some("play") => Command::Play,
+            Some("help" | "-h" | "--help") => Command::Help,
+            Some(other) => return Err(usage(format!("알 수 없는 명령 '{other}'"))),
+            None => return Err(usage("명령이 없음".to_string())),
+        };
+
+        let mut options = Options {
+            command,
+            size: None,
+            layout: None,
+            format: Format::Pretty,
+            input_format: InputFormat::Auto,
+            seed: None,
+            timeout: Duration::MAX,
+            solution: false,
+            puzzle: None,
+            count: None,
+            clues: 0..=usize::MAX,
+            difficulty: Difficulty::Single..=Difficulty::Guess,
+            attempts: None,
+            input: None,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| usage(format!("{name}에 값이 없음")))
+            };
+
+            match arg.as_str() {
+                "-s" | "--size" => {
+                    let size = parse_num(&value(&arg)?, &arg)?;
+                    if !SUPPORTED_SIZES.contains(&size) {
+                        return Err(usage(format!(
+                            "지원하지 않는 퍼즐 크기: {size} ({SUPPORTED_SIZES:?})"
+                        )));
+                    }
+                    options.size = Some(size);
+                }
+                "-l" | "--layout" => options.layout = Some(PathBuf::from(value(&arg)?)),
+                "-f" | "--format" => {
+                    let format = value(&arg)?;
+                    options.format = Format::parse(&format)
+                        .ok_or_else(|| usage(format!("알 수 없는 출력 형식 '{format}'")))?;
+                }
+                "-i" | "--input-format" => {
+                    let format = value(&arg)?;
+                    options.input_format = parse_input_format(&format)
+                        .ok_or_else(|| usage(format!("알 수 없는 입력 형식 '{format}'")))?;
+                }
+                "--seed" => options.seed = Some(parse_num(&value(&arg)?, &arg)?),
+                "-t" | "--timeout" => {
+                    let secs: f64 = parse_num(&value(&arg)?, &arg)?;
+                    options.timeout = Duration::try_from_secs_f64(secs)
+                        .map_err(|_| usage(format!("잘못된 제한시간 '{secs}'")))?;
+                }
+                "--solution" => options.solution = true,
+                "--puzzle" => options.puzzle = Some(PathBuf::from(value(&arg)?)),
+                "-n" | "--count" => options.count = Some(parse_num(&value(&arg)?, &arg)?),
+                "--clues" => {
+                    options.clues = parse_range(&value(&arg)?, &arg, 0, usize::MAX)?;
+                }
+                "--difficulty" => {
+                    options.difficulty =
+                        parse_range(&value(&arg)?, &arg, Difficulty::Single, Difficulty::Guess)?;
+                }
+                "--attempts" => options.attempts = Some(parse_num(&value(&arg)?, &arg)?),
+                "-h" | "--help" => options.command = Command::Help,
+                "-" => options.input = None,
+                _ if arg.starts_with('-') => {
+                    return Err(usage(format!("알 수 없는 옵션 '{arg}'")));
+                }
+                _ if options.input.is_none() => options.input = Some(PathBuf::from(arg)),
+                _ => return Err(usage(format!("입력 파일은 하나만 지정할 수 있음 '{arg}'"))),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_num<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::new(Exit::Usage, format!("{name}의 값이 숫자가 아님 '{value}'")))
+}
+
+/// "a", "a..b", "a..", "..b" 형식의 범위를 읽습니다. 생략된 끝은 min 또는 max가 됩니다.
+fn parse_range<T: FromStr + Copy>(
+    value: &str,
+    name: &str,
+    min: T,
+    max: T,
+) -> Result<RangeInclusive<T>, CliError> {
+    let parse = |s: &str, default: T| -> Result<T, CliError> {
+        if s.is_empty() {
+            return Ok(default);
+        }
+        s.parse()
+            .map_err(|_| CliError::new(Exit::Usage, format!("{name}의 값이 잘못됨 '{value}'")))
+    };
+
+    match value.split_once("..") {
+        Some((start, end)) => Ok(parse(start, min)?..=parse(end, max)?),
+        None => {
+            let value = parse(value, min)?;
+            Ok(value..=value)
+        }
+    }
+}
+
+fn parse_input_format(s: &str) -> Option<InputFormat> {
+    Some(match s {
+        "auto" => InputFormat::Auto,
+        "line" => InputFormat::Line,
+        "grid" => InputFormat::Grid,
+        "pencilmark" => InputFormat::Pencilmark,
+        "json" => InputFormat::Json,
+        "fpuzzles" => InputFormat::FPuzzles,
+        "share" => InputFormat::Share,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, CliError> {
+        Options::parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn parse_args() {
+        let options = parse("solve -s 16 --format line -t 1.5 puzzle.txt").unwrap();
+        assert_eq!(options.command, Command::Solve);
+        assert_eq!(options.size, Some(16));
+        assert_eq!(options.format, Format::Line);
+        assert_eq!(options.timeout, Duration::from_millis(1500));
+        assert_eq!(options.input, Some(PathBuf::from("puzzle.txt")));
+
+        let options = parse("generate -n 10 --clues 60..65 --difficulty ..blr").unwrap();
+        assert_eq!(options.count, Some(10));
+        assert_eq!(options.clues, 60..=65);
+        assert_eq!(
+            options.difficulty,
+            Difficulty::Single..=Difficulty::BoxLineReduction
+        );
+        assert_eq!(parse("generate --clues 30").unwrap().clues, 30..=30);
+
+        assert_eq!(parse("").unwrap_err().exit, Exit::Usage);
+        assert_eq!(parse("generate --clues a..").unwrap_err().exit, Exit::Usage);
+        assert_eq!(parse("solve --size").unwrap_err().exit, Exit::Usage);
+        assert_eq!(parse("solve --size 200").unwrap_err().exit, Exit::Usage);
+        assert_eq!(parse("solve --format png").unwrap_err().exit, Exit::Usage);
+        assert_eq!(parse("solve a.txt b.txt").unwrap_err().exit, Exit::Usage);
+    }
+}
@@ -0,0 +1,180 @@
+use super::{CliError, Exit, Options, args::InputFormat};
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::Path,
+};
+use sudoku_solver_lib::{
+    io::{
+        ParseError, TextFormat, default_regions, fpuzzles::FPuzzles, grid::parse_grid,
+        json::TableDocument, line::parse_line, share_code,
+    },
+    model::{max_num::MaxNum, table::Table, table_lock::TableLock},
+};
+
+/// 파일 또는 표준 입력(path가 None인 경우)의 내용을 읽습니다.
+pub fn read_input(path: Option<&Path>) -> Result<String, CliError> {
+    let mut text = String::new();
+    let result = match path {
+        Some(path) => std::fs::read_to_string(path).map(|s| text = s),
+        None => std::io::stdin().read_to_string(&mut text).map(|_| ()),
+    };
+
+    result.map_err(|err| {
+        let name = path.map_or("stdin".into(), |p| p.display().to_string());
+        CliError::new(Exit::Input, format!("{name}: {err}"))
+    })?;
+    Ok(text)
+}
+
+/// 파일 또는 표준 입력(path가 None인 경우)을 한 줄씩 읽습니다.
+pub fn open_lines(
+    path: Option<&Path>,
+) -> Result<Box<dyn Iterator<Item = std::io::Result<String>>>, CliError> {
+    let Some(path) = path else {
+        return Ok(Box::new(std::io::stdin().lock().lines()));
+    };
+
+    let file = std::fs::File::open(path)
+        .map_err(|err| CliError::new(Exit::Input, format!("{}: {err}", path.display())))?;
+    Ok(Box::new(BufReader::new(file).lines()))
+}
+
+/// 입력 형식이 Auto인 경우 내용을 보고 형식을 정합니다.
+pub fn resolve_format(text: &str, format: InputFormat) -> InputFormat {
+    if format != InputFormat::Auto {
+        return format;
+    }
+
+    match TextFormat::detect(text) {
+        TextFormat::Line => InputFormat::Line,
+        TextFormat::Grid => InputFormat::Grid,
+        TextFormat::Pencilmark => InputFormat::Pencilmark,
+        TextFormat::Json => InputFormat::Json,
+        TextFormat::FPuzzles => InputFormat::FPuzzles,
+        TextFormat::ShareCode => InputFormat::Share,
+    }
+}
+
+/// 구조 파일 또는 입력으로부터 퍼즐 크기를 알아냅니다. 알 수 없으면 None을 반환합니다.
+pub fn detect_size(options: &Options, text: Option<&str>) -> Result<Option<usize>, CliError> {
+    if let Some(layout) = &options.layout {
+        let layout = read_input(Some(layout))?;
+        return Ok(TextFormat::Json.detect_size(&layout));
+    }
+
+    let Some(text) = text else {
+        return Ok(None);
+    };
+
+    let format = match resolve_format(text, options.input_format) {
+        InputFormat::Json => TextFormat::Json,
+        InputFormat::FPuzzles => TextFormat::FPuzzles,
+        InputFormat::Share => {
+            // 잘못된 공유 코드는 크기를 알 수 없다고 하는 대신 오류를 알려줌
+            return Ok(Some(share_code::decode_size(text).map_err(input_error)?));
+        }
+        InputFormat::Line => TextFormat::Line,
+        InputFormat::Grid => TextFormat::Grid,
+        InputFormat::Pencilmark => TextFormat::Pencilmark,
+        InputFormat::Auto => return Ok(None),
+    };
+
+    Ok(format.detect_size(text))
+}
+
+/// given이 서로 충돌하는 경우도 잘못된 입력이므로 모두 입력 오류로 처리합니다.
+pub fn input_error(err: ParseError) -> CliError {
+    CliError::new(Exit::Input, err)
+}
+
+/// 구조 파일이 있으면 그 구조를, 없으면 기본 구조를 가진 문서를 만듭니다.
+pub fn layout_document<const N: usize>(options: &Options) -> Result<TableDocument, CliError> {
+    let Some(layout) = &options.layout else {
+        return Ok(TableDocument::new_with_regions(N, &default_regions(N)));
+    };
+
+    let text = read_input(Some(layout))?;
+    match resolve_format(&text, InputFormat::Auto) {
+        InputFormat::Json => serde_json::from_str(&text)
+            .map_err(|err| CliError::new(Exit::Input, format!("{}: {err}", layout.display()))),
+        InputFormat::FPuzzles => {
+            let fpuzzles: FPuzzles = serde_json::from_str(&text).map_err(|err| {
+                CliError::new(Exit::Input, format!("{}: {err}", layout.display()))
+            })?;
+            let (doc, unsupported) = fpuzzles.to_document::<N>().map_err(input_error)?;
+            for unsupported in &unsupported {
+                eprintln!("무시됨: {unsupported}");
+            }
+            Ok(doc)
+        }
+        _ => Err(CliError::new(
+            Exit::Input,
+            format!("{}: 구조 파일은 JSON 형식이어야 함", layout.display()),
+        )),
+    }
+}
+
+/// 구조 파일이 있으면 그 구조를, 없으면 기본 구조를 가진 테이블을 만듭니다.
+fn load_layout<const N: usize>(options: &Options) -> Result<TableLock<N>, CliError> {
+    layout_document::<N>(options)?
+        .to_table()
+        .map_err(input_error)
+}
+
+fn from_fpuzzles<const N: usize>(text: &str) -> Result<TableLock<N>, CliError> {
+    let import = Table::new_from_fpuzzles(text).map_err(input_error)?;
+    for unsupported in &import.unsupported {
+        eprintln!("무시됨: {unsupported}");
+    }
+    Ok(import.table)
+}
+
+/// 입력을 읽어 테이블을 만듭니다. 반환값은 (테이블, 입력에서 확정된 값 목록) 입니다.
+///
+/// text가 None인 경우 구조만 가진 빈 테이블을 만듭니다.
+pub fn load_table<const N: usize>(
+    options: &Options,
+    text: Option<&str>,
+) -> Result<(TableLock<N>, Vec<Option<MaxNum<N>>>), CliError> {
+    let table = match text.map(|text| (text, resolve_format(text, options.input_format))) {
+        None => load_layout(options)?,
+        Some((text, InputFormat::Json)) => Table::new_from_json(text).map_err(input_error)?,
+        Some((text, InputFormat::FPuzzles)) => from_fpuzzles(text)?,
+        Some((text, InputFormat::Share)) => {
+            Table::new_from_share_code(text).map_err(input_error)?.table
+        }
+        Some((text, format)) => {
+            let table = load_layout(options)?;
+            match format {
+                InputFormat::Grid => table.set_givens_from_grid(text),
+                InputFormat::Pencilmark => table.set_notes_from_pencilmark(text),
+                _ => table.set_givens_from_line(text),
+            }
+            .map_err(input_error)?;
+            table
+        }
+    };
+
+    let givens = {
+        let read = table.read_lock();
+        read.into_iter().map(|(_, chk)| chk.final_num()).collect()
+    };
+    Ok((table, givens))
+}
+
+/// 답안을 검사하기 위해 입력의 값을 읽습니다. 반환값은 (구조만 가진 테이블, 값 목록) 입니다.
+///
+/// 한 줄, 여러 줄 형식은 같은 zone에 중복된 값이 있어도 읽을 수 있습니다.
+pub fn load_values<const N: usize>(
+    options: &Options,
+    text: &str,
+) -> Result<(TableLock<N>, Vec<Option<MaxNum<N>>>), CliError> {
+    let values = match resolve_format(text, options.input_format) {
+        InputFormat::Line => parse_line::<N>(text.trim()),
+        InputFormat::Grid => parse_grid::<N>(text),
+        _ => return load_table::<N>(options, Some(text)),
+    }
+    .map_err(input_error)?;
+
+    Ok((load_layout(options)?, values))
+}
@@ -0,0 +1,76 @@
+use super::{CliError, Exit};
+use sudoku_solver_lib::{
+    io::{ParseError, share_code, svg::SvgOptions},
+    model::{max_num::MaxNum, table_lock::TableLock},
+};
+
+/// 출력 형식입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// TableLock의 Display 출력
+    Pretty,
+    Line,
+    Grid,
+    Pencilmark,
+    Json,
+    FPuzzles,
+    Share,
+    Svg,
+    Dimacs,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "pretty" => Format::Pretty,
+            "line" => Format::Line,
+            "grid" => Format::Grid,
+            "pencilmark" => Format::Pencilmark,
+            "json" => Format::Json,
+            "fpuzzles" => Format::FPuzzles,
+            "share" => Format::Share,
+            "svg" => Format::Svg,
+            "dimacs" => Format::Dimacs,
+            _ => return None,
+        })
+    }
+}
+
+/// 출력 형식이 테이블 구조를 지원하지 않는 것은 명령줄 인자의 문제이므로 Usage로 처리합니다.
+pub fn output_error(err: ParseError) -> CliError {
+    CliError::new(Exit::Usage, err)
+}
+
+/// 테이블을 형식에 맞게 출력합니다. 항상 줄바꿈으로 끝납니다.
+///
+/// givens는 svg에서 given을 구분하는 데 사용하며, solution은 share 형식에 정답을 포함할 때 사용합니다.
+/// 형식이 테이블 구조를 지원하지 않는 경우 (예: 사무라이 퍼즐의 fpuzzles, share 출력) 오류를 반환합니다.
+pub fn render<const N: usize>(
+    table: &TableLock<N>,
+    givens: &[Option<MaxNum<N>>],
+    solution: Option<&[MaxNum<N>]>,
+    format: Format,
+) -> Result<String, ParseError> {
+    let mut ret = match format {
+        Format::Pretty => table.to_string(),
+        Format::Line => table.to_line(),
+        Format::Grid => table.to_grid_string(),
+        Format::Pencilmark => table.to_pencilmark_string(),
+        Format::Json => table.to_json(),
+        Format::FPuzzles => table.to_fpuzzles()?,
+        Format::Share => {
+            let final_nums: Vec<Option<MaxNum<N>>> = {
+                let read = table.read_lock();
+                read.into_iter().map(|(_, chk)| chk.final_num()).collect()
+            };
+            share_code::encode(table, &final_nums, solution)?
+        }
+        Format::Svg => table.to_svg_with_givens(givens, &SvgOptions::default()),
+        Format::Dimacs => table.to_dimacs(),
+    };
+
+    if !ret.ends_with('\n') {
+        ret.push('\n');
+    }
+    Ok(ret)
+}
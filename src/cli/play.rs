@@ -0,0 +1,288 @@
+use super::{CliError, CliResult, Exit};
+use std::io::{BufRead, Write};
+use sudoku_solver_lib::{
+    io::line::parse_cell_token,
+    model::{cell_pos::CellPos, max_num::MaxNum, symbol_set::SymbolSet},
+    play::{Play, PlayError},
+};
+
+pub const PLAY_HELP: &str = "\
+명령 (칸은 r<행>c<열> 형식, 예: r3c5):
+  r3c5 7          숫자 입력 (r3c5=7 도 가능)
+  r3c5 .          칸 비우기
+  note r3c5 1 2   노트 넣기/빼기 (n r3c5 12 도 가능)
+  undo, redo      되돌리기, 다시하기
+  check           정답과 다른 입력 확인
+  hint            다음 풀이 단계 (적용하지 않음)
+  show, notes     퍼즐, 노트 출력
+  help            이 도움말
+  quit            종료
+";
+
+/// 플레이어가 입력한 한 줄의 명령입니다. 좌표는 (x, y)이며 0부터 시작합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PlayCommand<const N: usize> {
+    Set(usize, usize, Option<MaxNum<N>>),
+    Note(usize, usize, Vec<MaxNum<N>>),
+    Undo,
+    Redo,
+    Check,
+    Hint,
+    Show,
+    Notes,
+    Help,
+    Quit,
+}
+
+/// 표준 입력에서 명령을 한 줄씩 읽으며 퍼즐을 풉니다.
+pub fn play_loop<const N: usize>(mut play: Play<N>) -> CliResult {
+    if play.get_solution().is_none() {
+        eprintln!("정답을 구하지 못해 check를 사용할 수 없음");
+    }
+
+    println!("{play}");
+    println!("{PLAY_HELP}");
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        std::io::stdout()
+            .flush()
+            .map_err(|err| CliError::new(Exit::Failure, err))?;
+
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let line = line.map_err(|err| CliError::new(Exit::Input, err))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let command = match parse_command(&line, play.get_table().symbols()) {
+            Ok(command) => command,
+            Err(message) => {
+                println!("{message}");
+                continue;
+            }
+        };
+
+        match run_command(&mut play, command) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(err) => println!("{err}"),
+        }
+
+        if play.is_solved() {
+            println!("{play}");
+            println!("완성!");
+            return Ok(());
+        }
+    }
+}
+
+/// 명령을 실행합니다. 종료해야 하는 경우 false를 반환합니다.
+fn run_command<const N: usize>(
+    play: &mut Play<N>,
+    command: PlayCommand<N>,
+) -> Result<bool, PlayError> {
+    match command {
+        PlayCommand::Set(x, y, value) => {
+            play.set_value(x, y, value)?;
+            println!("{play}");
+        }
+        PlayCommand::Note(x, y, nums) => {
+            for num in nums {
+                play.toggle_note(x, y, num)?;
+            }
+            print!("{}", play.note_fmt());
+        }
+        PlayCommand::Undo => {
+            if play.undo() {
+                println!("{play}");
+            } else {
+                println!("되돌릴 입력이 없음");
+            }
+        }
+        PlayCommand::Redo => {
+            if play.redo() {
+                println!("{play}");
+            } else {
+                println!("다시 적용할 입력이 없음");
+            }
+        }
+        PlayCommand::Check => {
+            let mistakes = play.mistakes()?;
+            if mistakes.is_empty() {
+                println!("틀린 곳 없음");
+            } else {
+                let cells: Vec<String> = mistakes
+                    .iter()
+                    .map(|&pos| CellPos::from(pos).to_string())
+                    .collect();
+                println!("틀린 곳: {}", cells.join(", "));
+            }
+        }
+        PlayCommand::Hint => match play.hint()? {
+            Some(hint) => println!("{hint}"),
+            None => println!("적용할 수 있는 기법이 없음"),
+        },
+        PlayCommand::Show => println!("{play}"),
+        PlayCommand::Notes => print!("{}", play.note_fmt()),
+        PlayCommand::Help => print!("{PLAY_HELP}"),
+        PlayCommand::Quit => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// 숫자는 테이블의 기호 (SymbolSet)로 읽습니다.
+fn parse_command<const N: usize>(
+    line: &str,
+    symbols: &SymbolSet<N>,
+) -> Result<PlayCommand<N>, String> {
+    let line = line.trim().replace('=', " ");
+    let mut tokens = line.split_whitespace();
+    let Some(first) = tokens.next() else {
+        return Err("명령이 없음".to_string());
+    };
+    let rest: Vec<&str> = tokens.collect();
+
+    let command = match first.to_ascii_lowercase().as_str() {
+        "undo" | "u" => PlayCommand::Undo,
+        "redo" => PlayCommand::Redo,
+        "check" => PlayCommand::Check,
+        "hint" => PlayCommand::Hint,
+        "show" => PlayCommand::Show,
+        "notes" => PlayCommand::Notes,
+        "help" | "?" => PlayCommand::Help,
+        "quit" | "q" | "exit" => PlayCommand::Quit,
+        "note" | "n" => {
+            let Some((cell, nums)) = rest.split_first() else {
+                return Err("노트를 입력할 칸이 없음".to_string());
+            };
+            let (x, y) = parse_cell(cell)?;
+            let nums = nums
+                .iter()
+                .map(|token| parse_nums(symbols, token))
+                .collect::<Result<Vec<_>, _>>()?
+                .concat();
+            if nums.is_empty() {
+                return Err("노트에 넣을 숫자가 없음".to_string());
+            }
+            PlayCommand::Note(x, y, nums)
+        }
+        _ => {
+            let (x, y) = parse_cell(first)?;
+            let value = match rest.as_slice() {
+                [value] => parse_cell_token(symbols, value)
+                    .ok_or_else(|| format!("사용할 수 없는 숫자 '{value}'"))?,
+                _ => return Err(format!("알 수 없는 명령 '{line}'. help로 도움말 확인")),
+            };
+            PlayCommand::Set(x, y, value)
+        }
+    };
+
+    Ok(command)
+}
+
+/// r3c5 형식의 칸을 읽어 캔버스 좌표 (x, y)를 반환합니다.
+/// 퍼즐에 없는 칸인지는 Play가 검사합니다. (PlayError::InvalidCell)
+fn parse_cell(s: &str) -> Result<(usize, usize), String> {
+    let pos: CellPos = s
+        .parse()
+        .map_err(|_| format!("잘못된 칸 '{s}'. r<행>c<열> 형식이어야 함"))?;
+    Ok(pos.into())
+}
+
+/// 노트 토큰을 읽습니다. 토큰 전체가 기호가 아니고 모든 기호가 한 글자인 경우 "123"과 같이 문자마다 읽습니다.
+fn parse_nums<const N: usize>(
+    symbols: &SymbolSet<N>,
+    token: &str,
+) -> Result<Vec<MaxNum<N>>, String> {
+    if let Some(num) = symbols.parse(token) {
+        return Ok(vec![num]);
+    }
+
+    let err = || format!("사용할 수 없는 숫자 '{token}'");
+    if !symbols.is_single_char() {
+        return Err(err());
+    }
+    token
+        .chars()
+        .map(|c| symbols.parse(c.encode_utf8(&mut [0; 4])).ok_or_else(err))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_play_command() {
+        let n = |c: char| MaxNum::<9>::from_char(c).unwrap();
+        let symbols = SymbolSet::<9>::default();
+        let parse = |line: &str| parse_command(line, &symbols);
+
+        assert_eq!(parse("r3c5 7"), Ok(PlayCommand::Set(4, 2, Some(n('7')))));
+        assert_eq!(parse("R1C9=."), Ok(PlayCommand::Set(8, 0, None)));
+        assert_eq!(parse("r1c9 0"), Ok(PlayCommand::Set(8, 0, None)));
+        assert_eq!(
+            parse("note r2c2 1 23"),
+            Ok(PlayCommand::Note(1, 1, vec![n('1'), n('2'), n('3')]))
+        );
+        assert_eq!(parse("undo"), Ok(PlayCommand::Undo));
+        // 사무라이 등 넓은 캔버스의 칸도 읽으며, 퍼즐에 없는 칸인지는 Play가 검사함
+        assert_eq!(parse("r1c13 1"), Ok(PlayCommand::Set(12, 0, Some(n('1')))));
+        assert!(parse("r0c1 1").is_err());
+        assert!(parse("r1c1 A").is_err());
+        assert!(parse("r1c1").is_err());
+    }
+
+    #[test]
+    fn parse_play_command_symbols() {
+        // 워드도쿠: 대소문자 구분 없이 읽음
+        let word = SymbolSet::<9>::from_chars("WORDPUZLE");
+        assert_eq!(
+            parse_command("r1c1 z", &word),
+            Ok(PlayCommand::Set(0, 0, Some(MaxNum::new(6))))
+        );
+        assert_eq!(
+            parse_command("note r1c1 WO", &word),
+            Ok(PlayCommand::Note(
+                0,
+                0,
+                vec![MaxNum::new(0), MaxNum::new(1)]
+            ))
+        );
+        assert!(parse_command("r1c1 1", &word).is_err());
+
+        // 0이 기호인 경우 빈 칸이 아닌 값으로 읽음
+        let zero = SymbolSet::<10>::from_chars("0123456789");
+        assert_eq!(
+            parse_command("r1c1 0", &zero),
+            Ok(PlayCommand::Set(0, 0, Some(MaxNum::new(0))))
+        );
+        assert_eq!(
+            parse_command("r1c1 .", &zero),
+            Ok(PlayCommand::Set(0, 0, None))
+        );
+
+        // 여러 글자 기호는 토큰 단위로 읽음
+        let multi = SymbolSet::<16>::new((1..=16).map(|v| v.to_string()));
+        assert_eq!(
+            parse_command("r1c1 16", &multi),
+            Ok(PlayCommand::Set(0, 0, Some(MaxNum::new(15))))
+        );
+        assert_eq!(
+            parse_command("note r1c1 1 12", &multi),
+            Ok(PlayCommand::Note(
+                0,
+                0,
+                vec![MaxNum::new(0), MaxNum::new(11)]
+            ))
+        );
+        assert!(parse_command("note r1c1 17", &multi).is_err());
+    }
+}
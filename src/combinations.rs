@@ -75,7 +75,7 @@ fn make_comb_str(mut comb_iter: Combination<usize>) -> String {
     while let Some(arr) = comb_iter.next_comb() {
         for c in arr {
             print_str.push_str(c.to_string().as_str());
-            print_str.push_str(",");
+            print_str.push(',');
         }
         assert_eq!(print_str.pop().unwrap(), ',');
         print_str.push('\t');
@@ -100,7 +100,7 @@ fn combination_test() {
     assert_eq!(make_comb_str(Combination::new(&v, 5)), "1,2,3,4,5");
     assert_eq!(make_comb_str(Combination::new(&v, 0)), "");
     assert_eq!(make_comb_str(Combination::new(&v, 6)), "");
-    assert_eq!(make_comb_str(Combination::new(&vec![], 0)), "");
-    assert_eq!(make_comb_str(Combination::new(&vec![], 5)), "");
-    assert_eq!(make_comb_str(Combination::new(&vec![1], 1)), "1");
+    assert_eq!(make_comb_str(Combination::new(&[], 0)), "");
+    assert_eq!(make_comb_str(Combination::new(&[], 5)), "");
+    assert_eq!(make_comb_str(Combination::new(&[1], 1)), "1");
 }
@@ -0,0 +1,211 @@
+use crate::{io::ParseError, model::table_builder::BuildError};
+use std::fmt::{Display, Formatter};
+
+/// 라이브러리 전체에서 사용하는 오류입니다. 좌표는 (x, y)이며 0부터 시작합니다.
+///
+/// panic이 발생할 수 있는 함수는 같은 검사를 오류로 반환하는 try_ 함수를 함께 제공합니다.
+/// ParseError, BuildError는 From으로 변환되므로 ?로 함께 사용할 수 있습니다.
+/// 구조의 zone 오류(DuplicateZone, InvalidZoneSize)는 입력에서 읽은 구조와 같은 ParseError로 반환합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SudokuError {
+    /// 퍼즐 입력을 읽는 중 발생한 오류
+    Parse(ParseError),
+
+    /// TableBuilder로 구조를 만드는 중 발생한 오류
+    Build(BuildError),
+
+    /// 0부터 size - 1 사이가 아닌 값
+    OutOfRange { value: usize, size: usize },
+
+    /// cell의 개수가 N * N이 아님
+    InvalidCellCount { expected: usize, found: usize },
+
+    /// cell이 순서대로 들어오지 않음
+    InvalidCellOrder { x: usize, y: usize },
+
+    /// 박스의 크기가 퍼즐의 크기와 맞지 않음
+    InvalidBoxSize {
+        box_width: usize,
+        box_height: usize,
+        size: usize,
+    },
+
+    /// 노트에 없는 값으로 guess함. value는 테이블에 설정된 기호입니다.
+    ImpossibleGuess { x: usize, y: usize, value: String },
+
+    /// 다른 테이블에 속한 cell을 사용함
+    CellNotInTable { x: usize, y: usize },
+
+    /// 값이 확정되지 않은 cell이 있어 완성된 테이블이 필요한 작업을 할 수 없음
+    IncompleteTable { x: usize, y: usize },
+
+    /// 다른 테이블에서 만든 snapshot을 복원하려 함
+    SnapshotMismatch,
+
+    /// given인 cell의 값을 바꾸려 함
+    GivenCell { x: usize, y: usize },
+
+    /// SymbolSet의 기호 개수가 퍼즐 크기와 다름
+    InvalidSymbolCount { expected: usize, found: usize },
+
+    /// 비어있거나 공백, 예약된 문자를 포함한 기호
+    InvalidSymbol { symbol: String },
+
+    /// SymbolSet에 같은 기호가 두 번 이상 들어있음
+    DuplicateSymbol { symbol: String },
+
+    /// "Row 3" 과 같은 zone 종류 표기가 아님
+    InvalidZoneKind { kind: String },
+
+    /// "r3c7" 과 같은 cell 표기가 아님
+    InvalidCellNotation { notation: String },
+
+    /// 다른 스레드가 lock을 가진 채 panic하여 테이블의 상태를 신뢰할 수 없음
+    LockPoisoned,
+
+    /// 무작위 seed를 얻지 못함
+    RandomSeed { message: String },
+}
+
+impl Display for SudokuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SudokuError::Parse(err) => write!(f, "{err}"),
+            SudokuError::Build(err) => write!(f, "{err}"),
+            SudokuError::OutOfRange { value, size } => {
+                write!(f, "범위를 벗어난 값. value: {value}, size: {size}")
+            }
+            SudokuError::InvalidCellCount { expected, found } => write!(
+                f,
+                "cell 개수가 맞지 않음. expected: {expected}, found: {found}"
+            ),
+            SudokuError::InvalidCellOrder { x, y } => {
+                write!(f, "Cell은 순서대로 들어와야 함. ({x}, {y})")
+            }
+            SudokuError::InvalidBoxSize {
+                box_width,
+                box_height,
+                size,
+            } => write!(
+                f,
+                "박스의 크기가 퍼즐의 크기와 맞지 않음. box: {box_width}X{box_height}, size: {size}"
+            ),
+            SudokuError::ImpossibleGuess { x, y, value } => {
+                write!(f, "불가능한 값으로의 GUESS! '{value}' ({x}, {y})")
+            }
+            SudokuError::CellNotInTable { x, y } => {
+                write!(f, "테이블에 속하지 않은 cell. ({x}, {y})")
+            }
+            SudokuError::IncompleteTable { x, y } => {
+                write!(f, "값이 확정되지 않은 cell이 있음. ({x}, {y})")
+            }
+            SudokuError::SnapshotMismatch => write!(f, "다른 테이블에서 만든 snapshot임"),
+            SudokuError::GivenCell { x, y } => {
+                write!(f, "given cell의 값은 바꿀 수 없음. ({x}, {y})")
+            }
+            SudokuError::InvalidSymbolCount { expected, found } => write!(
+                f,
+                "기호 개수가 맞지 않음. expected: {expected}, found: {found}"
+            ),
+            SudokuError::InvalidSymbol { symbol } => {
+                write!(f, "기호로 사용할 수 없는 문자열 '{symbol}'")
+            }
+            SudokuError::DuplicateSymbol { symbol } => write!(f, "중복되는 기호 '{symbol}'"),
+            SudokuError::InvalidZoneKind { kind } => {
+                write!(f, "zone 종류 표기가 올바르지 않음 '{kind}'")
+            }
+            SudokuError::InvalidCellNotation { notation } => {
+                write!(f, "cell 표기가 올바르지 않음 '{notation}'")
+            }
+            SudokuError::LockPoisoned => write!(f, "테이블의 lock이 poison 상태임"),
+            SudokuError::RandomSeed { message } => {
+                write!(f, "무작위 seed를 얻지 못함. {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SudokuError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SudokuError::Parse(err) => Some(err),
+            SudokuError::Build(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for SudokuError {
+    fn from(err: ParseError) -> Self {
+        SudokuError::Parse(err)
+    }
+}
+
+impl From<BuildError> for SudokuError {
+    fn from(err: BuildError) -> Self {
+        SudokuError::Build(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::{max_num::MaxNum, table::Table, zone_cache::ZoneCache},
+        solver::Solver,
+    };
+
+    #[test]
+    fn try_guess_punch_and_lock() {
+        let t = Table::new_default_9_from_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        let other = Table::new_default_9();
+        let one = MaxNum::<9>::from_char('1').unwrap();
+
+        let mut solver = Solver::new_with_cache(&t, ZoneCache::new(&t));
+        assert_eq!(
+            solver.try_guess_mut_something(t.write_lock(), &t[0], one),
+            Err(SudokuError::ImpossibleGuess {
+                x: 0,
+                y: 0,
+                value: "1".to_string()
+            })
+        );
+        assert_eq!(
+            solver.try_guess_mut_something(t.write_lock(), &other[1], one),
+            Err(SudokuError::CellNotInTable { x: 1, y: 0 })
+        );
+
+        let mut punch = solver.into_punch();
+        assert_eq!(
+            punch.try_punch_all(),
+            Err(SudokuError::IncompleteTable { x: 2, y: 0 })
+        );
+
+        // 사무라이 퍼즐의 첫 줄은 왼쪽 위 격자의 9칸 다음에 오른쪽 위 격자의 (12, 0)이 옴
+        let samurai = Table::new_samurai();
+        samurai
+            .set_givens_from_line(&format!("534678912{}", ".".repeat(samurai.len() - 9)))
+            .unwrap();
+        let mut punch = Solver::new_with_cache(&samurai, ZoneCache::new(&samurai)).into_punch();
+        assert_eq!(
+            punch.try_punch_all(),
+            Err(SudokuError::IncompleteTable { x: 12, y: 0 })
+        );
+
+        let poisoned = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _write = t.write_lock();
+            panic!("lock을 가진 채 panic");
+        }));
+        assert!(poisoned.is_err());
+        assert!(matches!(t.try_read_lock(), Err(SudokuError::LockPoisoned)));
+        assert!(matches!(t.try_write_lock(), Err(SudokuError::LockPoisoned)));
+        // poison 상태를 무시하지 않고 panic함
+        let read = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            drop(t.read_lock());
+        }));
+        assert!(read.is_err());
+    }
+}
@@ -0,0 +1,339 @@
+pub mod booklet;
+pub mod dimacs;
+pub mod fpuzzles;
+pub mod grid;
+pub mod json;
+pub mod line;
+pub mod share_code;
+pub mod svg;
+
+use crate::model::{
+    array_note::ArrayNote,
+    cell::Cell,
+    index_key_map::IndexKeyMap,
+    max_num::{CASE_INSENSITIVE_LEN, CHAR_ARR, MaxNum},
+    table_lock::TableLock,
+    zone::Zone,
+};
+use std::fmt::{Display, Formatter};
+
+/// 퍼즐 텍스트를 읽는 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// 입력의 칸 개수가 퍼즐 크기와 맞지 않음
+    InvalidLength { expected: usize, found: usize },
+
+    /// 퍼즐에서 사용할 수 없는 문자 또는 기호. line, column은 1부터 시작합니다.
+    InvalidSymbol {
+        line: usize,
+        column: usize,
+        symbol: String,
+    },
+
+    /// 형식에 맞지 않는 줄. line은 1부터 시작합니다.
+    MalformedLine { line: usize },
+
+    /// JSON 문법 또는 필드 오류
+    Json { message: String },
+
+    /// 문서의 퍼즐 크기가 N과 다름
+    SizeMismatch { expected: usize, found: usize },
+
+    /// 좌표가 퍼즐 범위를 벗어났거나 중복된 cell
+    InvalidCell { x: usize, y: usize },
+
+    /// 문서에 존재하지 않는 cell
+    MissingCell { x: usize, y: usize },
+
+    /// 한 cell에 같은 zone이 두 번 이상 지정됨
+    DuplicateZone { x: usize, y: usize, zone: u16 },
+
+    /// 같은 zone 번호가 cell마다 다른 종류나 합으로 지정됨
+    InconsistentZone { zone: u16 },
+
+    /// Unique zone에 속한 cell의 개수가 퍼즐 크기와 다름
+    InvalidZoneSize {
+        zone: u16,
+        expected: usize,
+        found: usize,
+    },
+
+    /// 1부터 N 사이가 아닌 값
+    InvalidValue { x: usize, y: usize, value: usize },
+
+    /// 해석할 수 없는 cell 표기 (예: R1C1)
+    InvalidCellReference { reference: String },
+
+    /// 공유 코드의 내용이 올바르지 않음
+    InvalidShareCode { reason: &'static str },
+
+    /// 지원하지 않는 공유 코드 버전
+    UnsupportedVersion { version: u8 },
+
+    /// SAT solver가 해가 없다고 응답함
+    Unsatisfiable,
+
+    /// SAT 모델에서 cell의 값이 정확히 하나로 정해지지 않음
+    InvalidModel { x: usize, y: usize },
+
+    /// 지원하지 않는 퍼즐 크기
+    UnsupportedSize { size: usize },
+
+    /// 형식이 지원하지 않는 테이블 구조 (예: f-puzzles, 공유 코드로 여러 격자가 겹친 퍼즐을 출력)
+    UnsupportedLayout { format: &'static str },
+
+    /// 같은 Zone에 속한 두 given이 같은 값을 가짐
+    ContradictoryGivens {
+        x: usize,
+        y: usize,
+        other_x: usize,
+        other_y: usize,
+        symbol: String,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidLength { expected, found } => {
+                write!(
+                    f,
+                    "칸 개수가 맞지 않음. expected: {expected}, found: {found}"
+                )
+            }
+            ParseError::InvalidSymbol {
+                line,
+                column,
+                symbol,
+            } => write!(
+                f,
+                "사용할 수 없는 문자 '{symbol}'. line: {line}, column: {column}"
+            ),
+            ParseError::MalformedLine { line } => write!(f, "형식에 맞지 않는 줄. line: {line}"),
+            ParseError::Json { message } => write!(f, "JSON 오류: {message}"),
+            ParseError::SizeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "퍼즐 크기가 맞지 않음. expected: {expected}, found: {found}"
+                )
+            }
+            ParseError::InvalidCell { x, y } => {
+                write!(f, "범위를 벗어났거나 중복된 cell. ({x}, {y})")
+            }
+            ParseError::MissingCell { x, y } => write!(f, "cell이 없음. ({x}, {y})"),
+            ParseError::DuplicateZone { x, y, zone } => {
+                write!(f, "cell에 중복되는 zone이 있음. ({x}, {y}), zone: {zone}")
+            }
+            ParseError::InconsistentZone { zone } => {
+                write!(f, "zone의 종류가 cell마다 다름. zone: {zone}")
+            }
+            ParseError::InvalidZoneSize {
+                zone,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Unique zone의 cell 개수가 맞지 않음. zone: {zone}, expected: {expected}, found: {found}"
+            ),
+            ParseError::InvalidValue { x, y, value } => {
+                write!(f, "범위를 벗어난 값 {value}. ({x}, {y})")
+            }
+            ParseError::InvalidCellReference { reference } => {
+                write!(f, "잘못된 cell 표기 '{reference}'")
+            }
+            ParseError::InvalidShareCode { reason } => write!(f, "잘못된 공유 코드: {reason}"),
+            ParseError::UnsupportedVersion { version } => {
+                write!(f, "지원하지 않는 공유 코드 버전. version: {version}")
+            }
+            ParseError::Unsatisfiable => write!(f, "해가 없는 퍼즐"),
+            ParseError::InvalidModel { x, y } => {
+                write!(
+                    f,
+                    "SAT 모델에서 cell의 값이 하나로 정해지지 않음. ({x}, {y})"
+                )
+            }
+            ParseError::UnsupportedSize { size } => {
+                write!(f, "지원하지 않는 퍼즐 크기. size: {size}")
+            }
+            ParseError::UnsupportedLayout { format } => {
+                write!(f, "{format} 형식은 N X N 격자만 지원함")
+            }
+            ParseError::ContradictoryGivens {
+                x,
+                y,
+                other_x,
+                other_y,
+                symbol,
+            } => write!(
+                f,
+                "같은 zone에 '{symbol}'이 중복됨. ({x}, {y}) - ({other_x}, {other_y})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// 기본 박스 크기 (가로, 세로)를 반환합니다. f-puzzles와 같은 규칙을 사용합니다.
+///
+/// 세로는 √N 이하인 약수 중 가장 큰 값입니다. 예를 들어 6X6은 3X2, 12X12는 4X3 박스를 가집니다.
+#[must_use]
+pub fn default_box_size(n: usize) -> (usize, usize) {
+    let height = (1..=n)
+        .take_while(|h| h * h <= n)
+        .filter(|h| n.is_multiple_of(*h))
+        .last()
+        .unwrap_or(1);
+    (n / height, height)
+}
+
+/// 기본 박스 모양의 cell별 region 번호 (0부터 시작)를 반환합니다.
+#[must_use]
+pub fn default_regions(n: usize) -> Vec<usize> {
+    let (box_w, box_h) = default_box_size(n);
+    (0..n * n)
+        .map(|index| {
+            let (x, y) = (index % n, index / n);
+            x / box_w + (y / box_h) * (n / box_w)
+        })
+        .collect()
+}
+
+/// 퍼즐 텍스트의 형식입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFormat {
+    Line,
+    Grid,
+    Pencilmark,
+    Json,
+    FPuzzles,
+    ShareCode,
+}
+
+impl TextFormat {
+    /// 내용을 보고 형식을 추측합니다.
+    #[must_use]
+    pub fn detect(text: &str) -> Self {
+        let trimmed = text.trim();
+        if trimmed.starts_with('{') {
+            let has_cells = serde_json::from_str::<serde_json::Value>(trimmed)
+                .is_ok_and(|value| value.get("cells").is_some());
+            return if has_cells {
+                TextFormat::Json
+            } else {
+                TextFormat::FPuzzles
+            };
+        }
+
+        let lines = trimmed.lines().filter(|l| !l.trim().is_empty()).count();
+        if lines <= 1 {
+            // 길이가 제곱수인 공유 코드도 있으므로, 한 줄 형식의 문자만 있는지를 먼저 봄
+            let is_line = square_root(trimmed.chars().count())
+                .is_some_and(|n| trimmed.chars().all(|c| is_line_char(c, n)));
+
+            return if !is_line && share_code::decode_size(trimmed).is_ok() {
+                TextFormat::ShareCode
+            } else {
+                TextFormat::Line
+            };
+        }
+
+        let is_pencilmark = trimmed
+            .split_whitespace()
+            .filter(|token| !token.chars().all(|c| grid::SEPARATOR_CHARS.contains(&c)))
+            .any(|token| token.chars().count() > 1);
+        if is_pencilmark {
+            TextFormat::Pencilmark
+        } else {
+            TextFormat::Grid
+        }
+    }
+
+    /// 텍스트에 적힌 퍼즐의 크기를 알아냅니다. 알 수 없으면 None을 반환합니다.
+    #[must_use]
+    pub fn detect_size(&self, text: &str) -> Option<usize> {
+        match self {
+            TextFormat::Json | TextFormat::FPuzzles => {
+                serde_json::from_str::<serde_json::Value>(text)
+                    .ok()?
+                    .get("size")?
+                    .as_u64()
+                    .map(|size| size as usize)
+            }
+            TextFormat::ShareCode => share_code::decode_size(text).ok(),
+            TextFormat::Line => {
+                // 여러 글자 기호를 쓰는 크기는 칸을 공백으로 구분함
+                let text = text.trim();
+                if text.contains(char::is_whitespace) {
+                    square_root(text.split_whitespace().count())
+                } else {
+                    square_root(text.chars().count())
+                }
+            }
+            TextFormat::Grid | TextFormat::Pencilmark => Some(
+                text.lines()
+                    .filter(|line| {
+                        line.chars()
+                            .any(|c| !c.is_whitespace() && !grid::SEPARATOR_CHARS.contains(&c))
+                    })
+                    .count(),
+            ),
+        }
+    }
+}
+
+/// serde의 skip_serializing_if에 쓰기 위한 함수입니다.
+pub(crate) fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+/// c가 크기 n의 한 줄 형식에서 빈 칸 또는 기본 기호인지 여부
+fn is_line_char(c: char, n: usize) -> bool {
+    let c = if n <= CASE_INSENSITIVE_LEN {
+        c.to_ascii_uppercase()
+    } else {
+        c
+    };
+    line::BLANK_CHARS.contains(&c) || CHAR_ARR.iter().take(n).any(|&ch| ch == c)
+}
+
+/// n이 제곱수인 경우 제곱근을 반환합니다.
+fn square_root(n: usize) -> Option<usize> {
+    (1..=n).take_while(|r| r * r <= n).find(|r| r * r == n)
+}
+
+/// 값이 중복될 수 없는 Zone (Unique zone, killer cage) 내에 중복된 given이 있는지 검사합니다. givens의 순서는 cell의 순서와 같아야 합니다.
+pub(crate) fn check_givens<const N: usize>(
+    table: &TableLock<N>,
+    givens: &[Option<MaxNum<N>>],
+) -> Result<(), ParseError> {
+    let mut seen: IndexKeyMap<Zone, ArrayNote<Option<&Cell<N>>, N>> = IndexKeyMap::new();
+
+    for (cell, given) in table.iter().zip(givens) {
+        let Some(value) = *given else {
+            continue;
+        };
+
+        for z in cell.get_zone() {
+            if !z.get_zone_type().is_distinct() {
+                continue;
+            }
+
+            let zone_seen = seen.entry_or_insert_with(*z, || ArrayNote::new([None; N]));
+            if let Some(other) = zone_seen[value] {
+                let (x, y) = cell.get_coordinate();
+                let (other_x, other_y) = other.get_coordinate();
+                return Err(ParseError::ContradictoryGivens {
+                    x,
+                    y,
+                    other_x,
+                    other_y,
+                    symbol: table.symbols().get(value).to_string(),
+                });
+            }
+            zone_seen[value] = Some(cell);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use super::svg::{SvgOptions, escape_html};
+use crate::model::max_num::MaxNum;
+use crate::punch::Punch;
+use std::fmt::Write;
+
+/// 책자에 들어가는 퍼즐 하나입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookletPuzzle {
+    pub title: String,
+    /// 퍼즐을 만들 때 사용한 시드
+    pub seed: Option<u64>,
+    /// 난이도 표시 (예: "Single", "Naked", "Guess 3")
+    pub difficulty: String,
+    /// 문제 이미지
+    pub puzzle_svg: String,
+    /// 정답 이미지. None이면 정답지에서 제외됩니다.
+    pub answer_svg: Option<String>,
+}
+
+impl BookletPuzzle {
+    /// 구멍을 뚫은 퍼즐로부터 문제와 정답 이미지를 만듭니다.
+    ///
+    /// 남아있는 fixed_final_num이 문제가 되며, 정답은 구멍을 뚫기 전의 완성된 퍼즐을 사용합니다.
+    #[must_use]
+    pub fn from_punch<const N: usize>(
+        title: impl Into<String>,
+        seed: Option<u64>,
+        difficulty: impl Into<String>,
+        punch: &Punch<'_, N>,
+    ) -> Self {
+        let table = punch.get_table();
+        let givens: Vec<Option<MaxNum<N>>> = {
+            let read = table.read_lock();
+            read.into_iter()
+                .map(|(_, chk)| chk.fixed_final_num())
+                .collect()
+        };
+
+        let options = SvgOptions::default();
+        let puzzle_svg = table.to_svg_with_givens(
+            &givens,
+            &SvgOptions {
+                show_solved: false,
+                ..options
+            },
+        );
+        let answer_svg = table.to_svg_with_solution(&givens, punch.get_solution(), &options);
+
+        BookletPuzzle {
+            title: title.into(),
+            seed,
+            difficulty: difficulty.into(),
+            puzzle_svg,
+            answer_svg: Some(answer_svg),
+        }
+    }
+}
+
+/// 여러 퍼즐을 인쇄용 페이지로 배치한 책자입니다.
+///
+/// 문제 페이지 뒤에 정답지 페이지가 이어지는 하나의 HTML 파일로 출력합니다.
+/// 외부 파일이나 스크립트를 사용하지 않으므로 브라우저의 인쇄 기능으로 바로 PDF를 만들 수 있습니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Booklet {
+    pub title: String,
+    /// 문제 페이지 한 장에 들어가는 퍼즐 수
+    pub puzzles_per_page: usize,
+    /// 정답지 페이지 한 장에 들어가는 정답 수
+    pub answers_per_page: usize,
+    pub puzzles: Vec<BookletPuzzle>,
+}
+
+impl Booklet {
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Booklet {
+            title: title.into(),
+            puzzles_per_page: 4,
+            answers_per_page: 9,
+            puzzles: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, puzzle: BookletPuzzle) {
+        self.puzzles.push(puzzle);
+    }
+
+    /// 책자를 HTML 문서로 출력합니다.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut ret = String::new();
+        let title = escape_html(&self.title);
+
+        let _ = write!(
+            ret,
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+@page {{ size: A4; margin: 12mm; }}
+body {{ font-family: sans-serif; margin: 0; }}
+.page {{ display: grid; gap: 6mm; page-break-after: always; break-after: page; }}
+.page:last-child {{ page-break-after: auto; break-after: auto; }}
+.page > h1 {{ grid-column: 1 / -1; font-size: 16pt; margin: 0; }}
+.puzzle {{ break-inside: avoid; }}
+.puzzle h2 {{ font-size: 11pt; margin: 0 0 1mm 0; }}
+.puzzle .meta {{ font-size: 8pt; color: #555; margin-bottom: 1mm; }}
+.puzzle svg {{ width: 100%; height: auto; }}
+</style>
+</head>
+<body>
+"#
+        );
+
+        let puzzles: Vec<&BookletPuzzle> = self.puzzles.iter().collect();
+        for (page_num, page) in puzzles.chunks(self.puzzles_per_page.max(1)).enumerate() {
+            let heading = (page_num == 0).then_some(title.as_str());
+            write_page(&mut ret, heading, self.puzzles_per_page, page, |p| {
+                Some(&p.puzzle_svg)
+            });
+        }
+
+        let answers: Vec<&BookletPuzzle> = self
+            .puzzles
+            .iter()
+            .filter(|p| p.answer_svg.is_some())
+            .collect();
+        for (page_num, page) in answers.chunks(self.answers_per_page.max(1)).enumerate() {
+            let heading = (page_num == 0).then_some("정답");
+            write_page(&mut ret, heading, self.answers_per_page, page, |p| {
+                p.answer_svg.as_ref()
+            });
+        }
+
+        ret.push_str("</body>\n</html>\n");
+        ret
+    }
+}
+
+/// 페이지 하나를 출력합니다. 열 수는 한 페이지의 퍼즐 수의 제곱근으로 정합니다.
+fn write_page<'a>(
+    ret: &mut String,
+    heading: Option<&str>,
+    per_page: usize,
+    puzzles: &[&'a BookletPuzzle],
+    svg: impl Fn(&'a BookletPuzzle) -> Option<&'a String>,
+) {
+    let columns = (per_page.max(1) as f64).sqrt().ceil() as usize;
+    let _ = writeln!(
+        ret,
+        r#"<div class="page" style="grid-template-columns: repeat({columns}, 1fr);">"#
+    );
+    if let Some(heading) = heading {
+        let _ = writeln!(ret, "<h1>{heading}</h1>");
+    }
+
+    for &puzzle in puzzles {
+        let Some(svg) = svg(puzzle) else {
+            continue;
+        };
+
+        let mut meta = Vec::with_capacity(2);
+        if let Some(seed) = puzzle.seed {
+            meta.push(format!("seed {seed}"));
+        }
+        if !puzzle.difficulty.is_empty() {
+            meta.push(escape_html(&puzzle.difficulty));
+        }
+
+        let _ = writeln!(
+            ret,
+            "<div class=\"puzzle\">\n<h2>{}</h2>\n<div class=\"meta\">{}</div>\n{svg}</div>",
+            escape_html(&puzzle.title),
+            meta.join(" · ")
+        );
+    }
+
+    ret.push_str("</div>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::table::Table;
+    use crate::solver::{GeneralSolve, Solver};
+
+    #[test]
+    fn booklet_pages() {
+        let mut booklet = Booklet::new("Test <Book>");
+
+        for seed in 0..5 {
+            let mut t = Table::new_default_9();
+            let mut solver = Solver::new_with_seed(&mut t, seed);
+            solver.fill_puzzle_with_timeout(std::time::Duration::MAX);
+            let mut punch = solver.into_punch();
+            punch.punch_all();
+
+            let puzzle =
+                BookletPuzzle::from_punch(format!("#{seed}"), Some(seed), "Single", &punch);
+            let given_cnt = punch
+                .get_table()
+                .read_lock()
+                .into_iter()
+                .filter(|(_, chk)| chk.fixed_final_num().is_some())
+                .count();
+            assert_eq!(puzzle.puzzle_svg.matches("</text>").count(), given_cnt);
+            assert_eq!(
+                puzzle
+                    .answer_svg
+                    .as_ref()
+                    .unwrap()
+                    .matches("</text>")
+                    .count(),
+                81
+            );
+            booklet.push(puzzle);
+        }
+
+        let html = booklet.to_html();
+        assert!(html.contains("<title>Test &lt;Book&gt;</title>"));
+        assert!(html.contains("seed 3"));
+        // 문제 2장 + 정답 1장
+        assert_eq!(html.matches(r#"<div class="page""#).count(), 3);
+        assert_eq!(html.matches("<svg").count(), 10);
+    }
+}
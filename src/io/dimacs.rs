@@ -0,0 +1,346 @@
+use super::{ParseError, check_givens};
+use crate::model::{
+    index_key_map::IndexKeyMap,
+    max_num::MaxNum,
+    table_lock::TableLock,
+    zone::{Zone, ZoneType},
+};
+use std::fmt::Write;
+
+/// cell_pos 번째 cell이 num 값을 가지는지를 나타내는 CNF 변수 번호입니다. (1부터 시작)
+///
+/// cell_pos는 테이블의 cell 순서 입니다. 기본 구조에서는 x + y * N 과 같습니다.
+#[must_use]
+#[inline]
+pub fn cell_var<const N: usize>(cell_pos: usize, num: MaxNum<N>) -> usize {
+    cell_pos * N + num.get_value() + 1
+}
+
+/// 테이블을 CNF 절 목록으로 변환합니다. 반환값은 (변수 개수, 절 목록) 입니다.
+fn build_clauses<const N: usize>(table: &TableLock<N>) -> (usize, Vec<Vec<isize>>) {
+    let lit = |cell_pos: usize, num: MaxNum<N>| cell_var(cell_pos, num) as isize;
+    let mut clauses: Vec<Vec<isize>> = Vec::new();
+    let mut zone_map: IndexKeyMap<Zone, Vec<usize>> = IndexKeyMap::new();
+
+    let read = table.read_lock();
+    for (cell_pos, (cell, chk)) in read.into_iter().enumerate() {
+        for zone in cell.get_zone() {
+            zone_map
+                .entry_or_insert_with(*zone, Vec::new)
+                .push(cell_pos);
+        }
+
+        // 노트가 true인 값 중 하나 이상
+        clauses.push(
+            MaxNum::<N>::iter()
+                .filter(|&n| chk.get_chk(n))
+                .map(|n| lit(cell_pos, n))
+                .collect(),
+        );
+
+        // 노트가 false인 값은 사용할 수 없음
+        for n in MaxNum::<N>::iter().filter(|&n| !chk.get_chk(n)) {
+            clauses.push(vec![-lit(cell_pos, n)]);
+        }
+
+        // 두 개 이상의 값을 가질 수 없음
+        for n1 in MaxNum::<N>::iter() {
+            for n2 in MaxNum::<N>::iter().skip(n1.get_value() + 1) {
+                clauses.push(vec![-lit(cell_pos, n1), -lit(cell_pos, n2)]);
+            }
+        }
+    }
+    drop(read);
+
+    let mut var_cnt = table.len() * N;
+    for (zone, cells) in &zone_map {
+        match zone.get_zone_type() {
+            ZoneType::Unique => {
+                for n in MaxNum::<N>::iter() {
+                    // zone에 각 값이 하나 이상 존재하며, 두 cell이 같은 값을 가질 수 없음
+                    clauses.push(cells.iter().map(|&c| lit(c, n)).collect());
+                    for (i, &c1) in cells.iter().enumerate() {
+                        for &c2 in &cells[i + 1..] {
+                            clauses.push(vec![-lit(c1, n), -lit(c2, n)]);
+                        }
+                    }
+                }
+            }
+            ZoneType::Sum { sum, distinct } => {
+                // killer cage의 두 cell은 같은 값을 가질 수 없음
+                if distinct {
+                    for n in MaxNum::<N>::iter() {
+                        for (i, &c1) in cells.iter().enumerate() {
+                            for &c2 in &cells[i + 1..] {
+                                clauses.push(vec![-lit(c1, n), -lit(c2, n)]);
+                            }
+                        }
+                    }
+                }
+                push_sum_clauses(&mut clauses, &mut var_cnt, cells, sum, lit);
+            }
+        }
+    }
+
+    (var_cnt, clauses)
+}
+
+/// Sum zone을 부분합 보조 변수로 인코딩합니다.
+///
+/// 보조 변수 s(j, t)는 "앞의 j개 cell의 합이 t" 를 뜻합니다.
+/// s(j, t)가 true이고 j번째 cell의 값이 v이면 s(j - 1, t - v)도 true여야 하며,
+/// s(zone의 cell 개수, sum)이 true여야 합니다.
+fn push_sum_clauses<const N: usize>(
+    clauses: &mut Vec<Vec<isize>>,
+    var_cnt: &mut usize,
+    cells: &[usize],
+    sum: usize,
+    lit: impl Fn(usize, MaxNum<N>) -> isize,
+) {
+    if sum == 0 {
+        // 합이 0이 될 수는 없으므로 빈 절(항상 false)을 추가함
+        clauses.push(Vec::new());
+        return;
+    }
+
+    let base = *var_cnt;
+    let s = |j: usize, t: usize| (base + (j - 1) * sum + t) as isize;
+    *var_cnt += cells.len() * sum;
+
+    for (j, &cell_pos) in cells.iter().enumerate().map(|(i, c)| (i + 1, c)) {
+        for t in 1..=sum {
+            for n in MaxNum::<N>::iter() {
+                let v = n.get_value() + 1;
+                let mut clause = vec![-s(j, t), -lit(cell_pos, n)];
+
+                if j == 1 {
+                    if t == v {
+                        continue;
+                    }
+                } else if t > v {
+                    clause.push(s(j - 1, t - v));
+                }
+
+                clauses.push(clause);
+            }
+        }
+    }
+
+    clauses.push(vec![s(cells.len(), sum)]);
+}
+
+/// SAT solver의 출력에서 각 cell의 값을 읽습니다. 반환값의 순서는 cell 순서와 같습니다.
+///
+/// "s SATISFIABLE" 과 "v ..." 줄로 이루어진 표준 출력 형식과,
+/// MiniSat 등의 "SAT" 다음 줄에 리터럴만 나열하는 형식을 모두 읽을 수 있습니다.
+/// 보조 변수는 무시합니다.
+pub fn parse_sat_model<const N: usize>(model: &str) -> Result<Vec<MaxNum<N>>, ParseError> {
+    parse_sat_model_with_len(model, N * N, |cell_pos| (cell_pos % N, cell_pos / N))
+}
+
+/// parse_sat_model과 같으나, cell_cnt개의 cell 값을 읽습니다. coordinate는 오류에 표시할 cell 좌표입니다.
+fn parse_sat_model_with_len<const N: usize>(
+    model: &str,
+    cell_cnt: usize,
+    coordinate: impl Fn(usize) -> (usize, usize),
+) -> Result<Vec<MaxNum<N>>, ParseError> {
+    let mut values: Vec<Option<MaxNum<N>>> = vec![None; cell_cnt];
+    let mut conflict = vec![false; cell_cnt];
+
+    for (line_num, line) in model.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+        let first = line.split_whitespace().next().unwrap_or_default();
+        match first {
+            "" | "c" | "SAT" | "SATISFIABLE" => continue,
+            "UNSAT" | "UNSATISFIABLE" => return Err(ParseError::Unsatisfiable),
+            "s" => {
+                if line.contains("UNSAT") {
+                    return Err(ParseError::Unsatisfiable);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let literals = line.strip_prefix('v').unwrap_or(line);
+        for token in literals.split_whitespace() {
+            let literal: isize = token
+                .parse()
+                .map_err(|_| ParseError::MalformedLine { line: line_num })?;
+
+            if literal <= 0 || literal as usize > cell_cnt * N {
+                continue;
+            }
+
+            let var = literal as usize - 1;
+            let (cell_pos, num) = (var / N, MaxNum::new(var % N));
+            if values[cell_pos].replace(num).is_some() {
+                conflict[cell_pos] = true;
+            }
+        }
+    }
+
+    values
+        .into_iter()
+        .zip(conflict)
+        .enumerate()
+        .map(|(cell_pos, (value, conflict))| match value {
+            Some(num) if !conflict => Ok(num),
+            _ => {
+                let (x, y) = coordinate(cell_pos);
+                Err(ParseError::InvalidModel { x, y })
+            }
+        })
+        .collect()
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 테이블의 모든 zone과 현재 노트를 DIMACS CNF 형식으로 출력합니다.
+    ///
+    /// 변수 1..=(cell 개수 * N)은 cell_var의 cell 값 변수이며, 그 뒤의 변수는 Sum zone의 보조 변수입니다.
+    /// 출력한 CNF를 외부 SAT solver로 풀고, 그 결과를 set_from_sat_model로 읽어 Solver의 결과와 비교할 수 있습니다.
+    #[must_use]
+    pub fn to_dimacs(&self) -> String {
+        let (var_cnt, clauses) = build_clauses(self);
+        let mut ret = String::new();
+
+        if self.is_full_grid() {
+            let _ = writeln!(ret, "c sudoku {N}x{N}");
+            let _ = writeln!(ret, "c var = (x + y * {N}) * {N} + value");
+        } else {
+            let _ = writeln!(ret, "c sudoku {N}x{N}, {} cells", self.len());
+            let _ = writeln!(ret, "c var = cell_index * {N} + value");
+        }
+        let _ = writeln!(ret, "p cnf {var_cnt} {}", clauses.len());
+        for clause in &clauses {
+            for literal in clause {
+                let _ = write!(ret, "{literal} ");
+            }
+            ret.push_str("0\n");
+        }
+
+        ret
+    }
+
+    /// SAT solver의 출력을 읽어 모든 cell의 값을 확정합니다.
+    ///
+    /// 모델이 Unique zone의 조건을 어기거나 오류가 발생한 경우 테이블은 변경되지 않습니다.
+    pub fn set_from_sat_model(&self, model: &str) -> Result<(), ParseError> {
+        let values: Vec<Option<MaxNum<N>>> =
+            parse_sat_model_with_len::<N>(model, self.len(), |cell_pos| {
+                self[cell_pos].get_coordinate()
+            })?
+            .into_iter()
+            .map(Some)
+            .collect();
+        check_givens(self, &values)?;
+
+        let mut write = self.write_lock();
+        for ((_, chk), value) in (&mut write).into_iter().zip(values) {
+            chk.set_to_value(value.expect("모든 cell의 값이 존재함"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::json::{TableDocument, ZoneDocument};
+    use crate::model::table::Table;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    /// cell 값을 고정한 뒤 보조 변수를 필요한 만큼만 true로 만들어 모든 절이 만족되는지 확인합니다.
+    fn satisfies(var_cnt: usize, clauses: &[Vec<isize>], cell_values: &[usize]) -> bool {
+        let mut assign = vec![false; var_cnt + 1];
+        for (cell_pos, &value) in cell_values.iter().enumerate() {
+            assign[cell_pos * 9 + value] = true;
+        }
+
+        let holds = |assign: &[bool], l: isize| assign[l.unsigned_abs()] == (l > 0);
+        loop {
+            let mut changed = false;
+            for clause in clauses {
+                if clause.iter().any(|&l| holds(&assign, l)) {
+                    continue;
+                }
+                let Some(&aux) = clause.iter().find(|&&l| l > 9 * 9 * 9) else {
+                    return false;
+                };
+                assign[aux as usize] = true;
+                changed = true;
+            }
+            if !changed {
+                return true;
+            }
+        }
+    }
+
+    #[test]
+    fn dimacs_round_trip() {
+        let t = Table::new_default_9_from_line(PUZZLE).unwrap();
+        let (var_cnt, clauses) = build_clauses(&t);
+        assert!(
+            t.to_dimacs()
+                .contains(&format!("p cnf {var_cnt} {}", clauses.len()))
+        );
+
+        let solution: Vec<usize> = SOLUTION
+            .chars()
+            .map(|c| c.to_digit(10).unwrap() as usize)
+            .collect();
+        assert!(satisfies(var_cnt, &clauses, &solution));
+
+        let mut wrong = solution.clone();
+        wrong.swap(0, 1);
+        assert!(!satisfies(var_cnt, &clauses, &wrong));
+
+        let model = solution
+            .iter()
+            .enumerate()
+            .map(|(pos, &v)| cell_var::<9>(pos, MaxNum::new(v - 1)).to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        t.set_from_sat_model(&format!("s SATISFIABLE\nv {model} 0\n"))
+            .unwrap();
+        assert_eq!(t.to_line(), SOLUTION);
+
+        assert_eq!(
+            t.set_from_sat_model("s UNSATISFIABLE\n"),
+            Err(ParseError::Unsatisfiable)
+        );
+    }
+
+    #[test]
+    fn dimacs_sum_zone() {
+        let solution: Vec<usize> = SOLUTION
+            .chars()
+            .map(|c| c.to_digit(10).unwrap() as usize)
+            .collect();
+
+        // 첫 줄의 앞 세 칸 (5, 3, 4)에 합 12인 zone을 추가
+        let mut doc = TableDocument::from_table(&Table::new_default_9());
+        for cell in &mut doc.cells[..3] {
+            cell.zones.push(ZoneDocument {
+                id: 28,
+                sum: Some(12),
+                distinct: false,
+                kind: None,
+            });
+        }
+        let t = doc.to_table::<9>().unwrap();
+        let (var_cnt, clauses) = build_clauses(&t);
+        assert!(satisfies(var_cnt, &clauses, &solution));
+
+        doc.cells[..3]
+            .iter_mut()
+            .for_each(|c| c.zones.last_mut().unwrap().sum = Some(13));
+        let t = doc.to_table::<9>().unwrap();
+        let (var_cnt, clauses) = build_clauses(&t);
+        assert!(!satisfies(var_cnt, &clauses, &solution));
+    }
+}
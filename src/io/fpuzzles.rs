@@ -0,0 +1,442 @@
+use super::{
+    ParseError, default_regions, is_false,
+    json::{CellDocument, TableDocument, ZoneDocument},
+};
+use crate::model::{
+    cell_pos::CellPos,
+    index_key_map::IndexKeyMap,
+    table::Table,
+    table_lock::TableLock,
+    zone::{Zone, ZoneKind, ZoneType},
+};
+use crate::num_check::CellStatus;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// 읽을 때 제약조건이 아니므로 무시하는 f-puzzles 항목입니다.
+const IGNORED_KEYS: [&str; 1] = ["solution"];
+
+/// f-puzzles 형식의 JSON 문서입니다. 이 crate에서 표현할 수 있는 항목만 필드로 가지고 있으며,
+/// 나머지 항목은 other에 그대로 보관됩니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FPuzzles {
+    pub size: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ruleset: Option<String>,
+    pub grid: Vec<Vec<FPuzzlesCell>>,
+    /// 왼쪽 아래에서 오른쪽 위로 향하는 대각선
+    #[serde(rename = "diagonal+", default, skip_serializing_if = "is_false")]
+    pub diagonal_positive: bool,
+    /// 왼쪽 위에서 오른쪽 아래로 향하는 대각선
+    #[serde(rename = "diagonal-", default, skip_serializing_if = "is_false")]
+    pub diagonal_negative: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub killercage: Vec<FPuzzlesCage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extraregion: Vec<FPuzzlesRegion>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FPuzzlesCell {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<usize>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub given: bool,
+    /// 0부터 시작하는 region 번호. 생략된 경우 기본 박스 모양을 따릅니다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given_pencil_marks: Option<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FPuzzlesCage {
+    pub cells: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FPuzzlesRegion {
+    pub cells: Vec<String>,
+}
+
+/// f-puzzles 문서를 읽은 결과입니다.
+pub struct FPuzzlesImport<const N: usize> {
+    pub table: TableLock<N>,
+    /// 이 crate에서 표현할 수 없어 무시된 제약조건 목록
+    pub unsupported: Vec<String>,
+}
+
+/// "R3C7" 형식의 cell 표기를 (x, y)로 변환합니다. 대소문자를 구분하지 않습니다.
+fn parse_cell_reference<const N: usize>(reference: &str) -> Result<(usize, usize), ParseError> {
+    let err = || ParseError::InvalidCellReference {
+        reference: reference.to_string(),
+    };
+
+    let pos: CellPos = reference.parse().map_err(|_| err())?;
+    if pos.x >= N || pos.y >= N {
+        return Err(err());
+    }
+    Ok(pos.into())
+}
+
+fn cell_reference(x: usize, y: usize) -> String {
+    CellPos::new(x, y).to_string().to_ascii_uppercase()
+}
+
+/// 값이 없는 항목인지 확인합니다. false, null, 빈 배열, 빈 객체, 빈 문자열은 값이 없는 것으로 봅니다.
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(b) => !b,
+        Value::String(s) => s.is_empty(),
+        Value::Array(a) => a.is_empty(),
+        Value::Object(o) => o.is_empty(),
+        Value::Number(_) => false,
+    }
+}
+
+impl FPuzzles {
+    /// f-puzzles 문서를 TableDocument로 변환합니다.
+    ///
+    /// region은 Unique zone, 합이 있는 killer cage는 값이 서로 달라야 하는 ZoneType::Sum (distinct), 대각선과 extra region은 추가 Unique zone이 됩니다.
+    /// 대각선은 ZoneKind::Diagonal, killer cage는 ZoneKind::Cage이며 번호는 나온 순서대로 1부터 시작합니다.
+    /// 표현할 수 없는 제약조건은 두 번째 반환값에 담깁니다.
+    pub fn to_document<const N: usize>(&self) -> Result<(TableDocument, Vec<String>), ParseError> {
+        if self.size != N {
+            return Err(ParseError::SizeMismatch {
+                expected: N,
+                found: self.size,
+            });
+        }
+        if self.grid.len() != N || self.grid.iter().any(|row| row.len() != N) {
+            return Err(ParseError::InvalidLength {
+                expected: N * N,
+                found: self.grid.iter().map(Vec::len).sum(),
+            });
+        }
+
+        let mut unsupported: Vec<String> = Vec::new();
+        let mut regions = default_regions(N);
+
+        for (index, fp_cell) in self.grid.iter().flatten().enumerate() {
+            let Some(region) = fp_cell.region else {
+                continue;
+            };
+            if region >= N {
+                return Err(ParseError::InvalidValue {
+                    x: index % N,
+                    y: index / N,
+                    value: region,
+                });
+            }
+            regions[index] = region;
+        }
+
+        let mut doc = TableDocument::new_with_regions(N, &regions);
+        for (cell, fp_cell) in doc.cells.iter_mut().zip(self.grid.iter().flatten()) {
+            cell.given = fp_cell.value;
+            cell.candidates = fp_cell.given_pencil_marks.clone();
+
+            // given이 아닌 값은 사용자가 입력한 값
+            if fp_cell.value.is_some() && !fp_cell.given {
+                cell.status = Some(CellStatus::UserEntered);
+            }
+        }
+        let cells = &mut doc.cells;
+
+        let mut next_zone = N as u16 * 3 + 1;
+        let (mut diagonal_cnt, mut cage_cnt) = (0, 0);
+        let mut add_zone =
+            |cells: &mut Vec<CellDocument>, positions: &[(usize, usize)], sum, kind| {
+                for &(x, y) in positions {
+                    cells[x + y * N].zones.push(ZoneDocument {
+                        id: next_zone,
+                        sum,
+                        // f-puzzles의 킬러 cage는 항상 값이 서로 달라야 함
+                        distinct: sum.is_some(),
+                        kind,
+                    });
+                }
+                next_zone += 1;
+            };
+
+        if self.diagonal_negative {
+            let positions: Vec<(usize, usize)> = (0..N).map(|i| (i, i)).collect();
+            diagonal_cnt += 1;
+            add_zone(
+                cells,
+                &positions,
+                None,
+                Some(ZoneKind::Diagonal(diagonal_cnt)),
+            );
+        }
+        if self.diagonal_positive {
+            let positions: Vec<(usize, usize)> = (0..N).map(|i| (N - 1 - i, i)).collect();
+            diagonal_cnt += 1;
+            add_zone(
+                cells,
+                &positions,
+                None,
+                Some(ZoneKind::Diagonal(diagonal_cnt)),
+            );
+        }
+
+        for region in &self.extraregion {
+            let positions = region
+                .cells
+                .iter()
+                .map(|r| parse_cell_reference::<N>(r))
+                .collect::<Result<Vec<_>, _>>()?;
+            if positions.len() != N {
+                unsupported.push(format!(
+                    "extraregion ({}): cell이 {N}개가 아닌 region",
+                    region.cells.join(",")
+                ));
+                continue;
+            }
+            add_zone(cells, &positions, None, None);
+        }
+
+        for cage in &self.killercage {
+            let positions = cage
+                .cells
+                .iter()
+                .map(|r| parse_cell_reference::<N>(r))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            cage_cnt += 1;
+            let kind = Some(ZoneKind::Cage(cage_cnt));
+            match cage
+                .value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+            {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(sum) => add_zone(cells, &positions, Some(sum), kind),
+                    Err(_) => unsupported.push(format!(
+                        "killercage ({}): 숫자가 아닌 합 '{value}'",
+                        cage.cells.join(",")
+                    )),
+                },
+                None if positions.len() == N => add_zone(cells, &positions, None, kind),
+                None => unsupported.push(format!(
+                    "killercage ({}): 합이 없는 cage",
+                    cage.cells.join(",")
+                )),
+            }
+        }
+
+        for (key, value) in &self.other {
+            if IGNORED_KEYS.contains(&key.as_str()) || is_empty_value(value) {
+                continue;
+            }
+            unsupported.push(key.clone());
+        }
+
+        Ok((doc, unsupported))
+    }
+
+    /// 테이블을 f-puzzles 문서로 만듭니다.
+    ///
+    /// rep_zone은 region, 가로 및 세로 zone은 생략되며, 대각선은 diagonal, 그 외의 Unique zone은 extraregion,
+    /// ZoneType::Sum은 killercage가 됩니다.
+    /// 확정된 값은 value로 기록되며, CellStatus::Given인 경우에만 given이 true 입니다.
+    /// 여러 격자가 겹친 퍼즐과 같이 N X N 격자가 아닌 테이블은 UnsupportedLayout 오류를 반환합니다.
+    pub fn from_table<const N: usize>(table: &TableLock<N>) -> Result<Self, ParseError> {
+        if !table.is_full_grid() {
+            return Err(ParseError::UnsupportedLayout {
+                format: "f-puzzles",
+            });
+        }
+
+        let mut region_index: IndexKeyMap<Zone, usize> = IndexKeyMap::new();
+        let mut zone_cells: IndexKeyMap<Zone, (Zone, Vec<(usize, usize)>)> = IndexKeyMap::new();
+        let mut grid: Vec<Vec<FPuzzlesCell>> = vec![Vec::with_capacity(N); N];
+
+        let read = table.read_lock();
+        for (cell, chk) in &read {
+            let (x, y) = cell.get_coordinate();
+
+            let region = cell.rep_zone().map(|z| {
+                let next = region_index.iter().count();
+                *region_index.entry_or_insert_with(z, || next)
+            });
+
+            for &z in cell.get_zone() {
+                zone_cells
+                    .entry_or_insert_with(z, || (z, Vec::with_capacity(N)))
+                    .1
+                    .push((x, y));
+            }
+
+            let value = chk.final_num().map(|n| n.get_value() + 1);
+            grid[y].push(FPuzzlesCell {
+                value,
+                given: chk.is_given(),
+                region,
+                given_pencil_marks: None,
+            });
+        }
+
+        // 기본 박스 모양과 같은 경우 region을 생략
+        let regions = default_regions(N);
+        let default_region = grid
+            .iter()
+            .flatten()
+            .zip(&regions)
+            .all(|(c, &region)| c.region == Some(region));
+        if default_region {
+            for c in grid.iter_mut().flatten() {
+                c.region = None;
+            }
+        }
+
+        let mut ret = FPuzzles {
+            size: N,
+            grid,
+            ..Default::default()
+        };
+
+        for (z, (zone, positions)) in &zone_cells {
+            if region_index.contains(z) {
+                continue;
+            }
+
+            let refs = || {
+                positions
+                    .iter()
+                    .map(|&(x, y)| cell_reference(x, y))
+                    .collect()
+            };
+            match zone.get_zone_type() {
+                ZoneType::Sum { sum, .. } => ret.killercage.push(FPuzzlesCage {
+                    cells: refs(),
+                    value: Some(sum.to_string()),
+                }),
+                ZoneType::Unique => {
+                    let same_row = positions.iter().all(|p| p.1 == positions[0].1);
+                    let same_col = positions.iter().all(|p| p.0 == positions[0].0);
+                    if positions.len() == N && (same_row || same_col) {
+                        continue;
+                    }
+
+                    if positions.len() == N && positions.iter().all(|&(x, y)| x == y) {
+                        ret.diagonal_negative = true;
+                    } else if positions.len() == N && positions.iter().all(|&(x, y)| x + y == N - 1)
+                    {
+                        ret.diagonal_positive = true;
+                    } else {
+                        ret.extraregion.push(FPuzzlesRegion { cells: refs() });
+                    }
+                }
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+impl<const N: usize> Table<N> {
+    /// f-puzzles 형식의 JSON 문자열로부터 테이블을 만듭니다.
+    ///
+    /// 표현할 수 없는 제약조건은 오류 대신 FPuzzlesImport::unsupported에 담깁니다.
+    pub fn new_from_fpuzzles(json: &str) -> Result<FPuzzlesImport<N>, ParseError> {
+        let fp: FPuzzles = serde_json::from_str(json).map_err(|err| ParseError::Json {
+            message: err.to_string(),
+        })?;
+        let (doc, unsupported) = fp.to_document::<N>()?;
+
+        Ok(FPuzzlesImport {
+            table: doc.to_table()?,
+            unsupported,
+        })
+    }
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 테이블을 f-puzzles 형식의 JSON 문자열로 출력합니다. N X N 격자가 아닌 경우 UnsupportedLayout 오류
+    pub fn to_fpuzzles(&self) -> Result<String, ParseError> {
+        let doc = FPuzzles::from_table(self)?;
+        Ok(serde_json::to_string(&doc).expect("FPuzzles는 항상 직렬화 가능"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fpuzzles_import() {
+        let mut grid = vec![vec![FPuzzlesCell::default(); 9]; 9];
+        grid[0][0] = FPuzzlesCell {
+            value: Some(5),
+            given: true,
+            ..Default::default()
+        };
+        let fp = FPuzzles {
+            size: 9,
+            grid,
+            diagonal_negative: true,
+            killercage: vec![
+                FPuzzlesCage {
+                    cells: vec!["R1C2".to_string(), "R1C3".to_string()],
+                    value: Some("3".to_string()),
+                },
+                FPuzzlesCage {
+                    cells: vec!["R9C9".to_string(), "R9C8".to_string()],
+                    value: None,
+                },
+            ],
+            other: Map::from_iter([
+                (
+                    "thermometer".to_string(),
+                    serde_json::json!([{ "lines": [] }]),
+                ),
+                ("arrow".to_string(), serde_json::json!([])),
+            ]),
+            ..Default::default()
+        };
+
+        let import = Table::<9>::new_from_fpuzzles(&serde_json::to_string(&fp).unwrap()).unwrap();
+        assert_eq!(import.unsupported.len(), 2);
+        assert!(import.unsupported[0].starts_with("killercage (R9C9,R9C8)"));
+        assert_eq!(import.unsupported[1], "thermometer");
+
+        let t = &import.table;
+        assert_eq!(t[0].get_zone().len(), 4);
+        assert!(matches!(
+            t[1].get_zone()[3].get_zone_type(),
+            ZoneType::Sum {
+                sum: 3,
+                distinct: true
+            }
+        ));
+        assert_eq!(t[1].get_zone()[3].get_zone_num(), 29);
+        assert_eq!(t.to_line().chars().next(), Some('5'));
+
+        let exported = FPuzzles::from_table(t).unwrap();
+        assert!(exported.diagonal_negative);
+        assert_eq!(exported.killercage, fp.killercage[..1]);
+        assert!(exported.grid.iter().flatten().all(|c| c.region.is_none()));
+    }
+
+    #[test]
+    fn default_box_size_test() {
+        use crate::io::default_box_size;
+
+        assert_eq!(default_box_size(6), (3, 2));
+        assert_eq!(default_box_size(9), (3, 3));
+        assert_eq!(default_box_size(12), (4, 3));
+        assert_eq!(default_box_size(7), (7, 1));
+    }
+}
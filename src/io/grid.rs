@@ -0,0 +1,533 @@
+use super::{
+    ParseError,
+    line::{BLANK_CHARS, char_tokens, parse_cell_token, split_tokens},
+};
+use crate::model::{
+    array_vector::ArrayVector, cell::Cell, max_num::MaxNum, symbol_set::SymbolSet,
+    table_lock::TableLock,
+};
+use crate::num_check::NumCheck;
+
+/// 여러 줄 형식에서 박스 구분선으로 사용하는 문자입니다. 읽을 때는 무시됩니다.
+pub const SEPARATOR_CHARS: [char; 3] = ['|', '+', '-'];
+
+/// 노트가 하나도 없는 cell을 pencilmark 형식에서 나타내는 토큰입니다.
+const EMPTY_NOTE: &str = ".";
+
+/// 여러 글자 기호를 사용할 때 pencilmark 형식에서 노트를 구분하는 문자입니다.
+const NOTE_SEPARATOR: char = ',';
+
+/// 여러 줄 형식의 퍼즐을 칸마다의 given 목록으로 변환합니다.
+///
+/// 공백과 구분선 문자는 무시하며, 빈 칸은 '.' 또는 '0'으로 나타냅니다.
+pub fn parse_grid<const N: usize>(text: &str) -> Result<Vec<Option<MaxNum<N>>>, ParseError> {
+    parse_grid_with(text, N * N, &SymbolSet::default())
+}
+
+/// parse_grid와 같으나, symbols의 기호로 읽고 칸 개수가 cell_cnt인지 검사합니다.
+///
+/// 여러 글자 기호가 있는 경우 칸은 공백 또는 '|'로 구분하며, '-', '+'로만 이루어진 토큰은 무시합니다.
+fn parse_grid_with<const N: usize>(
+    text: &str,
+    cell_cnt: usize,
+    symbols: &SymbolSet<N>,
+) -> Result<Vec<Option<MaxNum<N>>>, ParseError> {
+    let mut givens: Vec<Option<MaxNum<N>>> = Vec::with_capacity(cell_cnt);
+
+    for (line_index, line) in text.lines().enumerate() {
+        let tokens: Vec<(usize, &str)> = if symbols.is_single_char() {
+            char_tokens(line)
+                .filter(|(_, token)| {
+                    !token
+                        .chars()
+                        .any(|c| c.is_whitespace() || SEPARATOR_CHARS.contains(&c))
+                })
+                .collect()
+        } else {
+            split_tokens(line, |c| c.is_whitespace() || c == '|')
+                .filter(|(_, token)| !token.chars().all(|c| c == '-' || c == '+'))
+                .collect()
+        };
+
+        for (column, token) in tokens {
+            let Some(given) = parse_cell_token(symbols, token) else {
+                return Err(ParseError::InvalidSymbol {
+                    line: line_index + 1,
+                    column: column + 1,
+                    symbol: token.to_string(),
+                });
+            };
+            givens.push(given);
+        }
+    }
+
+    if givens.len() != cell_cnt {
+        return Err(ParseError::InvalidLength {
+            expected: cell_cnt,
+            found: givens.len(),
+        });
+    }
+
+    Ok(givens)
+}
+
+/// pencilmark 형식을 칸마다의 노트 목록으로 변환합니다.
+///
+/// cell 하나는 공백으로 구분된 토큰 하나이며, 토큰의 각 문자가 true인 노트입니다.
+/// 노트가 하나도 없는 cell은 '.'으로 나타냅니다. '-', '+'로만 이루어진 토큰과 '|'는 무시합니다.
+pub fn parse_pencilmark<const N: usize>(
+    text: &str,
+) -> Result<Vec<ArrayVector<MaxNum<N>, N>>, ParseError> {
+    parse_pencilmark_with(text, N * N, &SymbolSet::default())
+}
+
+/// parse_pencilmark와 같으나, symbols의 기호로 읽고 칸 개수가 cell_cnt인지 검사합니다.
+///
+/// 여러 글자 기호가 있는 경우 토큰 안의 노트는 ','로 구분합니다.
+fn parse_pencilmark_with<const N: usize>(
+    text: &str,
+    cell_cnt: usize,
+    symbols: &SymbolSet<N>,
+) -> Result<Vec<ArrayVector<MaxNum<N>, N>>, ParseError> {
+    let mut notes: Vec<ArrayVector<MaxNum<N>, N>> = Vec::with_capacity(cell_cnt);
+
+    for (line_index, line) in text.lines().enumerate() {
+        for (token_column, token) in split_tokens(line, |c| c.is_whitespace() || c == '|') {
+            if token.chars().all(|c| c == '-' || c == '+') {
+                continue;
+            }
+
+            let mut note: ArrayVector<MaxNum<N>, N> = ArrayVector::new();
+            if token != EMPTY_NOTE {
+                let note_tokens: Vec<(usize, &str)> = if symbols.is_single_char() {
+                    char_tokens(token).collect()
+                } else {
+                    split_tokens(token, |c| c == NOTE_SEPARATOR).collect()
+                };
+
+                for (offset, note_token) in note_tokens {
+                    let num = symbols.parse(note_token).filter(|n| !note.contains(n));
+                    let Some(num) = num else {
+                        return Err(ParseError::InvalidSymbol {
+                            line: line_index + 1,
+                            column: token_column + offset + 1,
+                            symbol: note_token.to_string(),
+                        });
+                    };
+                    note.push(num);
+                }
+            }
+            notes.push(note);
+        }
+    }
+
+    if notes.len() != cell_cnt {
+        return Err(ParseError::InvalidLength {
+            expected: cell_cnt,
+            found: notes.len(),
+        });
+    }
+
+    Ok(notes)
+}
+
+/// TableLock::note_fmt로 출력된 문자열을 칸마다의 노트 목록으로 변환합니다.
+///
+/// 캔버스가 N X N인 경우만 읽습니다. 사무라이 등의 구조는 TableLock::set_notes_from_note_fmt를 사용합니다.
+pub fn parse_note_fmt<const N: usize>(
+    text: &str,
+) -> Result<Vec<ArrayVector<MaxNum<N>, N>>, ParseError> {
+    parse_note_fmt_with(text, (N, N), |_, _| true, &SymbolSet::default())
+}
+
+/// parse_note_fmt와 같으나, width X height 캔버스를 symbols의 기호로 읽습니다.
+///
+/// has_cell이 false인 칸은 건너뛰며, 결과는 캔버스에서 cell이 있는 칸만 행 순서대로 담습니다.
+fn parse_note_fmt_with<const N: usize>(
+    text: &str,
+    (width, height): (usize, usize),
+    has_cell: impl Fn(usize, usize) -> bool,
+    symbols: &SymbolSet<N>,
+) -> Result<Vec<ArrayVector<MaxNum<N>, N>>, ParseError> {
+    let rec_size = note_rec_size::<N>();
+    let note_width = symbols.note_width();
+    let mut notes: Vec<ArrayVector<MaxNum<N>, N>> = Vec::with_capacity(width * height);
+    notes.resize_with(width * height, ArrayVector::new);
+
+    let note_lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.chars().all(|c| c == '-'));
+
+    let mut note_line_cnt = 0;
+    for (line_index, line) in note_lines {
+        let y = note_line_cnt / rec_size;
+        let sub_row = note_line_cnt % rec_size;
+        note_line_cnt += 1;
+
+        if y >= height {
+            return Err(ParseError::MalformedLine {
+                line: line_index + 1,
+            });
+        }
+
+        let segments: Vec<&str> = line.split('|').collect();
+        // 각 cell 뒤에 '|'가 붙으므로 마지막 조각은 비어있어야 함
+        if segments.len() != width + 1 || !segments[width].trim().is_empty() {
+            return Err(ParseError::MalformedLine {
+                line: line_index + 1,
+            });
+        }
+
+        let mut column = 0;
+        for (x, segment) in segments.iter().take(width).enumerate() {
+            let chars: Vec<char> = segment.chars().collect();
+            if !has_cell(x, y) {
+                column += chars.len() + 1;
+                continue;
+            }
+
+            for (offset, slot) in chars.chunks(note_width).enumerate() {
+                let slot: String = slot.iter().collect();
+                let slot = slot.trim();
+                let expected = MaxNum::<N>::new_optional(sub_row * rec_size + offset)
+                    .filter(|_| offset < rec_size);
+
+                match expected {
+                    _ if slot.is_empty() => {}
+                    Some(n) if symbols.parse(slot) == Some(n) => notes[x + y * width].push(n),
+                    _ => {
+                        return Err(ParseError::InvalidSymbol {
+                            line: line_index + 1,
+                            column: column + offset * note_width + 1,
+                            symbol: slot.to_string(),
+                        });
+                    }
+                }
+            }
+            column += chars.len() + 1;
+        }
+    }
+
+    if note_line_cnt != height * rec_size {
+        return Err(ParseError::InvalidLength {
+            expected: height * rec_size,
+            found: note_line_cnt,
+        });
+    }
+
+    Ok(notes
+        .into_iter()
+        .enumerate()
+        .filter(|(pos, _)| has_cell(pos % width, pos / width))
+        .map(|(_, note)| note)
+        .collect())
+}
+
+/// note_fmt에서 cell 하나를 표현하는 정사각형의 한 변 길이
+#[inline]
+pub(crate) fn note_rec_size<const N: usize>() -> usize {
+    (N as f64).sqrt().ceil() as usize
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 여러 줄 형식의 퍼즐을 읽어 given을 채웁니다. given이 아닌 cell은 모든 노트가 true로 초기화됩니다.
+    ///
+    /// 칸은 cell의 순서와 같으므로, to_grid_string과 같이 cell이 없는 칸을 공백으로 둔 형식도 읽을 수 있습니다.
+    /// 오류가 발생한 경우 테이블은 변경되지 않습니다.
+    pub fn set_givens_from_grid(&self, text: &str) -> Result<(), ParseError> {
+        let givens = parse_grid_with(text, self.len(), self.symbols())?;
+        self.set_givens(&givens)
+    }
+
+    /// pencilmark 형식을 읽어 모든 cell의 노트를 그대로 설정합니다.
+    ///
+    /// 오류가 발생한 경우 테이블은 변경되지 않습니다.
+    pub fn set_notes_from_pencilmark(&self, text: &str) -> Result<(), ParseError> {
+        let notes = parse_pencilmark_with(text, self.len(), self.symbols())?;
+        self.set_notes(&notes)
+    }
+
+    /// note_fmt 형식을 읽어 모든 cell의 노트를 그대로 설정합니다.
+    ///
+    /// 오류가 발생한 경우 테이블은 변경되지 않습니다.
+    pub fn set_notes_from_note_fmt(&self, text: &str) -> Result<(), ParseError> {
+        let notes = parse_note_fmt_with(
+            text,
+            (self.width(), self.height()),
+            |x, y| self.get_cell_from_canvas(x, y).is_some(),
+            self.symbols(),
+        )?;
+        self.set_notes(&notes)
+    }
+
+    /// 칸마다의 노트 목록으로 모든 cell의 노트를 설정합니다. notes의 순서는 cell의 순서와 같아야 합니다.
+    pub fn set_notes(&self, notes: &[ArrayVector<MaxNum<N>, N>]) -> Result<(), ParseError> {
+        if notes.len() != self.len() {
+            return Err(ParseError::InvalidLength {
+                expected: self.len(),
+                found: notes.len(),
+            });
+        }
+
+        let mut write = self.write_lock();
+        for ((_, chk), note) in (&mut write).into_iter().zip(notes) {
+            *chk = NumCheck::new_with_false();
+            chk.set_to_chk_list(note);
+        }
+
+        Ok(())
+    }
+
+    /// 확정된 값을 여러 줄 형식으로 출력합니다. 확정되지 않은 cell은 '.'으로 표시됩니다.
+    ///
+    /// 모든 줄에서 rep_zone이 나뉘는 위치에만 박스 구분선을 넣습니다.
+    #[must_use]
+    pub fn to_grid_string(&self) -> String {
+        let read = self.read_lock();
+        let tokens: Vec<String> = read
+            .into_iter()
+            .map(|(_, chk)| match chk.final_num() {
+                Some(num) => self.symbols().get(num).to_string(),
+                None => BLANK_CHARS[0].to_string(),
+            })
+            .collect();
+
+        self.make_grid_string(&tokens)
+    }
+
+    /// 모든 cell의 노트를 pencilmark 형식으로 출력합니다. set_notes_from_pencilmark로 다시 읽을 수 있습니다.
+    ///
+    /// 여러 글자 기호가 있는 경우 노트를 ','로 구분합니다.
+    #[must_use]
+    pub fn to_pencilmark_string(&self) -> String {
+        let read = self.read_lock();
+        let separator = if self.symbols().is_single_char() {
+            String::new()
+        } else {
+            NOTE_SEPARATOR.to_string()
+        };
+        let tokens: Vec<String> = read
+            .into_iter()
+            .map(|(_, chk)| {
+                let token: String = MaxNum::<N>::iter()
+                    .filter(|&n| chk.get_chk(n))
+                    .map(|n| self.symbols().get(n))
+                    .collect::<Vec<_>>()
+                    .join(&separator);
+                if token.is_empty() {
+                    EMPTY_NOTE.to_string()
+                } else {
+                    token
+                }
+            })
+            .collect();
+
+        self.make_grid_string(&tokens)
+    }
+
+    /// cell마다의 토큰을 열 너비에 맞춰 정렬하고 박스 구분선을 넣어 출력합니다. cell이 없는 칸은 공백으로 채웁니다.
+    fn make_grid_string(&self, tokens: &[String]) -> String {
+        let width = self.width();
+        let (col_break, row_break) = self.box_breaks();
+
+        let mut col_width = vec![0usize; width];
+        for (cell, token) in self.iter().zip(tokens) {
+            let w = &mut col_width[cell.get_coordinate().0];
+            *w = (*w).max(token.chars().count());
+        }
+
+        let mut separator = String::new();
+        for x in 0..width {
+            let dash_cnt = col_width[x] + usize::from(x != 0) + usize::from(col_break[x]);
+            separator.push_str(&"-".repeat(dash_cnt));
+            if col_break[x] {
+                separator.push('+');
+            }
+        }
+
+        let mut ret = String::with_capacity(tokens.len() * 4);
+        let mut line = String::new();
+        for (y, &row_break) in row_break.iter().enumerate() {
+            line.clear();
+            for x in 0..width {
+                let token = self
+                    .get_cell_from_canvas(x, y)
+                    .map_or("", |cell| tokens[cell.index].as_str());
+                if x != 0 {
+                    line.push(' ');
+                }
+                line.push_str(token);
+                if x != width - 1 {
+                    line.push_str(&" ".repeat(col_width[x] - token.chars().count()));
+                }
+                if col_break[x] {
+                    line.push_str(" |");
+                }
+            }
+            ret.push_str(line.trim_end());
+            ret.push('\n');
+
+            if row_break {
+                ret.push_str(&separator);
+                ret.push('\n');
+            }
+        }
+
+        ret
+    }
+
+    /// 모든 줄에서 rep_zone이 나뉘는 경계를 반환합니다. 한쪽에 cell이 없는 칸은 비교하지 않습니다.
+    ///
+    /// 반환값은 (x와 x + 1 사이에 세로 구분선이 필요한지, y와 y + 1 사이에 가로 구분선이 필요한지) 입니다.
+    fn box_breaks(&self) -> (Vec<bool>, Vec<bool>) {
+        let (width, height) = (self.width(), self.height());
+        // 비교할 수 있는 두 cell이 하나 이상 있고, 모두 rep_zone이 다른지 여부
+        let all_differ = |pairs: &mut dyn Iterator<Item = (Option<&Cell<N>>, Option<&Cell<N>>)>| {
+            let mut compared = false;
+            for pair in pairs {
+                if let (Some(a), Some(b)) = pair {
+                    if a.rep_zone() == b.rep_zone() {
+                        return false;
+                    }
+                    compared = true;
+                }
+            }
+            compared
+        };
+
+        let col_break = (0..width)
+            .map(|x| {
+                all_differ(&mut (0..height).map(|y| {
+                    (
+                        self.get_cell_from_canvas(x, y),
+                        self.get_cell_from_canvas(x + 1, y),
+                    )
+                }))
+            })
+            .collect();
+        let row_break = (0..height)
+            .map(|y| {
+                all_differ(&mut (0..width).map(|x| {
+                    (
+                        self.get_cell_from_canvas(x, y),
+                        self.get_cell_from_canvas(x, y + 1),
+                    )
+                }))
+            })
+            .collect();
+
+        (col_break, row_break)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{symbol_set::SymbolSet, table::Table};
+    use crate::solver::{GeneralSolve, Solver};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn grid_round_trip() {
+        let t = Table::new_default_9_from_line(PUZZLE).unwrap();
+        let grid = t.to_grid_string();
+        assert!(grid.starts_with("5 3 . | . 7 . | . . .\n"));
+        assert_eq!(grid.lines().nth(3), Some("------+-------+------"));
+
+        let t2 = Table::new_default_9();
+        t2.set_givens_from_grid(&grid).unwrap();
+        assert_eq!(t, t2);
+    }
+
+    #[test]
+    fn pencilmark_round_trip() {
+        let mut t = Table::new_default_9_from_line(PUZZLE).unwrap();
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        for _ in 0..5 {
+            solver.solve();
+        }
+        drop(solver);
+
+        let t2 = Table::new_default_9();
+        t2.set_notes_from_pencilmark(&t.to_pencilmark_string())
+            .unwrap();
+        assert_eq!(t, t2);
+
+        let t3 = Table::new_default_9();
+        t3.set_notes_from_note_fmt(&t.note_fmt()).unwrap();
+        assert_eq!(t, t3);
+    }
+
+    #[test]
+    fn samurai_note_fmt_round_trip() {
+        let mut t = Table::new_samurai();
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        for _ in 0..5 {
+            solver.solve();
+        }
+        drop(solver);
+
+        let note_fmt = t.note_fmt();
+        // 격자가 없는 칸도 캔버스의 가로 칸 수만큼 출력됨
+        assert_eq!(note_fmt.lines().next().unwrap().matches('|').count(), 21);
+
+        let t2 = Table::new_samurai();
+        t2.set_notes_from_note_fmt(&note_fmt).unwrap();
+        assert_eq!(t, t2);
+    }
+
+    #[test]
+    fn custom_symbols_round_trip() {
+        // '0'이 기호인 경우 빈 칸이 아닌 값으로 읽음
+        let mut hex = Table::<16>::new_default_16();
+        hex.set_symbols(SymbolSet::from_chars("0123456789ABCDEF"));
+        let line = format!("0f{}", ".".repeat(254));
+        hex.set_givens_from_line(&line).unwrap();
+        assert_eq!(hex.to_line(), line.to_ascii_uppercase());
+        assert!(
+            hex.to_string()
+                .lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("║0\u{332}┆F\u{332}┆")
+        );
+
+        let mut t = Table::<16>::new_default_16();
+        t.set_symbols(SymbolSet::new_numbered());
+        let line = format!("16 1 {}", ". ".repeat(254));
+        t.set_givens_from_line(&line).unwrap();
+        assert_eq!(t.to_line(), line.trim_end());
+        assert!(t.to_grid_string().starts_with("16 1 . . |"));
+        assert!(
+            t.to_string()
+                .lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("║1\u{332}6\u{332}┆ 1\u{332}┆  ┆")
+        );
+
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        for _ in 0..3 {
+            solver.solve();
+        }
+        drop(solver);
+        assert!(t.to_pencilmark_string().contains("2,3,"));
+
+        let mut t2 = Table::<16>::new_default_16();
+        t2.set_symbols(SymbolSet::new_numbered());
+        t2.set_givens_from_grid(&t.to_grid_string()).unwrap();
+        t2.set_notes_from_pencilmark(&t.to_pencilmark_string())
+            .unwrap();
+        assert_eq!(t, t2);
+        t2.set_notes_from_note_fmt(&t.note_fmt()).unwrap();
+        assert_eq!(t, t2);
+
+        // 기본 기호도 NumCheck가 지원하는 크기까지 출력할 수 있음
+        let big = Table::<36>::new_standard(6, 6);
+        big.set_givens_from_line(&format!("a{}", ".".repeat(36 * 36 - 1)))
+            .unwrap();
+        assert!(big.to_line().starts_with("a."));
+    }
+}
@@ -0,0 +1,472 @@
+use super::{ParseError, check_givens, is_false};
+use crate::model::{
+    array_vector::ArrayVector,
+    cell::Cell,
+    index_key_map::{IndexKeyMap, IndexKeySet},
+    max_num::MaxNum,
+    symbol_set::SymbolSet,
+    table::Table,
+    table_lock::TableLock,
+    zone::{Zone, ZoneKind, ZoneType},
+};
+use crate::num_check::{CellStatus, NumCheck};
+use serde::{Deserialize, Serialize};
+
+/// 테이블의 구조(Zone)와 상태(given, 노트)를 담는 JSON 문서입니다.
+///
+/// ```json
+/// {
+///   "size": 9,
+///   "cells": [
+///     {
+///       "x": 0, "y": 0,
+///       "zones": [{ "id": 1, "kind": "Box 1" }, { "id": 10, "kind": "Column 1" }, { "id": 19, "kind": "Row 1" }],
+///       "given": 5
+///     },
+///     {
+///       "x": 1, "y": 0,
+///       "zones": [{ "id": 1 }, { "id": 11 }, { "id": 19 }, { "id": 28, "sum": 10, "distinct": true, "kind": "Cage 1" }],
+///       "candidates": [1, 2, 4]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// * size: 퍼즐 크기. 문서를 읽는 TableLock<N>의 N과 같아야 합니다.
+/// * width, height: 캔버스 크기. 사무라이 등 여러 격자가 겹친 퍼즐에서 사용하며, 생략하면 size입니다.
+/// * symbols: 값마다 화면과 입출력에 사용할 기호 (SymbolSet). 생략하면 기본 기호입니다.
+/// * cells: 모든 cell 목록. 순서는 상관 없으나 좌표가 중복될 수 없습니다. x, y는 0부터 시작합니다.
+///   캔버스 크기가 있는 경우 cell이 없는 칸은 퍼즐에 포함되지 않으며, 없으면 size X size의 모든 cell이 있어야 합니다.
+/// * zones: cell이 속한 Zone 목록. 첫 Zone이 rep_zone이 됩니다. sum이 있으면 ZoneType::Sum, 없으면 ZoneType::Unique 입니다.
+///   distinct가 true이면 sum zone 안의 값이 서로 달라야 하는 킬러 cage이며, 생략하면 false 입니다. sum이 없으면 무시됩니다.
+///   kind는 "Row 3", "Cage 12" 와 같은 ZoneKind 표기이며, 생략하면 ZoneKind::Other 입니다. 같은 번호의 zone은 모두 같은 kind여야 합니다.
+/// * given: 확정된 값 (1부터 N). 생략할 수 있습니다.
+/// * status: given의 출처. "solved" 또는 "user_entered"이며, 생략하면 문제에 주어진 값 ("given") 입니다.
+/// * candidates: 확정되지 않은 cell의 노트 (1부터 N). 생략하면 모든 노트가 true 입니다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableDocument {
+    pub size: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbols: Option<Vec<String>>,
+    pub cells: Vec<CellDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellDocument {
+    pub x: usize,
+    pub y: usize,
+    pub zones: Vec<ZoneDocument>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<CellStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZoneDocument {
+    pub id: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sum: Option<usize>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub distinct: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ZoneKind>,
+}
+
+impl From<Zone> for ZoneDocument {
+    fn from(zone: Zone) -> Self {
+        let (sum, distinct) = match zone.get_zone_type() {
+            ZoneType::Unique => (None, false),
+            ZoneType::Sum { sum, distinct } => (Some(sum), distinct),
+        };
+
+        let kind = match zone.kind() {
+            ZoneKind::Other => None,
+            kind => Some(kind),
+        };
+
+        ZoneDocument {
+            id: zone.get_zone_num(),
+            sum,
+            distinct,
+            kind,
+        }
+    }
+}
+
+impl From<ZoneDocument> for Zone {
+    fn from(doc: ZoneDocument) -> Self {
+        let zone = match doc.sum {
+            Some(sum) if doc.distinct => Zone::new_cage_from_u16(doc.id, sum),
+            Some(sum) => Zone::new_sum_from_u16(doc.id, sum),
+            None => Zone::new_unique_from_u16(doc.id),
+        };
+        zone.with_kind(doc.kind.unwrap_or_default())
+    }
+}
+
+impl TableDocument {
+    /// 가로, 세로, region zone으로 이루어진 구조의 문서를 만듭니다. given과 노트는 비어있습니다.
+    ///
+    /// regions는 cell 순서대로의 region 번호 (0부터 시작) 입니다.
+    /// zone 번호는 Table::new_default_9와 같이 region 1..=N, 세로 N+1..=2N, 가로 2N+1..=3N 입니다.
+    /// region은 ZoneKind::Box, 세로와 가로는 ZoneKind::Column, ZoneKind::Row 입니다.
+    #[must_use]
+    pub fn new_with_regions(size: usize, regions: &[usize]) -> Self {
+        let n = size as u16;
+        let cells = regions
+            .iter()
+            .enumerate()
+            .map(|(index, &region)| {
+                let (x, y) = (index % size, index / size);
+                let (region, x_u16, y_u16) = (region as u16, x as u16, y as u16);
+                let zones = [
+                    (region + 1, ZoneKind::Box(region + 1)),
+                    (n + x_u16 + 1, ZoneKind::Column(x_u16 + 1)),
+                    (n * 2 + y_u16 + 1, ZoneKind::Row(y_u16 + 1)),
+                ]
+                .into_iter()
+                .map(|(id, kind)| ZoneDocument {
+                    id,
+                    sum: None,
+                    distinct: false,
+                    kind: Some(kind),
+                })
+                .collect();
+
+                CellDocument {
+                    x,
+                    y,
+                    zones,
+                    given: None,
+                    status: None,
+                    candidates: None,
+                }
+            })
+            .collect();
+
+        TableDocument {
+            size,
+            width: None,
+            height: None,
+            symbols: None,
+            cells,
+        }
+    }
+
+    /// 테이블의 구조와 현재 노트를 문서로 만듭니다.
+    #[must_use]
+    pub fn from_table<const N: usize>(table: &TableLock<N>) -> Self {
+        let read = table.read_lock();
+        let cells = read
+            .into_iter()
+            .map(|(cell, chk)| {
+                let (x, y) = cell.get_coordinate();
+                let (given, candidates) = note_to_document(chk);
+
+                CellDocument {
+                    x,
+                    y,
+                    zones: cell.get_zone().iter().map(|&z| z.into()).collect(),
+                    given,
+                    status: Some(chk.status()).filter(|status| {
+                        matches!(status, CellStatus::Solved | CellStatus::UserEntered)
+                    }),
+                    candidates,
+                }
+            })
+            .collect();
+
+        let canvas = !table.is_full_grid();
+        TableDocument {
+            size: N,
+            width: canvas.then(|| table.width()),
+            height: canvas.then(|| table.height()),
+            symbols: (!table.symbols().is_default()).then(|| table.symbols().as_slice().to_vec()),
+            cells,
+        }
+    }
+
+    /// 문서로부터 테이블을 만듭니다. 구조가 올바르지 않거나 given이 서로 충돌하면 오류를 반환합니다.
+    pub fn to_table<const N: usize>(&self) -> Result<TableLock<N>, ParseError> {
+        if self.size != N {
+            return Err(ParseError::SizeMismatch {
+                expected: N,
+                found: self.size,
+            });
+        }
+
+        let canvas = self.width.is_some() || self.height.is_some();
+        let (width, height) = (self.width.unwrap_or(N), self.height.unwrap_or(N));
+
+        let mut ordered: Vec<Option<&CellDocument>> = vec![None; width * height];
+        for doc in &self.cells {
+            let slot = (doc.x < width && doc.y < height)
+                .then(|| &mut ordered[doc.x + doc.y * width])
+                .filter(|slot| slot.is_none());
+            let Some(slot) = slot else {
+                return Err(ParseError::InvalidCell { x: doc.x, y: doc.y });
+            };
+            *slot = Some(doc);
+        }
+
+        let mut docs: Vec<&CellDocument> = Vec::with_capacity(self.cells.len());
+        for (index, doc) in ordered.into_iter().enumerate() {
+            match doc {
+                Some(doc) => docs.push(doc),
+                None if canvas => {}
+                None => {
+                    return Err(ParseError::MissingCell {
+                        x: index % width,
+                        y: index / width,
+                    });
+                }
+            }
+        }
+
+        validate_zones::<N>(&docs)?;
+
+        let mut givens: Vec<Option<MaxNum<N>>> = Vec::with_capacity(docs.len());
+        let mut notes: Vec<Option<ArrayVector<MaxNum<N>, N>>> = Vec::with_capacity(docs.len());
+        for doc in &docs {
+            let to_num = |value: usize| {
+                value
+                    .checked_sub(1)
+                    .and_then(MaxNum::<N>::new_optional)
+                    .ok_or(ParseError::InvalidValue {
+                        x: doc.x,
+                        y: doc.y,
+                        value,
+                    })
+            };
+
+            givens.push(doc.given.map(to_num).transpose()?);
+            notes.push(match &doc.candidates {
+                Some(candidates) => Some(
+                    candidates
+                        .iter()
+                        .map(|&value| to_num(value))
+                        .collect::<Result<_, _>>()?,
+                ),
+                None => None,
+            });
+        }
+
+        let cells = docs
+            .iter()
+            .map(|doc| {
+                let zone = doc.zones.iter().map(|&z| z.into()).collect();
+                Cell::new(doc.x, doc.y, zone)
+            })
+            .collect();
+        let mut table = Table::new_with_canvas(width, height, cells);
+        if let Some(symbols) = &self.symbols {
+            let symbols =
+                SymbolSet::try_new(symbols.iter().cloned()).map_err(|err| ParseError::Json {
+                    message: err.to_string(),
+                })?;
+            table.set_symbols(symbols);
+        }
+
+        check_givens(&table, &givens)?;
+
+        let mut write = table.write_lock();
+        for ((((_, chk), given), note), doc) in
+            (&mut write).into_iter().zip(givens).zip(notes).zip(&docs)
+        {
+            match (given, note) {
+                (Some(value), _) => {
+                    chk.set_to_value(value);
+                    chk.set_status(doc.status.unwrap_or(CellStatus::Given));
+                }
+                (None, Some(note)) => chk.set_to_chk_list(&note),
+                (None, None) => {}
+            }
+        }
+        drop(write);
+
+        Ok(table)
+    }
+}
+
+/// 노트를 문서의 (given, candidates)로 변환합니다. 모든 노트가 true인 경우 candidates는 생략됩니다.
+fn note_to_document<const N: usize>(chk: &NumCheck<N>) -> (Option<usize>, Option<Vec<usize>>) {
+    if let Some(num) = chk.final_num() {
+        return (Some(num.get_value() + 1), None);
+    }
+
+    if chk.true_cnt() == N {
+        return (None, None);
+    }
+
+    let candidates = MaxNum::<N>::iter()
+        .filter(|&n| chk.get_chk(n))
+        .map(|n| n.get_value() + 1)
+        .collect();
+    (None, Some(candidates))
+}
+
+/// 각 cell의 zone이 중복되지 않고, 같은 번호의 zone이 모두 같은 종류이며, Unique zone이 N개의 cell을 가지는지 검사합니다.
+fn validate_zones<const N: usize>(docs: &[&CellDocument]) -> Result<(), ParseError> {
+    let mut zone_cnt: IndexKeyMap<Zone, (ZoneDocument, usize)> = IndexKeyMap::new();
+
+    for doc in docs {
+        let mut cell_zone: IndexKeySet<Zone> = IndexKeySet::new();
+
+        for &zone_doc in &doc.zones {
+            let zone: Zone = zone_doc.into();
+            if cell_zone.contains(&zone) {
+                return Err(ParseError::DuplicateZone {
+                    x: doc.x,
+                    y: doc.y,
+                    zone: zone_doc.id,
+                });
+            }
+            cell_zone.insert(zone);
+
+            let (first, cnt) = zone_cnt.entry_or_insert_with(zone, || (zone_doc, 0));
+            if *first != zone_doc {
+                return Err(ParseError::InconsistentZone { zone: zone_doc.id });
+            }
+            *cnt += 1;
+        }
+    }
+
+    for (_, (zone_doc, cnt)) in &zone_cnt {
+        if zone_doc.sum.is_none() && *cnt != N {
+            return Err(ParseError::InvalidZoneSize {
+                zone: zone_doc.id,
+                expected: N,
+                found: *cnt,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+impl<const N: usize> Table<N> {
+    /// TableDocument 형식의 JSON 문자열로부터 테이블을 만듭니다.
+    pub fn new_from_json(json: &str) -> Result<TableLock<N>, ParseError> {
+        let doc: TableDocument = serde_json::from_str(json).map_err(|err| ParseError::Json {
+            message: err.to_string(),
+        })?;
+        doc.to_table()
+    }
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 테이블의 구조와 현재 노트를 TableDocument 형식의 JSON 문자열로 출력합니다.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&TableDocument::from_table(self))
+            .expect("TableDocument는 항상 직렬화 가능")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_round_trip() {
+        let t = Table::new_default_9_from_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        t.write_lock()
+            .write_from_coordinate(MaxNum::new(2), MaxNum::new(0))
+            .set_to_chk_list(&[MaxNum::new(0), MaxNum::new(3)]);
+
+        let t2 = Table::<9>::new_from_json(&t.to_json()).unwrap();
+        assert_eq!(t, t2);
+        assert_eq!(
+            TableDocument::from_table(&t),
+            TableDocument::from_table(&t2)
+        );
+    }
+
+    #[test]
+    fn cell_status_survives_solve_and_export() {
+        use crate::solver::{GeneralSolve, Solver};
+
+        let mut t = Table::new_default_9_from_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        t.write_lock()
+            .write_from_coordinate(MaxNum::new(2), MaxNum::new(0))
+            .set_status(CellStatus::UserEntered);
+        Solver::new_with_seed(&mut t, 0).fill_puzzle_with_timeout(std::time::Duration::MAX);
+
+        let status =
+            |t: &TableLock<9>, index: usize| t.read_lock().read_from_cell(&t[index]).status();
+        assert_eq!(status(&t, 0), CellStatus::Given);
+        assert_eq!(status(&t, 2), CellStatus::UserEntered);
+        assert_eq!(status(&t, 3), CellStatus::Solved);
+
+        let t2 = Table::<9>::new_from_json(&t.to_json()).unwrap();
+        for index in [0, 2, 3] {
+            assert_eq!(status(&t2, index), status(&t, index));
+        }
+        assert_eq!(
+            t2.read_lock().to_string_givens(),
+            Table::new_default_9_from_line(
+                "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+            )
+            .unwrap()
+            .read_lock()
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn json_layout_errors() {
+        let mut doc = TableDocument::from_table(&Table::new_default_9());
+
+        doc.cells[0].zones[1] = ZoneDocument {
+            id: 11,
+            sum: None,
+            distinct: false,
+            kind: Some(ZoneKind::Column(2)),
+        };
+        assert_eq!(
+            doc.to_table::<9>().err(),
+            Some(ParseError::InvalidZoneSize {
+                zone: 10,
+                expected: 9,
+                found: 8
+            })
+        );
+
+        doc.cells[0].zones[1] = ZoneDocument {
+            id: 10,
+            sum: Some(45),
+            distinct: false,
+            kind: Some(ZoneKind::Column(1)),
+        };
+        assert_eq!(
+            doc.to_table::<9>().err(),
+            Some(ParseError::InconsistentZone { zone: 10 })
+        );
+
+        doc.cells.pop();
+        assert_eq!(
+            doc.to_table::<16>().err(),
+            Some(ParseError::SizeMismatch {
+                expected: 16,
+                found: 9
+            })
+        );
+        assert_eq!(
+            doc.to_table::<9>().err(),
+            Some(ParseError::MissingCell { x: 8, y: 8 })
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use super::{ParseError, check_givens};
+use crate::model::{max_num::MaxNum, symbol_set::SymbolSet, table::Table, table_lock::TableLock};
+use crate::num_check::{CellStatus, NumCheck};
+
+/// 한 줄 형식에서 빈 칸을 나타내는 문자입니다. '0'이 기호로 설정된 경우 '0'은 값으로 읽습니다.
+pub const BLANK_CHARS: [char; 2] = ['.', '0'];
+
+/// 한 줄 형식의 퍼즐을 칸마다의 given 목록으로 변환합니다.
+///
+/// 빈 칸은 '.' 또는 '0', 값은 MaxNum::get_char의 문자를 사용하며 앞뒤 공백은 무시합니다.
+pub fn parse_line<const N: usize>(line: &str) -> Result<Vec<Option<MaxNum<N>>>, ParseError> {
+    parse_line_with(line, N * N, &SymbolSet::default())
+}
+
+/// parse_line과 같으나, symbols의 기호로 읽고 칸 개수가 cell_cnt인지 검사합니다.
+///
+/// 여러 글자 기호가 있는 경우 칸은 공백으로 구분합니다.
+fn parse_line_with<const N: usize>(
+    line: &str,
+    cell_cnt: usize,
+    symbols: &SymbolSet<N>,
+) -> Result<Vec<Option<MaxNum<N>>>, ParseError> {
+    let leading = line.chars().take_while(|c| c.is_whitespace()).count();
+    let line = line.trim();
+    let mut givens: Vec<Option<MaxNum<N>>> = Vec::with_capacity(cell_cnt);
+
+    let tokens: Vec<(usize, &str)> = if symbols.is_single_char() {
+        char_tokens(line).collect()
+    } else {
+        split_tokens(line, char::is_whitespace).collect()
+    };
+
+    for (pos, token) in tokens {
+        let Some(given) = parse_cell_token(symbols, token) else {
+            return Err(ParseError::InvalidSymbol {
+                line: 1,
+                column: leading + pos + 1,
+                symbol: token.to_string(),
+            });
+        };
+        givens.push(given);
+    }
+
+    if givens.len() != cell_cnt {
+        return Err(ParseError::InvalidLength {
+            expected: cell_cnt,
+            found: givens.len(),
+        });
+    }
+
+    Ok(givens)
+}
+
+/// 칸 하나의 토큰을 읽습니다. 빈 칸이면 Some(None), 알 수 없는 토큰이면 None
+///
+/// 기호가 빈 칸 문자와 같은 경우 기호로 읽습니다.
+pub fn parse_cell_token<const N: usize>(
+    symbols: &SymbolSet<N>,
+    token: &str,
+) -> Option<Option<MaxNum<N>>> {
+    if let Some(num) = symbols.parse(token) {
+        return Some(Some(num));
+    }
+
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if BLANK_CHARS.contains(&c) => Some(None),
+        _ => None,
+    }
+}
+
+/// 문자 하나씩을 토큰으로, 문자 단위의 위치와 함께 반환합니다.
+pub(crate) fn char_tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.char_indices()
+        .enumerate()
+        .map(|(pos, (i, c))| (pos, &line[i..i + c.len_utf8()]))
+}
+
+/// 구분 문자로 나눈 비어있지 않은 토큰과, 토큰이 시작하는 위치를 문자 단위로 반환합니다.
+pub(crate) fn split_tokens(
+    line: &str,
+    is_separator: impl Fn(char) -> bool,
+) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 0;
+    line.split(move |c: char| is_separator(c))
+        .map(move |token| {
+            let token_column = column;
+            column += token.chars().count() + 1;
+            (token_column, token)
+        })
+        .filter(|(_, token)| !token.is_empty())
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 한 줄 형식의 퍼즐을 읽어 given을 채웁니다. given이 아닌 cell은 모든 노트가 true로 초기화됩니다.
+    ///
+    /// 칸은 cell의 순서와 같으며, 여러 격자가 겹친 퍼즐에서는 cell이 없는 칸을 건너뜁니다.
+    /// 값은 테이블에 설정된 기호로 읽습니다.
+    /// 오류가 발생한 경우 테이블은 변경되지 않습니다.
+    pub fn set_givens_from_line(&self, line: &str) -> Result<(), ParseError> {
+        let givens = parse_line_with(line, self.len(), self.symbols())?;
+        self.set_givens(&givens)
+    }
+
+    /// 칸마다의 given 목록으로 테이블을 채웁니다. givens의 순서는 cell의 순서와 같아야 합니다.
+    ///
+    /// 값이 있는 cell은 CellStatus::Given이 됩니다.
+    pub fn set_givens(&self, givens: &[Option<MaxNum<N>>]) -> Result<(), ParseError> {
+        if givens.len() != self.len() {
+            return Err(ParseError::InvalidLength {
+                expected: self.len(),
+                found: givens.len(),
+            });
+        }
+
+        check_givens(self, givens)?;
+
+        let mut write = self.write_lock();
+        for ((_, chk), given) in (&mut write).into_iter().zip(givens) {
+            match given {
+                Some(value) => {
+                    *chk = NumCheck::new_with_true();
+                    chk.set_to_value(*value);
+                    chk.set_status(CellStatus::Given);
+                }
+                None => *chk = NumCheck::new_with_true(),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 확정된 값만 한 줄 형식으로 출력합니다. 확정되지 않은 cell은 '.'으로 표시됩니다.
+    ///
+    /// 여러 글자 기호가 있는 경우 칸을 공백으로 구분합니다.
+    #[must_use]
+    pub fn to_line(&self) -> String {
+        let read = self.read_lock();
+        let separator = if self.symbols().is_single_char() {
+            ""
+        } else {
+            " "
+        };
+        let mut ret = String::with_capacity(self.len() * (self.symbols().width() + 1));
+
+        for (index, (_, chk)) in read.into_iter().enumerate() {
+            if index != 0 {
+                ret.push_str(separator);
+            }
+            match chk.final_num() {
+                Some(num) => ret.push_str(self.symbols().get(num)),
+                None => ret.push(BLANK_CHARS[0]),
+            }
+        }
+
+        ret
+    }
+}
+
+impl Table<9> {
+    /// 9X9 기본 스도쿠 구조에 한 줄 형식의 given을 채웁니다.
+    pub fn new_default_9_from_line(line: &str) -> Result<TableLock<9>, ParseError> {
+        let t = Table::new_default_9();
+        t.set_givens_from_line(line)?;
+        Ok(t)
+    }
+}
+
+impl Table<16> {
+    /// 16X16 기본 스도쿠 구조에 한 줄 형식의 given을 채웁니다.
+    pub fn new_default_16_from_line(line: &str) -> Result<TableLock<16>, ParseError> {
+        let t = Table::new_default_16();
+        t.set_givens_from_line(line)?;
+        Ok(t)
+    }
+}
+
+impl Table<32> {
+    /// 32X32 기본 스도쿠 구조에 한 줄 형식의 given을 채웁니다.
+    pub fn new_default_32_from_line(line: &str) -> Result<TableLock<32>, ParseError> {
+        let t = Table::new_default_32();
+        t.set_givens_from_line(line)?;
+        Ok(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{GeneralSolve, Solver};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn parse_and_solve_line() {
+        let mut t = Table::new_default_9_from_line(PUZZLE).unwrap();
+        assert_eq!(t.to_line(), PUZZLE);
+
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        assert_eq!(solver.fill_puzzle_with_timeout(std::time::Duration::MAX), 0);
+        drop(solver);
+
+        assert_eq!(
+            t.to_line(),
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179"
+        );
+    }
+
+    #[test]
+    fn parse_line_errors() {
+        assert_eq!(
+            Table::new_default_9_from_line(&PUZZLE[1..]).err(),
+            Some(ParseError::InvalidLength {
+                expected: 81,
+                found: 80
+            })
+        );
+
+        let bad_symbol = PUZZLE.replacen('.', "A", 1);
+        assert_eq!(
+            Table::new_default_9_from_line(&bad_symbol).err(),
+            Some(ParseError::InvalidSymbol {
+                line: 1,
+                column: 3,
+                symbol: "A".to_string()
+            })
+        );
+
+        // 첫 줄의 빈 칸에 5를 넣으면 (0, 0)의 5와 충돌
+        let contradiction = PUZZLE.replacen('.', "5", 1);
+        assert_eq!(
+            Table::new_default_9_from_line(&contradiction).err(),
+            Some(ParseError::ContradictoryGivens {
+                x: 2,
+                y: 0,
+                other_x: 0,
+                other_y: 0,
+                symbol: "5".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,575 @@
+use super::{
+    ParseError, default_regions,
+    json::{CellDocument, TableDocument, ZoneDocument},
+};
+use crate::model::{max_num::MaxNum, table::Table, table_lock::TableLock, zone::ZoneKind};
+use crate::punch::Punch;
+
+/// 현재 공유 코드의 버전입니다. 형식이 바뀌면 올려야 합니다.
+///
+/// 버전 2부터 모든 zone 목록을 담는 경우 zone의 종류 (ZoneKind)를 함께 담습니다.
+/// 버전 3부터 합이 있는 zone은 값이 서로 달라야 하는지 (킬러 cage)를 함께 담습니다. 이전 버전의 코드도 읽을 수 있습니다.
+pub const SHARE_CODE_VERSION: u8 = 3;
+
+/// 종류 없이 zone 목록을 담던 버전
+const VERSION_WITHOUT_KIND: u8 = 1;
+
+/// 합이 있는 zone의 distinct 여부 없이 zone 목록을 담던 버전
+const VERSION_WITHOUT_DISTINCT: u8 = 2;
+
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// 공유 코드에 Zone 구조를 담는 방식입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayoutMode {
+    /// 가로, 세로, 기본 박스. 구조 데이터를 담지 않습니다.
+    Standard = 0,
+    /// 가로, 세로, cell별 region 번호 (직소 스도쿠 등)
+    Regions = 1,
+    /// 모든 cell의 zone 목록
+    Zones = 2,
+}
+
+/// 공유 코드를 읽은 결과입니다.
+pub struct SharedPuzzle<const N: usize> {
+    /// given이 채워진 테이블
+    pub table: TableLock<N>,
+    /// 공유 코드에 정답이 포함된 경우 cell 순서대로의 정답
+    pub solution: Option<Vec<MaxNum<N>>>,
+}
+
+/// 테이블의 구조와 given, 정답을 URL에 그대로 넣을 수 있는 짧은 문자열로 만듭니다.
+///
+/// 코드는 버전, 크기, 구조, given, 정답 순서의 비트열을 base64url (padding 없음)로 표현한 것입니다.
+/// 기본 구조와 직소 구조는 짧게 표현되며, 그 외의 구조는 모든 zone 목록을 담습니다.
+/// 여러 격자가 겹친 퍼즐과 같이 N X N 격자가 아닌 테이블은 UnsupportedLayout 오류를 반환합니다.
+pub fn encode<const N: usize>(
+    table: &TableLock<N>,
+    givens: &[Option<MaxNum<N>>],
+    solution: Option<&[MaxNum<N>]>,
+) -> Result<String, ParseError> {
+    if !table.is_full_grid() {
+        return Err(ParseError::UnsupportedLayout {
+            format: "공유 코드",
+        });
+    }
+
+    let doc = TableDocument::from_table(table);
+    let regions: Option<Vec<usize>> = doc
+        .cells
+        .iter()
+        .map(|c| {
+            let first = c.zones.first()?;
+            (first.sum.is_none() && first.id >= 1 && usize::from(first.id) <= N)
+                .then(|| usize::from(first.id) - 1)
+        })
+        .collect();
+
+    let layout_mode = if same_zones(
+        &doc,
+        &TableDocument::new_with_regions(N, &default_regions(N)),
+    ) {
+        LayoutMode::Standard
+    } else if regions
+        .as_ref()
+        .is_some_and(|r| same_zones(&doc, &TableDocument::new_with_regions(N, r)))
+    {
+        LayoutMode::Regions
+    } else {
+        LayoutMode::Zones
+    };
+
+    let mut writer = BitWriter::default();
+    writer.write(u64::from(SHARE_CODE_VERSION), 8);
+    writer.write_varint(N as u64);
+    writer.write(layout_mode as u64, 2);
+    writer.write(u64::from(solution.is_some()), 1);
+
+    match layout_mode {
+        LayoutMode::Standard => {}
+        LayoutMode::Regions => {
+            let bits = bits_for(N - 1);
+            for &region in regions.iter().flatten() {
+                writer.write(region as u64, bits);
+            }
+        }
+        LayoutMode::Zones => {
+            let mut zone_list: Vec<ZoneDocument> = Vec::new();
+            for z in doc.cells.iter().flat_map(|c| &c.zones) {
+                if !zone_list.contains(z) {
+                    zone_list.push(*z);
+                }
+            }
+
+            writer.write_varint(zone_list.len() as u64);
+            for z in &zone_list {
+                writer.write_varint(u64::from(z.id));
+                writer.write(u64::from(z.sum.is_some()), 1);
+                if let Some(sum) = z.sum {
+                    writer.write_varint(sum as u64);
+                    writer.write(u64::from(z.distinct), 1);
+                }
+                write_kind(&mut writer, z.kind);
+            }
+
+            let bits = bits_for(zone_list.len().saturating_sub(1));
+            for c in &doc.cells {
+                writer.write_varint(c.zones.len() as u64);
+                for z in &c.zones {
+                    let index = zone_list.iter().position(|l| l == z).unwrap_or_default();
+                    writer.write(index as u64, bits);
+                }
+            }
+        }
+    }
+
+    let given_bits = bits_for(N);
+    for given in givens {
+        writer.write(given.map_or(0, |n| n.get_value() as u64 + 1), given_bits);
+    }
+
+    if let Some(solution) = solution {
+        let bits = bits_for(N - 1);
+        for n in solution {
+            writer.write(n.get_value() as u64, bits);
+        }
+    }
+
+    Ok(base64_url_encode(&writer.bytes))
+}
+
+/// 공유 코드로부터 테이블과 정답을 만듭니다.
+pub fn decode<const N: usize>(code: &str) -> Result<SharedPuzzle<N>, ParseError> {
+    let bytes = base64_url_decode(code.trim())?;
+    let mut reader = BitReader {
+        bytes: &bytes,
+        pos: 0,
+    };
+
+    let (version, size) = read_header(&mut reader)?;
+    if size != N {
+        return Err(ParseError::SizeMismatch {
+            expected: N,
+            found: size,
+        });
+    }
+
+    let layout_mode = match reader.read(2)? {
+        0 => LayoutMode::Standard,
+        1 => LayoutMode::Regions,
+        2 => LayoutMode::Zones,
+        _ => {
+            return Err(ParseError::InvalidShareCode {
+                reason: "알 수 없는 구조 형식",
+            });
+        }
+    };
+    let has_solution = reader.read(1)? == 1;
+
+    let mut doc = match layout_mode {
+        LayoutMode::Standard => TableDocument::new_with_regions(N, &default_regions(N)),
+        LayoutMode::Regions => {
+            let bits = bits_for(N - 1);
+            let regions = (0..N * N)
+                .map(|_| reader.read(bits).map(|r| r as usize))
+                .collect::<Result<Vec<usize>, _>>()?;
+            TableDocument::new_with_regions(N, &regions)
+        }
+        LayoutMode::Zones => {
+            let zone_cnt = reader.read_varint()? as usize;
+            let mut zone_list: Vec<ZoneDocument> = Vec::with_capacity(zone_cnt.min(N * N));
+            for _ in 0..zone_cnt {
+                let id = u16::try_from(reader.read_varint()?).map_err(|_| {
+                    ParseError::InvalidShareCode {
+                        reason: "zone 번호가 너무 큼",
+                    }
+                })?;
+                let (sum, distinct) = match reader.read(1)? {
+                    1 => {
+                        let sum = reader.read_varint()? as usize;
+                        let distinct = version > VERSION_WITHOUT_DISTINCT && reader.read(1)? == 1;
+                        (Some(sum), distinct)
+                    }
+                    _ => (None, false),
+                };
+                let kind = if version > VERSION_WITHOUT_KIND {
+                    read_kind(&mut reader)?
+                } else {
+                    None
+                };
+                zone_list.push(ZoneDocument {
+                    id,
+                    sum,
+                    distinct,
+                    kind,
+                });
+            }
+
+            let bits = bits_for(zone_cnt.saturating_sub(1));
+            let mut cells: Vec<CellDocument> = Vec::with_capacity(N * N);
+            for index in 0..N * N {
+                let cnt = reader.read_varint()? as usize;
+                let zones = (0..cnt)
+                    .map(|_| {
+                        let zone_index = reader.read(bits)? as usize;
+                        zone_list
+                            .get(zone_index)
+                            .copied()
+                            .ok_or(ParseError::InvalidShareCode {
+                                reason: "존재하지 않는 zone",
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                cells.push(CellDocument {
+                    x: index % N,
+                    y: index / N,
+                    zones,
+                    given: None,
+                    status: None,
+                    candidates: None,
+                });
+            }
+            TableDocument {
+                size: N,
+                width: None,
+                height: None,
+                symbols: None,
+                cells,
+            }
+        }
+    };
+
+    let given_bits = bits_for(N);
+    for cell in &mut doc.cells {
+        let value = reader.read(given_bits)? as usize;
+        cell.given = (value != 0).then_some(value);
+    }
+
+    let solution = if has_solution {
+        let bits = bits_for(N - 1);
+        let solution = (0..N * N)
+            .map(|_| {
+                let value = reader.read(bits)? as usize;
+                MaxNum::new_optional(value).ok_or(ParseError::InvalidShareCode {
+                    reason: "범위를 벗어난 정답",
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Some(solution)
+    } else {
+        None
+    };
+
+    Ok(SharedPuzzle {
+        table: doc.to_table()?,
+        solution,
+    })
+}
+
+/// 공유 코드의 퍼즐 크기만 읽습니다. 크기를 모르는 상태에서 어떤 TableLock<N>으로 읽을지 정할 때 사용합니다.
+pub fn decode_size(code: &str) -> Result<usize, ParseError> {
+    let bytes = base64_url_decode(code.trim())?;
+    read_header(&mut BitReader {
+        bytes: &bytes,
+        pos: 0,
+    })
+    .map(|(_, size)| size)
+}
+
+/// 버전을 확인하고 (버전, 퍼즐 크기)를 반환합니다.
+fn read_header(reader: &mut BitReader) -> Result<(u8, usize), ParseError> {
+    let version = reader.read(8)? as u8;
+    if !(VERSION_WITHOUT_KIND..=SHARE_CODE_VERSION).contains(&version) {
+        return Err(ParseError::UnsupportedVersion { version });
+    }
+
+    Ok((version, reader.read_varint()? as usize))
+}
+
+/// zone의 종류를 3비트 태그와 종류 번호로 씁니다. 종류가 없으면 태그 0만 씁니다.
+fn write_kind(writer: &mut BitWriter, kind: Option<ZoneKind>) {
+    let (tag, num) = match kind.unwrap_or_default() {
+        ZoneKind::Other => (0, None),
+        ZoneKind::Row(num) => (1, Some(num)),
+        ZoneKind::Column(num) => (2, Some(num)),
+        ZoneKind::Box(num) => (3, Some(num)),
+        ZoneKind::Cage(num) => (4, Some(num)),
+        ZoneKind::Diagonal(num) => (5, Some(num)),
+    };
+
+    writer.write(tag, 3);
+    if let Some(num) = num {
+        writer.write_varint(u64::from(num));
+    }
+}
+
+fn read_kind(reader: &mut BitReader) -> Result<Option<ZoneKind>, ParseError> {
+    let kind: fn(u16) -> ZoneKind = match reader.read(3)? {
+        0 => return Ok(None),
+        1 => ZoneKind::Row,
+        2 => ZoneKind::Column,
+        3 => ZoneKind::Box,
+        4 => ZoneKind::Cage,
+        5 => ZoneKind::Diagonal,
+        _ => {
+            return Err(ParseError::InvalidShareCode {
+                reason: "알 수 없는 zone 종류",
+            });
+        }
+    };
+
+    let num = u16::try_from(reader.read_varint()?).map_err(|_| ParseError::InvalidShareCode {
+        reason: "zone 종류 번호가 너무 큼",
+    })?;
+    Ok(Some(kind(num)))
+}
+
+/// 두 문서의 zone 번호와 합, distinct 여부가 같은지 비교합니다. 기본 구조와 직소 구조는 종류를 담지 않고 Box, Column, Row로 읽으므로
+/// kind는 비교하지 않습니다.
+fn same_zones(a: &TableDocument, b: &TableDocument) -> bool {
+    let structure = |z: &ZoneDocument| (z.id, z.sum, z.distinct);
+    a.cells.len() == b.cells.len()
+        && a.cells.iter().zip(&b.cells).all(|(c1, c2)| {
+            c1.zones.len() == c2.zones.len()
+                && c1
+                    .zones
+                    .iter()
+                    .map(structure)
+                    .eq(c2.zones.iter().map(structure))
+        })
+}
+
+/// 0부터 max_value까지를 표현하는 데 필요한 비트 수
+#[inline]
+fn bits_for(max_value: usize) -> u32 {
+    usize::BITS - max_value.leading_zeros()
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_len: usize,
+}
+
+impl BitWriter {
+    /// value의 하위 bits개 비트를 상위 비트부터 씁니다.
+    fn write(&mut self, value: u64, bits: u32) {
+        for i in (0..bits).rev() {
+            if self.bit_len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0x80 >> (self.bit_len % 8);
+            }
+            self.bit_len += 1;
+        }
+    }
+
+    /// 7비트 단위로 나누어, 뒤에 데이터가 더 있는지를 나타내는 1비트와 함께 씁니다.
+    fn write_varint(&mut self, mut value: u64) {
+        loop {
+            let group = value & 0x7F;
+            value >>= 7;
+            self.write(u64::from(value != 0), 1);
+            self.write(group, 7);
+            if value == 0 {
+                break;
+            }
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, bits: u32) -> Result<u64, ParseError> {
+        let mut ret = 0u64;
+        for _ in 0..bits {
+            let byte = self
+                .bytes
+                .get(self.pos / 8)
+                .ok_or(ParseError::InvalidShareCode {
+                    reason: "데이터가 부족함",
+                })?;
+            let bit = (byte >> (7 - self.pos % 8)) & 1;
+            ret = (ret << 1) | u64::from(bit);
+            self.pos += 1;
+        }
+        Ok(ret)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, ParseError> {
+        let mut ret = 0u64;
+        for shift in (0..64).step_by(7) {
+            let more = self.read(1)? == 1;
+            ret |= self.read(7)? << shift;
+            if !more {
+                return Ok(ret);
+            }
+        }
+
+        Err(ParseError::InvalidShareCode {
+            reason: "숫자가 너무 큼",
+        })
+    }
+}
+
+fn base64_url_encode(bytes: &[u8]) -> String {
+    let mut ret = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buf = [0u8; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let value = u32::from(buf[0]) << 16 | u32::from(buf[1]) << 8 | u32::from(buf[2]);
+
+        for i in 0..=chunk.len() {
+            let index = (value >> (18 - i * 6)) & 0x3F;
+            ret.push(BASE64_URL[index as usize] as char);
+        }
+    }
+    ret
+}
+
+fn base64_url_decode(code: &str) -> Result<Vec<u8>, ParseError> {
+    let mut ret: Vec<u8> = Vec::with_capacity(code.len() * 3 / 4);
+    let mut buf = 0u32;
+    let mut buf_bits = 0;
+
+    for (column, c) in code.chars().enumerate() {
+        let Some(value) = BASE64_URL.iter().position(|&b| b as char == c) else {
+            return Err(ParseError::InvalidSymbol {
+                line: 1,
+                column: column + 1,
+                symbol: c.to_string(),
+            });
+        };
+
+        buf = buf << 6 | value as u32;
+        buf_bits += 6;
+        if buf_bits >= 8 {
+            buf_bits -= 8;
+            ret.push((buf >> buf_bits) as u8);
+            buf &= (1 << buf_bits) - 1;
+        }
+    }
+
+    Ok(ret)
+}
+
+impl<const N: usize> Table<N> {
+    /// 공유 코드로부터 테이블을 만듭니다.
+    pub fn new_from_share_code(code: &str) -> Result<SharedPuzzle<N>, ParseError> {
+        decode(code)
+    }
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 테이블의 구조와 확정된 값을 공유 코드로 만듭니다. 정답은 포함하지 않습니다.
+    /// N X N 격자가 아닌 경우 UnsupportedLayout 오류
+    pub fn to_share_code(&self) -> Result<String, ParseError> {
+        let givens: Vec<Option<MaxNum<N>>> = {
+            let read = self.read_lock();
+            read.into_iter().map(|(_, chk)| chk.final_num()).collect()
+        };
+        encode(self, &givens, None)
+    }
+}
+
+impl<const N: usize> Punch<'_, N> {
+    /// 구멍을 뚫은 퍼즐을 공유 코드로 만듭니다. 남아있는 fixed_final_num이 given이 됩니다.
+    ///
+    /// include_solution이 true인 경우 구멍을 뚫기 전의 완성된 퍼즐을 정답으로 포함합니다.
+    /// N X N 격자가 아닌 경우 UnsupportedLayout 오류
+    pub fn to_share_code(&self, include_solution: bool) -> Result<String, ParseError> {
+        let table = self.get_table();
+        let givens: Vec<Option<MaxNum<N>>> = {
+            let read = table.read_lock();
+            read.into_iter()
+                .map(|(_, chk)| chk.fixed_final_num())
+                .collect()
+        };
+        let solution: Option<Vec<MaxNum<N>>> = include_solution
+            .then(|| self.get_solution().iter().copied().collect())
+            .flatten();
+
+        encode(table, &givens, solution.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{GeneralSolve, Solver};
+
+    #[test]
+    fn share_code_round_trip() {
+        let mut t = Table::new_default_9();
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        solver.fill_puzzle_with_timeout(std::time::Duration::MAX);
+        let mut punch = solver.into_punch();
+        punch.punch_all();
+
+        let code = punch.to_share_code(true).unwrap();
+        assert!(code.len() < 120);
+        assert!(
+            code.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+
+        let shared = Table::<9>::new_from_share_code(&code).unwrap();
+        let solution = shared.solution.unwrap();
+        assert_eq!(
+            solution.iter().map(|n| Some(*n)).collect::<Vec<_>>(),
+            punch.get_solution()
+        );
+
+        let read = shared.table.read_lock();
+        let punch_read = punch.get_table().read_lock();
+        for ((_, chk), (_, punch_chk)) in read.into_iter().zip(&punch_read) {
+            assert_eq!(chk.final_num(), punch_chk.fixed_final_num());
+        }
+    }
+
+    #[test]
+    fn share_code_custom_layout() {
+        let fp = r#"{"size":4,"grid":[[{},{},{},{}],[{},{},{},{}],[{},{},{},{}],[{},{},{},{"value":2,"given":true}]],
+            "killercage":[{"cells":["R1C1","R1C2"],"value":"3"}]}"#;
+        let t = Table::<4>::new_from_fpuzzles(fp).unwrap().table;
+
+        let code = t.to_share_code().unwrap();
+        let shared = Table::<4>::new_from_share_code(&code).unwrap();
+        assert!(shared.solution.is_none());
+        assert_eq!(shared.table.to_json(), t.to_json());
+
+        assert_eq!(
+            Table::<9>::new_from_share_code(&code).err(),
+            Some(ParseError::SizeMismatch {
+                expected: 9,
+                found: 4
+            })
+        );
+        assert_eq!(
+            Table::<4>::new_from_share_code(&code[..code.len() - 4]).err(),
+            Some(ParseError::InvalidShareCode {
+                reason: "데이터가 부족함"
+            })
+        );
+
+        assert_eq!(
+            Table::new_samurai().to_share_code(),
+            Err(ParseError::UnsupportedLayout {
+                format: "공유 코드"
+            })
+        );
+        assert_eq!(
+            Table::new_samurai().to_fpuzzles(),
+            Err(ParseError::UnsupportedLayout {
+                format: "f-puzzles"
+            })
+        );
+    }
+}
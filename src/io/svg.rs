@@ -0,0 +1,360 @@
+use super::grid::note_rec_size;
+use crate::model::{
+    cell::Cell,
+    index_key_map::IndexKeyMap,
+    max_num::MaxNum,
+    table_lock::TableLock,
+    zone::{Zone, ZoneType},
+};
+use std::fmt::Write;
+
+const GIVEN_COLOR: &str = "#000000";
+const SOLVED_COLOR: &str = "#1e5aa8";
+const CANDIDATE_COLOR: &str = "#808080";
+const CAGE_COLOR: &str = "#404040";
+
+/// SVG 출력 설정입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// cell 한 칸의 크기 (px)
+    pub cell_size: usize,
+    /// 확정되지 않은 cell에 노트를 작게 표시할지 여부
+    pub show_candidates: bool,
+    /// given이 아닌 확정된 값을 표시할지 여부. 문제지를 출력할 때는 false로 설정합니다.
+    pub show_solved: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 48,
+            show_candidates: false,
+            show_solved: true,
+        }
+    }
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 테이블을 SVG 이미지로 출력합니다.
+    ///
+    /// CellStatus::Given인 cell은 given으로, 그 외에 확정된 cell은 푼 값으로 다른 색을 사용합니다.
+    #[must_use]
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let givens: Vec<Option<MaxNum<N>>> = {
+            let read = self.read_lock();
+            read.into_iter()
+                .map(|(_, chk)| chk.final_num().filter(|_| chk.is_given()))
+                .collect()
+        };
+        self.to_svg_with_givens(&givens, options)
+    }
+
+    /// 테이블을 SVG 이미지로 출력합니다. givens에 값이 있는 cell은 given 스타일로 표시됩니다.
+    ///
+    /// givens의 순서는 cell의 순서와 같아야 합니다. (예: 풀기 전에 parse_line으로 읽은 목록)
+    /// Unique zone의 경계는 rep_zone을 기준으로 굵은 선으로, Sum zone은 점선 테두리와 합계로 표시됩니다.
+    #[must_use]
+    pub fn to_svg_with_givens(&self, givens: &[Option<MaxNum<N>>], options: &SvgOptions) -> String {
+        self.to_svg_with_solution(givens, &[], options)
+    }
+
+    /// 풀이 결과를 SVG 이미지로 출력합니다. 정답지 출력 등에 사용합니다.
+    ///
+    /// given이 아닌 cell은 테이블의 값 대신 solution의 값을 푼 값 스타일로 표시합니다.
+    /// solution이 비어있거나 값이 None인 cell은 테이블의 값을 사용합니다.
+    #[must_use]
+    pub fn to_svg_with_solution(
+        &self,
+        givens: &[Option<MaxNum<N>>],
+        solution: &[Option<MaxNum<N>>],
+        options: &SvgOptions,
+    ) -> String {
+        let size = options.cell_size;
+        let margin = size / 4;
+        let full_width = size * self.width() + margin * 2;
+        let full_height = size * self.height() + margin * 2;
+        let mut ret = String::new();
+
+        let _ = writeln!(
+            ret,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{full_width}" height="{full_height}" viewBox="0 0 {full_width} {full_height}" font-family="sans-serif">"#
+        );
+        let _ = writeln!(
+            ret,
+            r#"<rect x="0" y="0" width="{full_width}" height="{full_height}" fill="white"/>"#
+        );
+
+        self.write_grid_lines(&mut ret, size, margin);
+        self.write_cages(&mut ret, size, margin);
+        self.write_digits(&mut ret, givens, solution, options, margin);
+
+        // 여러 격자가 겹친 퍼즐의 바깥 테두리는 write_grid_lines에서 cell마다 그림
+        if self.is_full_grid() {
+            let _ = writeln!(
+                ret,
+                r#"<rect x="{margin}" y="{margin}" width="{w}" height="{w}" fill="none" stroke="black" stroke-width="3"/>"#,
+                w = size * N
+            );
+        }
+        ret.push_str("</svg>\n");
+        ret
+    }
+
+    /// 모든 cell의 얇은 테두리와, rep_zone이 달라지는 곳의 굵은 테두리를 그립니다.
+    ///
+    /// 캔버스의 일부 칸에만 cell이 있는 경우 cell이 없는 칸과의 경계도 굵은 테두리로 그립니다.
+    fn write_grid_lines(&self, ret: &mut String, size: usize, margin: usize) {
+        ret.push_str(r#"<g stroke="black" stroke-linecap="square">"#);
+        ret.push('\n');
+
+        let outline = !self.is_full_grid();
+        for cell in self.iter() {
+            let (x, y) = cell.get_coordinate();
+            let (left, top) = (margin + x * size, margin + y * size);
+            let (right, bottom) = (left + size, top + size);
+
+            // 오른쪽, 아래, 왼쪽, 위 순서의 (이웃 cell, 경계선 좌표)
+            let edges = [
+                (
+                    self.get_cell_from_canvas(x + 1, y),
+                    (right, top, right, bottom),
+                ),
+                (
+                    self.get_cell_from_canvas(x, y + 1),
+                    (left, bottom, right, bottom),
+                ),
+                (
+                    x.checked_sub(1)
+                        .and_then(|prev_x| self.get_cell_from_canvas(prev_x, y)),
+                    (left, top, left, bottom),
+                ),
+                (
+                    y.checked_sub(1)
+                        .and_then(|prev_y| self.get_cell_from_canvas(x, prev_y)),
+                    (left, top, right, top),
+                ),
+            ];
+
+            for (side, (other, (x1, y1, x2, y2))) in edges.into_iter().enumerate() {
+                let w = match other {
+                    // 왼쪽, 위의 경계는 이웃 cell에서 그림
+                    Some(_) if side >= 2 => continue,
+                    Some(other) if other.rep_zone() != cell.rep_zone() => 3,
+                    Some(_) => 1,
+                    None if outline => 3,
+                    None => continue,
+                };
+                let _ = writeln!(
+                    ret,
+                    r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke-width="{w}"/>"#
+                );
+            }
+        }
+
+        ret.push_str("</g>\n");
+    }
+
+    /// Sum zone마다 cell 안쪽에 점선 테두리를 그리고, 가장 위 왼쪽 cell에 합계를 씁니다.
+    fn write_cages(&self, ret: &mut String, size: usize, margin: usize) {
+        let mut cages: IndexKeyMap<Zone, Vec<&Cell<N>>> = IndexKeyMap::new();
+        for cell in self.iter() {
+            for zone in cell.get_zone() {
+                if let ZoneType::Sum { .. } = zone.get_zone_type() {
+                    cages.entry_or_insert_with(*zone, Vec::new).push(cell);
+                }
+            }
+        }
+
+        let inset = (size / 12).max(2);
+        let font = size / 4;
+
+        for (zone, cells) in &cages {
+            let ZoneType::Sum { sum, .. } = zone.get_zone_type() else {
+                continue;
+            };
+            let in_cage = |x: Option<usize>, y: Option<usize>| match (x, y) {
+                (Some(x), Some(y)) => self
+                    .get_cell_from_canvas(x, y)
+                    .is_some_and(|other| cells.contains(&other)),
+                _ => false,
+            };
+
+            let _ = writeln!(
+                ret,
+                r#"<g stroke="{CAGE_COLOR}" stroke-width="1" stroke-dasharray="4,3" fill="none">"#
+            );
+            for cell in cells {
+                let (x, y) = cell.get_coordinate();
+                let (left, top) = (margin + x * size, margin + y * size);
+                let (right, bottom) = (left + size, top + size);
+                let up = in_cage(Some(x), y.checked_sub(1));
+                let down = in_cage(Some(x), Some(y + 1));
+                let prev = in_cage(x.checked_sub(1), Some(y));
+                let next = in_cage(Some(x + 1), Some(y));
+
+                // 이웃 cell도 같은 zone이면 선을 cell 경계까지 이어 그림
+                let (x1, x2) = (
+                    if prev { left } else { left + inset },
+                    if next { right } else { right - inset },
+                );
+                let (y1, y2) = (
+                    if up { top } else { top + inset },
+                    if down { bottom } else { bottom - inset },
+                );
+
+                if !up {
+                    let _ = writeln!(
+                        ret,
+                        r#"<line x1="{x1}" y1="{y}" x2="{x2}" y2="{y}"/>"#,
+                        y = top + inset
+                    );
+                }
+                if !down {
+                    let _ = writeln!(
+                        ret,
+                        r#"<line x1="{x1}" y1="{y}" x2="{x2}" y2="{y}"/>"#,
+                        y = bottom - inset
+                    );
+                }
+                if !prev {
+                    let _ = writeln!(
+                        ret,
+                        r#"<line x1="{x}" y1="{y1}" x2="{x}" y2="{y2}"/>"#,
+                        x = left + inset
+                    );
+                }
+                if !next {
+                    let _ = writeln!(
+                        ret,
+                        r#"<line x1="{x}" y1="{y1}" x2="{x}" y2="{y2}"/>"#,
+                        x = right - inset
+                    );
+                }
+            }
+            ret.push_str("</g>\n");
+
+            let Some(label_cell) = cells.iter().min_by_key(|c| (c.y, c.x)) else {
+                continue;
+            };
+            let _ = writeln!(
+                ret,
+                r#"<text x="{x}" y="{y}" font-size="{font}" fill="{CAGE_COLOR}" stroke="white" stroke-width="3" paint-order="stroke">{sum}</text>"#,
+                x = margin + label_cell.x * size + inset + 1,
+                y = margin + label_cell.y * size + inset + font,
+            );
+        }
+    }
+
+    /// 확정된 값과, 설정된 경우 노트를 씁니다.
+    fn write_digits(
+        &self,
+        ret: &mut String,
+        givens: &[Option<MaxNum<N>>],
+        solution: &[Option<MaxNum<N>>],
+        options: &SvgOptions,
+        margin: usize,
+    ) {
+        let size = options.cell_size;
+        let rec = note_rec_size::<N>();
+        let mini = size / rec;
+        // 여러 글자 기호는 칸을 벗어나지 않도록 글자 크기를 줄임
+        let symbol_width = self.symbols().width();
+        let read = self.read_lock();
+
+        for (index, (cell, chk)) in read.into_iter().enumerate() {
+            let (x, y) = cell.get_coordinate();
+            let (left, top) = (margin + x * size, margin + y * size);
+            let given = givens.get(index).copied().flatten();
+            let solved = solution.get(index).copied().flatten();
+
+            let solved = solved.or(chk.final_num()).filter(|_| options.show_solved);
+
+            if let Some(num) = given.or(solved) {
+                let (color, weight) = if given.is_some() {
+                    (GIVEN_COLOR, "bold")
+                } else {
+                    (SOLVED_COLOR, "normal")
+                };
+                let _ = writeln!(
+                    ret,
+                    r#"<text x="{cx}" y="{cy}" font-size="{font}" font-weight="{weight}" fill="{color}" text-anchor="middle" dominant-baseline="central">{c}</text>"#,
+                    cx = left + size / 2,
+                    cy = top + size / 2,
+                    font = size * 6 / (5 * (symbol_width + 1)),
+                    c = escape_html(self.symbols().get(num))
+                );
+                continue;
+            }
+
+            if !options.show_candidates || chk.final_num().is_some() {
+                continue;
+            }
+
+            for n in MaxNum::<N>::iter().filter(|&n| chk.get_chk(n)) {
+                let (col, row) = (n.get_value() % rec, n.get_value() / rec);
+                let _ = writeln!(
+                    ret,
+                    r#"<text x="{cx}" y="{cy}" font-size="{font}" fill="{CANDIDATE_COLOR}" text-anchor="middle" dominant-baseline="central">{c}</text>"#,
+                    cx = left + col * mini + mini / 2,
+                    cy = top + row * mini + mini / 2,
+                    font = (mini * 8 / (5 * (symbol_width + 1))).max(1),
+                    c = escape_html(self.symbols().get(n))
+                );
+            }
+        }
+    }
+}
+
+/// SVG, HTML에서 특별한 의미를 가지는 문자를 escape합니다.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut ret = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => ret.push_str("&amp;"),
+            '<' => ret.push_str("&lt;"),
+            '>' => ret.push_str("&gt;"),
+            '"' => ret.push_str("&quot;"),
+            _ => ret.push(c),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{symbol_set::SymbolSet, table::Table};
+
+    #[test]
+    fn svg_render() {
+        let fp = r#"{"size":4,"grid":[[{},{},{},{}],[{},{},{},{}],[{},{},{},{}],[{},{},{},{"value":2,"given":true}]],
+            "killercage":[{"cells":["R1C1","R1C2","R2C2"],"value":"7"}]}"#;
+        let t = Table::<4>::new_from_fpuzzles(fp).unwrap().table;
+        let givens = crate::io::line::parse_line::<4>("...............2").unwrap();
+
+        let svg = t.to_svg_with_givens(
+            &givens,
+            &SvgOptions {
+                show_candidates: true,
+                ..SvgOptions::default()
+            },
+        );
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("stroke-dasharray"));
+        assert!(svg.contains(">7</text>"));
+        assert!(svg.contains(&format!(r#"font-weight="bold" fill="{GIVEN_COLOR}""#)));
+        // 4X4에서 굵은 내부 경계는 가로 4개, 세로 4개
+        assert_eq!(svg.matches(r#"stroke-width="3"/>"#).count(), 8 + 1);
+        assert!(svg.contains(&format!(r#"fill="{CANDIDATE_COLOR}""#)));
+
+        // 기호의 특수 문자는 escape함
+        let mut t = Table::<4>::new_standard(2, 2);
+        t.set_symbols(SymbolSet::from_chars("<>&\""));
+        t.set_givens_from_line("<>&\"............").unwrap();
+        let svg = t.to_svg(&SvgOptions::default());
+        for escaped in ["&lt;", "&gt;", "&amp;", "&quot;"] {
+            assert!(svg.contains(&format!(">{escaped}</text>")), "{escaped}");
+        }
+    }
+}
@@ -1,5 +1,12 @@
+pub mod any_table;
+pub mod batch;
 pub mod combinations;
+pub mod error;
+pub mod io;
 pub mod model;
 pub mod num_check;
+pub mod play;
 pub mod punch;
+pub mod rating;
 pub mod solver;
+pub mod verify;
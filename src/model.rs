@@ -1,11 +1,17 @@
 pub mod array_note;
 pub mod array_vector;
 pub mod cell;
+pub mod cell_pos;
 pub mod index_key_map;
 pub mod max_num;
+pub mod note_bits;
 pub mod relaxed_bool;
+pub mod symbol_set;
 pub mod table;
+pub mod table_builder;
 pub mod table_lock;
+pub mod table_snapshot;
+pub mod table_structure;
 pub mod unsafe_cell_sync;
 pub mod zone;
 pub mod zone_cache;
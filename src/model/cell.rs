@@ -1,51 +1,80 @@
 use super::{
-    index_key_map::IndexKeySet, max_num::MaxNum, unsafe_cell_sync::UnsafeCellSync, zone::Zone,
+    cell_pos::CellPos, index_key_map::IndexKeySet, unsafe_cell_sync::UnsafeCellSync, zone::Zone,
+};
+use crate::{error::SudokuError, io::ParseError, num_check::NumCheck};
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomPinned,
 };
-use crate::num_check::NumCheck;
-use std::{fmt::Debug, marker::PhantomPinned};
 
 pub struct Cell<const N: usize> {
     pub(crate) chk_unsafe: UnsafeCellSync<NumCheck<N>>,
     pub(crate) rep_zone: Option<Zone>,
     pub(crate) zone_set: IndexKeySet<Zone>,
     pub(crate) zone_vec: Vec<Zone>,
-    pub(crate) x: MaxNum<N>,
-    pub(crate) y: MaxNum<N>,
+    pub(crate) x: usize,
+    pub(crate) y: usize,
+    /// 테이블 내에서 cell의 순서. 테이블을 만들 때 정해집니다.
     pub(crate) index: usize,
     _pin: PhantomPinned,
 }
 
 impl<const N: usize> Cell<N> {
+    /// cell에 중복되는 zone이 있는 경우 panic
+    ///
+    /// x, y는 캔버스 좌표입니다. 여러 격자가 겹치는 퍼즐에서는 N 이상일 수 있으며, 범위는 테이블을 만들 때 검사합니다.
     #[must_use]
     pub fn new(x: usize, y: usize, zone: Vec<Zone>) -> Self {
-        let x = MaxNum::new(x);
-        let y = MaxNum::new(y);
+        Self::try_new(x, y, zone).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// cell에 중복되는 zone이 있는 경우 오류를 반환합니다.
+    pub fn try_new(x: usize, y: usize, zone: Vec<Zone>) -> Result<Self, SudokuError> {
+        let zone_set: IndexKeySet<Zone> = zone.iter().copied().collect();
+        if zone_set.iter().count() != zone.len() {
+            let duplicate = zone
+                .iter()
+                .enumerate()
+                .find(|(index, z)| {
+                    zone[..*index]
+                        .iter()
+                        .any(|other| other.get_zone_num() == z.get_zone_num())
+                })
+                .map_or(0, |(_, z)| z.get_zone_num());
+            return Err(ParseError::DuplicateZone {
+                x,
+                y,
+                zone: duplicate,
+            }
+            .into());
+        }
 
-        let ret = Cell {
+        Ok(Cell {
             chk_unsafe: UnsafeCellSync::new(NumCheck::<N>::new_with_true()),
             rep_zone: zone.first().copied(),
-            zone_set: zone.iter().copied().collect(),
+            zone_set,
             zone_vec: zone,
             x,
             y,
-            index: x.get_value() + y.get_value() * N,
+            index: 0,
             _pin: PhantomPinned,
-        };
-
-        assert_eq!(
-            ret.zone_set.iter().count(),
-            ret.zone_vec.len(),
-            "cell에 중복되는 zone이 있음. x:{x}, y:{y}"
-        );
-        ret
+        })
     }
 
+    /// 캔버스 좌표 (x, y)를 반환합니다. 기본 구조에서는 0..N 범위입니다.
     #[must_use]
     #[inline]
-    pub fn get_coordinate(&self) -> (MaxNum<N>, MaxNum<N>) {
+    pub fn get_coordinate(&self) -> (usize, usize) {
         (self.x, self.y)
     }
 
+    /// 캔버스 좌표를 r1c1 표기로 다루기 위한 CellPos를 반환합니다.
+    #[must_use]
+    #[inline]
+    pub fn pos(&self) -> CellPos {
+        CellPos::new(self.x, self.y)
+    }
+
     #[must_use]
     #[inline]
     pub fn get_zone(&self) -> &[Zone] {
@@ -69,6 +98,13 @@ impl<const N: usize> Debug for Cell<N> {
     }
 }
 
+/// "r3c7" 과 같은 r1c1 표기로 출력합니다.
+impl<const N: usize> Display for Cell<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.pos(), f)
+    }
+}
+
 impl<const N: usize> PartialEq for Cell<N> {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -0,0 +1,63 @@
+use crate::error::SudokuError;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// cell의 캔버스 좌표를 "r3c7" 형식 (3번째 행, 7번째 열)으로 읽고 씁니다.
+///
+/// x, y는 0부터 시작하며, 표기의 행과 열은 1부터 시작합니다. 읽을 때는 대소문자를 구분하지 않습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CellPos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl CellPos {
+    #[must_use]
+    #[inline]
+    pub const fn new(x: usize, y: usize) -> Self {
+        CellPos { x, y }
+    }
+}
+
+impl Display for CellPos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "r{}c{}", self.y + 1, self.x + 1)
+    }
+}
+
+impl FromStr for CellPos {
+    type Err = SudokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || SudokuError::InvalidCellNotation {
+            notation: s.to_string(),
+        };
+
+        let lower = s.trim().to_ascii_lowercase();
+        let (row, col) = lower
+            .strip_prefix('r')
+            .and_then(|rest| rest.split_once('c'))
+            .ok_or_else(err)?;
+        let row: usize = row.parse().map_err(|_| err())?;
+        let col: usize = col.parse().map_err(|_| err())?;
+
+        if row == 0 || col == 0 {
+            return Err(err());
+        }
+        Ok(CellPos::new(col - 1, row - 1))
+    }
+}
+
+impl From<(usize, usize)> for CellPos {
+    fn from((x, y): (usize, usize)) -> Self {
+        CellPos::new(x, y)
+    }
+}
+
+impl From<CellPos> for (usize, usize) {
+    fn from(pos: CellPos) -> Self {
+        (pos.x, pos.y)
+    }
+}
@@ -78,6 +78,18 @@ where
         }
     }
 
+    /// get과 같으나, 저장된 key도 함께 반환합니다. 같은 index를 가진 서로 다른 key를 구분할 때 사용합니다.
+    #[must_use]
+    #[inline]
+    pub fn get_key_value(&self, k: &K) -> Option<(&K, &V)> {
+        let index = k.index() as usize;
+
+        match self.arr.get(index) {
+            Some(Some((k, v))) => Some((k, v)),
+            _ => None,
+        }
+    }
+
     #[must_use]
     #[inline]
     pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
@@ -1,7 +1,19 @@
+use crate::error::SudokuError;
 use std::fmt::{Debug, Display, Formatter};
 use std::hint::unreachable_unchecked;
 use std::ops::{Bound, RangeBounds};
 
+/// 기본 기호입니다. 35를 넘는 크기에서는 소문자와 특수문자를 이어서 사용하며, 64를 넘는 크기는 10진수를 사용합니다.
+pub(crate) const CHAR_ARR: [char; 64] = [
+    '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J',
+    'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'a', 'b', 'c',
+    'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v',
+    'w', 'x', 'y', 'z', '@', '#', '$',
+];
+
+/// 기본 기호 중 대소문자를 구분하지 않고 읽을 수 있는 개수
+pub(crate) const CASE_INSENSITIVE_LEN: usize = 35;
+
 /// MaxNum의 값은 0 <= value < N를 보장함.
 pub struct MaxNum<const N: usize> {
     num: usize,
@@ -19,6 +31,14 @@ impl<const N: usize> MaxNum<N> {
         Self { num }
     }
 
+    /// num의 값은 num < N을 충족해야 함. 그렇지 않을 경우 OutOfRange 오류
+    pub fn try_new(num: usize) -> Result<Self, SudokuError> {
+        Self::new_optional(num).ok_or(SudokuError::OutOfRange {
+            value: num,
+            size: N,
+        })
+    }
+
     /// num의 값은 num < N을 충족해야 함. 그렇지 않을 경우 None
     #[must_use]
     pub const fn new_optional(num: usize) -> Option<Self> {
@@ -51,17 +71,46 @@ impl<const N: usize> MaxNum<N> {
         self.num
     }
 
+    /// 기본 기호입니다. 테이블에 설정된 기호는 SymbolSet::get으로 얻습니다.
+    ///
+    /// # Panics
+    ///
+    /// 값이 64 이상인 경우. 이 경우 try_get_char를 사용합니다.
     #[must_use]
     #[inline]
     pub const fn get_char(&self) -> char {
-        const CHAR_ARR: [char; 35] = [
-            '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H',
-            'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y',
-            'Z',
-        ];
         CHAR_ARR[self.get_value()]
     }
 
+    /// get_char와 같으나, 값이 64 이상이어서 기본 기호가 없는 경우 None을 반환합니다.
+    ///
+    /// N이 64를 넘는 경우 Display는 모든 값을 1부터 시작하는 10진수로 출력합니다.
+    #[must_use]
+    #[inline]
+    pub const fn try_get_char(&self) -> Option<char> {
+        let value = self.get_value();
+        if value < CHAR_ARR.len() {
+            Some(CHAR_ARR[value])
+        } else {
+            None
+        }
+    }
+
+    /// get_char의 역변환입니다. N이 35 이하인 경우 알파벳은 대소문자를 구분하지 않습니다.
+    /// N 범위를 벗어나거나 알 수 없는 문자인 경우 None
+    ///
+    /// 테이블에 설정된 기호로 읽으려면 SymbolSet::parse를 사용합니다.
+    #[must_use]
+    pub fn from_char(c: char) -> Option<Self> {
+        let c = if N <= CASE_INSENSITIVE_LEN {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        };
+        let num = CHAR_ARR.iter().take(N).position(|&ch| ch == c)?;
+        Self::new_optional(num)
+    }
+
     /// MaxNum<N>에서 0부터 N - 1까지의 전체 구간에 대한 Iterator를 반환합니다.
     pub fn iter() -> MaxNumIter<N> {
         MaxNumIter { cur: 0, end: N }
@@ -129,15 +178,22 @@ impl<const N: usize> Eq for MaxNum<N> {}
 
 impl<const N: usize> Debug for MaxNum<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("MaxNum")
-            .field("num", &self.get_char())
-            .finish()
+        let mut debug = f.debug_struct("MaxNum");
+        match self.try_get_char().filter(|_| N <= CHAR_ARR.len()) {
+            Some(c) => debug.field("num", &c),
+            None => debug.field("num", &(self.get_value() + 1)),
+        };
+        debug.finish()
     }
 }
 
 impl<const N: usize> Display for MaxNum<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.get_char())
+        // 64를 넘는 크기는 SymbolSet::default와 같이 모든 값을 10진수로 출력함
+        match self.try_get_char().filter(|_| N <= CHAR_ARR.len()) {
+            Some(c) => write!(f, "{c}"),
+            None => write!(f, "{}", self.get_value() + 1),
+        }
     }
 }
 
@@ -0,0 +1,211 @@
+use super::max_num::MaxNum;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+/// NoteBits가 사용하는 u64의 개수
+const WORDS: usize = 2;
+
+/// 노트를 비트 연산으로 다루기 위한 bit 집합입니다. n번째 bit가 1이면 값 n의 노트가 true 입니다.
+///
+/// u64 WORDS개로 표현하므로 최대 CAPACITY개의 값을 담을 수 있습니다.
+/// N이 64 이하인 경우 연산은 첫 u64만 사용하도록 컴파일 시점에 정해집니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoteBits<const N: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const N: usize> NoteBits<N> {
+    /// 담을 수 있는 최대 값의 개수
+    pub const CAPACITY: usize = WORDS * u64::BITS as usize;
+
+    /// 모든 bit가 0인 집합
+    pub const EMPTY: NoteBits<N> = NoteBits { words: [0; WORDS] };
+
+    /// 0부터 N - 1까지의 bit가 모두 1인 집합
+    pub const FULL: NoteBits<N> = {
+        assert!(N <= Self::CAPACITY);
+        let mut words = [0; WORDS];
+        let mut i = 0;
+        while i < WORDS {
+            let start = i * u64::BITS as usize;
+            words[i] = if N >= start + u64::BITS as usize {
+                u64::MAX
+            } else if N > start {
+                (1 << (N - start)) - 1
+            } else {
+                0
+            };
+            i += 1;
+        }
+        NoteBits { words }
+    };
+
+    /// N이 64 이하여서 첫 u64만 사용하는지 여부
+    const SINGLE_WORD: bool = N <= u64::BITS as usize;
+
+    #[inline]
+    const fn position(num: MaxNum<N>) -> (usize, u64) {
+        let value = num.get_value();
+        if Self::SINGLE_WORD {
+            (0, 1 << value)
+        } else {
+            (
+                value / u64::BITS as usize,
+                1 << (value % u64::BITS as usize),
+            )
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn contains(self, num: MaxNum<N>) -> bool {
+        let (word, bit) = Self::position(num);
+        self.words[word] & bit != 0
+    }
+
+    #[inline]
+    pub fn insert(&mut self, num: MaxNum<N>) {
+        let (word, bit) = Self::position(num);
+        self.words[word] |= bit;
+    }
+
+    #[inline]
+    pub fn remove(&mut self, num: MaxNum<N>) {
+        let (word, bit) = Self::position(num);
+        self.words[word] &= !bit;
+    }
+
+    /// 1인 bit의 개수
+    #[must_use]
+    #[inline]
+    pub fn len(self) -> usize {
+        if Self::SINGLE_WORD {
+            self.words[0].count_ones() as usize
+        } else {
+            self.words.iter().map(|w| w.count_ones() as usize).sum()
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        if Self::SINGLE_WORD {
+            self.words[0] == 0
+        } else {
+            self.words.iter().all(|&w| w == 0)
+        }
+    }
+
+    /// 1인 bit의 값을 오름차순으로 반환합니다.
+    pub fn iter(self) -> NoteBitsIter<N> {
+        NoteBitsIter {
+            words: self.words,
+            word: 0,
+        }
+    }
+}
+
+impl<const N: usize> Default for NoteBits<N> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<const N: usize> From<MaxNum<N>> for NoteBits<N> {
+    fn from(num: MaxNum<N>) -> Self {
+        let mut ret = NoteBits::EMPTY;
+        ret.insert(num);
+        ret
+    }
+}
+
+impl<const N: usize> FromIterator<MaxNum<N>> for NoteBits<N> {
+    fn from_iter<T: IntoIterator<Item = MaxNum<N>>>(iter: T) -> Self {
+        let mut ret = NoteBits::EMPTY;
+        for num in iter {
+            ret.insert(num);
+        }
+        ret
+    }
+}
+
+impl<const N: usize> BitOr for NoteBits<N> {
+    type Output = NoteBits<N>;
+
+    #[inline]
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<const N: usize> BitOrAssign for NoteBits<N> {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        if Self::SINGLE_WORD {
+            self.words[0] |= rhs.words[0];
+        } else {
+            for (w, r) in self.words.iter_mut().zip(rhs.words) {
+                *w |= r;
+            }
+        }
+    }
+}
+
+impl<const N: usize> BitAnd for NoteBits<N> {
+    type Output = NoteBits<N>;
+
+    #[inline]
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self &= rhs;
+        self
+    }
+}
+
+impl<const N: usize> BitAndAssign for NoteBits<N> {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        if Self::SINGLE_WORD {
+            self.words[0] &= rhs.words[0];
+        } else {
+            for (w, r) in self.words.iter_mut().zip(rhs.words) {
+                *w &= r;
+            }
+        }
+    }
+}
+
+pub struct NoteBitsIter<const N: usize> {
+    words: [u64; WORDS],
+    word: usize,
+}
+
+impl<const N: usize> Iterator for NoteBitsIter<N> {
+    type Item = MaxNum<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if NoteBits::<N>::SINGLE_WORD {
+            let bits = &mut self.words[0];
+            if *bits == 0 {
+                return None;
+            }
+
+            let index = bits.trailing_zeros() as usize;
+            *bits &= *bits - 1;
+            return MaxNum::new_optional(index);
+        }
+
+        while self.word < WORDS {
+            let bits = &mut self.words[self.word];
+            if *bits == 0 {
+                self.word += 1;
+                continue;
+            }
+
+            let index = self.word * u64::BITS as usize + bits.trailing_zeros() as usize;
+            *bits &= *bits - 1;
+            return MaxNum::new_optional(index);
+        }
+
+        None
+    }
+}
@@ -0,0 +1,225 @@
+use super::max_num::MaxNum;
+use crate::error::SudokuError;
+
+/// 기호에 사용할 수 없는 문자입니다. 빈 칸, 구분선, 노트 구분자입니다.
+pub const RESERVED_CHARS: [char; 5] = ['.', ',', '|', '+', '-'];
+
+/// 값마다 화면과 입출력에 사용하는 기호입니다. 테이블마다 설정할 수 있습니다.
+///
+/// 기본값은 MaxNum::get_char의 문자 (64를 넘는 크기는 10진수) 이며, Wordoku처럼 알파벳만 사용하거나 '0'부터 'F'까지의 16진수,
+/// 큰 퍼즐을 위한 "10", "11" 같은 여러 글자 기호도 사용할 수 있습니다.
+///
+/// 빈 칸 문자 '0'을 기호로 사용하는 경우, 한 줄 형식과 여러 줄 형식, play 명령의 '0'은 빈 칸이 아닌 값으로 읽습니다.
+/// 이 경우 빈 칸은 '.'으로만 나타낼 수 있습니다.
+///
+/// 모든 기호가 한 글자인 경우 입출력 형식은 기존과 같습니다. 여러 글자 기호가 있는 경우
+/// 한 줄 형식과 여러 줄 형식은 공백으로 칸을 구분하고, pencilmark 형식은 ','로 노트를 구분합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolSet<const N: usize> {
+    symbols: Box<[String]>,
+    /// 가장 긴 기호의 글자 수
+    width: usize,
+}
+
+impl<const N: usize> SymbolSet<N> {
+    /// 값의 순서대로 N개의 기호를 받습니다. 개수가 다르거나, 잘못되거나 중복된 기호가 있는 경우 panic
+    #[must_use]
+    pub fn new<S: Into<String>>(symbols: impl IntoIterator<Item = S>) -> Self {
+        Self::try_new(symbols).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// new와 같으나, 기호가 올바르지 않은 경우 오류를 반환합니다.
+    ///
+    /// 기호는 비어있지 않아야 하며, 공백과 RESERVED_CHARS를 포함할 수 없습니다.
+    /// 대소문자만 다른 기호는 서로 다른 기호로 취급합니다.
+    pub fn try_new<S: Into<String>>(
+        symbols: impl IntoIterator<Item = S>,
+    ) -> Result<Self, SudokuError> {
+        let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
+        if symbols.len() != N {
+            return Err(SudokuError::InvalidSymbolCount {
+                expected: N,
+                found: symbols.len(),
+            });
+        }
+
+        for (index, symbol) in symbols.iter().enumerate() {
+            if symbol.is_empty()
+                || symbol
+                    .chars()
+                    .any(|c| c.is_whitespace() || RESERVED_CHARS.contains(&c))
+            {
+                return Err(SudokuError::InvalidSymbol {
+                    symbol: symbol.clone(),
+                });
+            }
+
+            if symbols[..index].contains(symbol) {
+                return Err(SudokuError::DuplicateSymbol {
+                    symbol: symbol.clone(),
+                });
+            }
+        }
+
+        let width = symbols.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+        Ok(Self {
+            symbols: symbols.into_boxed_slice(),
+            width,
+        })
+    }
+
+    /// 문자열의 각 문자를 값의 순서대로 기호로 사용합니다. 문자 수가 N이 아니거나 중복된 문자가 있는 경우 panic
+    #[must_use]
+    pub fn from_chars(chars: &str) -> Self {
+        Self::try_from_chars(chars).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// from_chars와 같으나, 기호가 올바르지 않은 경우 오류를 반환합니다.
+    pub fn try_from_chars(chars: &str) -> Result<Self, SudokuError> {
+        Self::try_new(chars.chars().map(String::from))
+    }
+
+    /// 1부터 N까지의 10진수를 기호로 사용합니다. N이 10 이상인 경우 여러 글자 기호가 됩니다.
+    #[must_use]
+    pub fn new_numbered() -> Self {
+        Self::new((1..=N).map(|n| n.to_string()))
+    }
+
+    /// 값의 기호를 반환합니다.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, num: MaxNum<N>) -> &str {
+        &self.symbols[num.get_value()]
+    }
+
+    /// get의 역변환입니다. 같은 기호가 없는 경우 대소문자를 구분하지 않고 한 번 더 찾으며, 그래도 없으면 None
+    ///
+    /// 대소문자만 다른 기호가 여러 개인 경우 대소문자를 구분하지 않는 검색은 하지 않습니다.
+    #[must_use]
+    pub fn parse(&self, token: &str) -> Option<MaxNum<N>> {
+        if let Some(index) = self.symbols.iter().position(|s| s == token) {
+            return MaxNum::new_optional(index);
+        }
+
+        let mut found = self
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.eq_ignore_ascii_case(token));
+        match (found.next(), found.next()) {
+            (Some((index, _)), None) => MaxNum::new_optional(index),
+            _ => None,
+        }
+    }
+
+    /// 가장 긴 기호의 글자 수입니다. Display는 모든 기호를 이 너비에 맞춰 출력합니다.
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// note_fmt에서 노트 하나가 차지하는 글자 수입니다. 여러 글자 기호는 서로 구분되도록 한 칸을 더 사용합니다.
+    #[must_use]
+    #[inline]
+    pub(crate) fn note_width(&self) -> usize {
+        if self.is_single_char() {
+            1
+        } else {
+            self.width + 1
+        }
+    }
+
+    /// 모든 기호가 한 글자인지 여부
+    #[must_use]
+    #[inline]
+    pub fn is_single_char(&self) -> bool {
+        self.width == 1
+    }
+
+    /// 기본 기호인지 여부
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 값의 순서대로 모든 기호를 반환합니다.
+    #[must_use]
+    pub fn as_slice(&self) -> &[String] {
+        &self.symbols
+    }
+}
+
+impl<const N: usize> Default for SymbolSet<N> {
+    /// MaxNum::get_char의 문자를 사용합니다. 64를 넘는 크기는 new_numbered와 같습니다.
+    fn default() -> Self {
+        let Some(symbols) = MaxNum::<N>::iter()
+            .map(|n| n.try_get_char().map(String::from))
+            .collect()
+        else {
+            return Self::new_numbered();
+        };
+
+        Self { symbols, width: 1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbol_set_parse_and_errors() {
+        let wordoku = SymbolSet::<9>::from_chars("WORDSPUZL");
+        assert_eq!(wordoku.get(MaxNum::new(0)), "W");
+        assert_eq!(wordoku.parse("z"), Some(MaxNum::new(7)));
+        assert_eq!(wordoku.parse("1"), None);
+
+        let hex = SymbolSet::<16>::from_chars("0123456789ABCDEF");
+        assert_eq!(hex.parse("0"), Some(MaxNum::new(0)));
+        assert_eq!(hex.parse("f"), Some(MaxNum::new(15)));
+
+        let numbered = SymbolSet::<49>::new_numbered();
+        assert_eq!(numbered.width(), 2);
+        assert_eq!(numbered.parse("49"), Some(MaxNum::new(48)));
+
+        // 대소문자만 다른 기호가 있으면 정확히 같은 기호만 찾음
+        let default = SymbolSet::<64>::default();
+        assert_eq!(default.get(MaxNum::new(63)), "$");
+        assert_eq!(default.parse("a"), Some(MaxNum::new(35)));
+        assert_eq!(default.parse("A"), Some(MaxNum::new(9)));
+
+        // 기본 문자가 없는 64 이상의 값은 10진수
+        assert_eq!(MaxNum::<81>::new(63).get_char(), '$');
+        assert_eq!(MaxNum::<81>::new(63).try_get_char(), Some('$'));
+        assert_eq!(MaxNum::<81>::new(70).try_get_char(), None);
+        assert_eq!(MaxNum::<81>::new(70).to_string(), "71");
+        assert_eq!(SymbolSet::<81>::default().get(MaxNum::new(70)), "71");
+        // 64를 넘는 크기는 64 미만의 값도 기본 기호와 같이 10진수로 출력
+        assert_eq!(MaxNum::<81>::new(5).to_string(), "6");
+        assert_eq!(SymbolSet::<81>::default().get(MaxNum::new(5)), "6");
+
+        assert_eq!(
+            SymbolSet::<4>::try_from_chars("ABC"),
+            Err(SudokuError::InvalidSymbolCount {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            SymbolSet::<4>::try_from_chars("ABCA"),
+            Err(SudokuError::DuplicateSymbol {
+                symbol: "A".to_string()
+            })
+        );
+        assert_eq!(
+            SymbolSet::<2>::try_new(["1", "a b"]),
+            Err(SudokuError::InvalidSymbol {
+                symbol: "a b".to_string()
+            })
+        );
+        assert!(SymbolSet::<2>::try_from_chars("1.").is_err());
+        // SVG에서 특별한 의미를 가지는 문자는 출력할 때 escape하므로 사용할 수 있음
+        assert!(SymbolSet::<4>::try_from_chars("<>&\"").is_ok());
+    }
+}
@@ -1,148 +1,539 @@
-use super::{cell::Cell, max_num::MaxNum, table_lock::TableLock, zone::Zone};
+use super::{
+    cell::Cell,
+    max_num::MaxNum,
+    table_lock::TableLock,
+    zone::{Zone, ZoneKind},
+};
+use crate::{error::SudokuError, io::default_box_size};
 use std::pin::Pin;
 
 pub struct Table<const N: usize> {
     pub(crate) cells: Pin<Box<[Cell<N>]>>,
+    /// 캔버스의 각 칸 (x + y * width)에 놓인 cell의 순서. cell이 없는 칸은 None
+    pub(crate) canvas: Box<[Option<usize>]>,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
 }
 
 impl Table<9> {
-    /// 9X9 기본 스도쿠 구조입니다.0
+    /// 9X9 기본 스도쿠 구조입니다.
     pub fn new_default_9() -> TableLock<9> {
-        let mut zone: Vec<usize> = Vec::with_capacity(81);
-        let mut zone_row = [1, 1, 1, 2, 2, 2, 3, 3, 3];
-        for _ in 0..3 {
-            for _ in 0..3 {
-                zone.extend_from_slice(&zone_row);
-            }
-
-            for z in zone_row.iter_mut() {
-                *z += 3;
-            }
-        }
-
-        let mut cells: Vec<Vec<Cell<9>>> = Vec::with_capacity(9);
-        for y in 0..9 {
-            let mut row: Vec<Cell<9>> = Vec::with_capacity(9);
-            for x in 0..9 {
-                let index = zone[x + y * 9];
-
-                let this_zone = vec![
-                    Zone::new_unique_from_usize(index),
-                    Zone::new_unique_from_usize(x + 10),
-                    Zone::new_unique_from_usize(y + 19),
-                ];
-                let cell = Cell::new(x, y, this_zone);
+        Table::new_standard(3, 3)
+    }
 
-                row.push(cell);
-            }
-            cells.push(row);
-        }
+    /// 다섯 개의 9X9 격자가 모서리 박스를 공유하는 21X21 사무라이 스도쿠 구조입니다.
+    ///
+    /// 격자 순서는 왼쪽 위, 오른쪽 위, 가운데, 왼쪽 아래, 오른쪽 아래입니다.
+    pub fn new_samurai() -> TableLock<9> {
+        Table::new_overlapping(3, 3, &[(0, 0), (12, 0), (6, 6), (0, 12), (12, 12)])
+    }
 
-        Table::new_with_vec_cells(cells)
+    /// 네 개의 9X9 격자가 3칸씩 어긋나게 겹친 12X12 버터플라이 스도쿠 구조입니다.
+    pub fn new_butterfly() -> TableLock<9> {
+        Table::new_overlapping(3, 3, &[(0, 0), (3, 0), (0, 3), (3, 3)])
     }
 }
 
 impl Table<16> {
     /// 16X16 스도쿠 구조입니다.
     pub fn new_default_16() -> TableLock<16> {
-        let mut zone: Vec<usize> = Vec::with_capacity(256);
-        let mut zone_row = [1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4];
-        for _ in 0..4 {
-            for _ in 0..4 {
-                zone.extend_from_slice(&zone_row);
-            }
+        Table::new_standard(4, 4)
+    }
+}
 
-            for z in zone_row.iter_mut() {
-                *z += 4;
-            }
-        }
+impl Table<32> {
+    /// 32X32 스도쿠 구조입니다. 박스는 가로 8칸, 세로 4칸입니다.
+    pub fn new_default_32() -> TableLock<32> {
+        Table::new_standard(8, 4)
+    }
+}
 
-        let mut cells: Vec<Vec<Cell<16>>> = Vec::with_capacity(16);
-        for y in 0..16 {
-            let mut row: Vec<Cell<16>> = Vec::with_capacity(16);
-            for x in 0..16 {
-                let index = zone[x + y * 16];
+impl<const N: usize> Table<N> {
+    /// 가로 box_width칸, 세로 box_height칸의 박스를 가진 표준 스도쿠 구조입니다.
+    ///
+    /// 6X6은 (3, 2), 8X8은 (4, 2), 12X12는 (4, 3)과 같이 사용합니다.
+    /// zone 번호는 박스가 1..=N, 세로줄이 N+1..=2N, 가로줄이 2N+1..=3N 입니다.
+    ///
+    /// # Panics
+    ///
+    /// box_width * box_height가 N이 아닌 경우
+    #[must_use]
+    pub fn new_standard(box_width: usize, box_height: usize) -> TableLock<N> {
+        Self::try_new_standard(box_width, box_height).unwrap_or_else(|err| panic!("{err}"))
+    }
 
-                let this_zone = vec![
-                    Zone::new_unique_from_usize(index),
-                    Zone::new_unique_from_usize(x + 17),
-                    Zone::new_unique_from_usize(y + 33),
-                ];
+    /// new_standard와 같으나, box_width * box_height가 N이 아닌 경우 오류를 반환합니다.
+    pub fn try_new_standard(
+        box_width: usize,
+        box_height: usize,
+    ) -> Result<TableLock<N>, SudokuError> {
+        Self::try_new_overlapping(box_width, box_height, &[(0, 0)])
+    }
 
-                let cell = Cell::new(x, y, this_zone);
-                row.push(cell);
-            }
-            cells.push(row);
+    /// 표준 스도쿠 격자 여러 개를 캔버스의 origins 위치에 겹쳐 놓은 구조입니다. (사무라이, 버터플라이 등)
+    ///
+    /// origins는 각 격자의 왼쪽 위 좌표이며, 캔버스는 모든 격자를 덮는 가장 작은 크기가 됩니다.
+    /// 격자가 겹치는 cell은 각 격자의 zone에 모두 속하며, 같은 위치에 놓인 박스는 하나의 zone이 됩니다.
+    /// zone 번호는 격자마다 박스, 세로줄, 가로줄 순으로 이어지므로 격자가 하나인 경우 new_standard와 같습니다.
+    /// zone의 종류는 박스가 만든 순서대로 Box 1부터, 가로줄과 세로줄은 캔버스의 행, 열 번호를 가집니다.
+    ///
+    /// # Panics
+    ///
+    /// box_width * box_height가 N이 아닌 경우
+    #[must_use]
+    pub fn new_overlapping(
+        box_width: usize,
+        box_height: usize,
+        origins: &[(usize, usize)],
+    ) -> TableLock<N> {
+        Self::try_new_overlapping(box_width, box_height, origins)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// new_overlapping과 같으나, box_width * box_height가 N이 아닌 경우 오류를 반환합니다.
+    pub fn try_new_overlapping(
+        box_width: usize,
+        box_height: usize,
+        origins: &[(usize, usize)],
+    ) -> Result<TableLock<N>, SudokuError> {
+        if box_width.checked_mul(box_height) != Some(N) {
+            return Err(SudokuError::InvalidBoxSize {
+                box_width,
+                box_height,
+                size: N,
+            });
         }
 
-        Table::new_with_vec_cells(cells)
-    }
-}
+        let width = origins.iter().map(|&(x, _)| x + N).max().unwrap_or(0);
+        let height = origins.iter().map(|&(_, y)| y + N).max().unwrap_or(0);
+        let boxes_per_row = N / box_width;
 
-impl Table<32> {
-    /// 32X32 스도쿠 구조입니다.
-    pub fn new_default_32() -> TableLock<32> {
-        let mut zone: Vec<usize> = Vec::with_capacity(1024);
-        let mut zone_row = [
-            1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4,
-            4, 4, 4,
-        ];
-        for _ in 0..8 {
-            for _ in 0..4 {
-                zone.extend_from_slice(&zone_row);
-            }
+        let mut canvas_zones: Vec<Vec<Zone>> = vec![Vec::new(); width * height];
+        // 이미 만든 박스의 (왼쪽 위 좌표, zone)
+        let mut box_zones: Vec<((usize, usize), Zone)> = Vec::new();
+        let mut next_zone = 1;
 
-            for z in zone_row.iter_mut() {
-                *z += 4;
-            }
-        }
+        for &(origin_x, origin_y) in origins {
+            // 격자의 박스 0..N, 세로줄 N..2N, 가로줄 2N..3N
+            let mut grid_zones: Vec<Zone> = Vec::with_capacity(N * 3);
+            for box_index in 0..N {
+                let corner = (
+                    origin_x + (box_index % boxes_per_row) * box_width,
+                    origin_y + (box_index / boxes_per_row) * box_height,
+                );
 
-        let mut cells: Vec<Vec<Cell<32>>> = Vec::with_capacity(32);
-        for y in 0..32 {
-            let mut row: Vec<Cell<32>> = Vec::with_capacity(32);
-            for x in 0..32 {
-                let index = zone[x + y * 32];
+                if let Some(&(_, zone)) = box_zones.iter().find(|(c, _)| *c == corner) {
+                    grid_zones.push(zone);
+                } else {
+                    let kind = ZoneKind::Box(to_u16(box_zones.len() + 1));
+                    let zone = Zone::new_unique_from_usize(next_zone).with_kind(kind);
+                    next_zone += 1;
+                    box_zones.push((corner, zone));
+                    grid_zones.push(zone);
+                }
+            }
+            // 가로줄과 세로줄의 번호는 캔버스의 행, 열 번호이므로 r1c1 표기와 같습니다.
+            for x in 0..N {
+                let kind = ZoneKind::Column(to_u16(origin_x + x + 1));
+                grid_zones.push(Zone::new_unique_from_usize(next_zone).with_kind(kind));
+                next_zone += 1;
+            }
+            for y in 0..N {
+                let kind = ZoneKind::Row(to_u16(origin_y + y + 1));
+                grid_zones.push(Zone::new_unique_from_usize(next_zone).with_kind(kind));
+                next_zone += 1;
+            }
 
-                let this_zone = vec![
-                    Zone::new_unique_from_usize(index),
-                    Zone::new_unique_from_usize(x + 33),
-                    Zone::new_unique_from_usize(y + 65),
-                ];
+            for y in 0..N {
+                for x in 0..N {
+                    let box_index = x / box_width + (y / box_height) * boxes_per_row;
+                    let zones = &mut canvas_zones[origin_x + x + (origin_y + y) * width];
 
-                let cell = Cell::new(x, y, this_zone);
-                row.push(cell);
+                    for zone in [
+                        grid_zones[box_index],
+                        grid_zones[N + x],
+                        grid_zones[N * 2 + y],
+                    ] {
+                        if !zones.contains(&zone) {
+                            zones.push(zone);
+                        }
+                    }
+                }
             }
-            cells.push(row);
         }
 
-        Table::new_with_vec_cells(cells)
+        let cells = canvas_zones
+            .into_iter()
+            .enumerate()
+            .filter(|(_, zones)| !zones.is_empty())
+            .map(|(pos, zones)| Cell::new(pos % width, pos / width, zones))
+            .collect();
+
+        Table::try_new_with_canvas(width, height, cells)
     }
-}
 
-impl<const N: usize> Table<N> {
+    /// 기본 박스 모양을 가진 표준 스도쿠 구조입니다. 박스 크기는 default_box_size를 따릅니다.
+    #[must_use]
+    pub fn new_default() -> TableLock<N> {
+        let (box_width, box_height) = default_box_size(N);
+        Table::new_standard(box_width, box_height)
+    }
+
+    /// cell이 순서대로 들어오지 않았거나 개수가 N * N이 아닌 경우 panic
     pub fn new_with_vec_cells(cells: Vec<Vec<Cell<N>>>) -> TableLock<N> {
+        Self::try_new_with_vec_cells(cells).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// cell이 순서대로 들어오지 않았거나, 좌표가 N 이상이거나, 개수가 N * N이 아닌 경우 오류를 반환합니다.
+    pub fn try_new_with_vec_cells(cells: Vec<Vec<Cell<N>>>) -> Result<TableLock<N>, SudokuError> {
         let mut ret: Vec<Cell<N>> = Vec::with_capacity(N * N);
-        let mut index_cursor: i32 = -1;
 
         for row in cells {
             for cell in row {
-                let this_index = cell.index as i32;
-                assert!(index_cursor < this_index, "Cell은 순서대로 들어와야 함");
-                index_cursor = this_index;
+                let (x, y) = cell.get_coordinate();
+                if let Some(value) = [x, y].into_iter().find(|&v| v >= N) {
+                    return Err(SudokuError::OutOfRange { value, size: N });
+                }
+
+                if ret
+                    .last()
+                    .is_some_and(|last| (last.y, last.x) >= (cell.y, cell.x))
+                {
+                    return Err(SudokuError::InvalidCellOrder { x, y });
+                }
                 ret.push(cell);
             }
         }
 
-        TableLock::new(Table {
-            cells: Box::into_pin(ret.into_boxed_slice()),
-        })
+        if ret.len() != N * N {
+            return Err(SudokuError::InvalidCellCount {
+                expected: N * N,
+                found: ret.len(),
+            });
+        }
+
+        Table::try_new_with_canvas(N, N, ret)
+    }
+
+    /// width X height 캔버스의 일부 칸에만 cell이 놓인 구조입니다. cell이 없는 칸은 퍼즐에 포함되지 않습니다.
+    ///
+    /// cells는 가로줄 순서 (y, x 순)로 정렬되어 있어야 합니다.
+    /// 정렬되지 않았거나 좌표가 캔버스를 벗어난 경우 panic
+    pub fn new_with_canvas(width: usize, height: usize, cells: Vec<Cell<N>>) -> TableLock<N> {
+        Self::try_new_with_canvas(width, height, cells).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// new_with_canvas와 같으나, cell이 정렬되지 않았거나 좌표가 캔버스를 벗어난 경우 오류를 반환합니다.
+    ///
+    /// Unique zone에 속한 cell의 개수는 ZoneCache를 만들 때 검사합니다.
+    pub fn try_new_with_canvas(
+        width: usize,
+        height: usize,
+        mut cells: Vec<Cell<N>>,
+    ) -> Result<TableLock<N>, SudokuError> {
+        let mut canvas: Vec<Option<usize>> = vec![None; width * height];
+        let mut last_pos: Option<usize> = None;
+
+        for (index, cell) in cells.iter_mut().enumerate() {
+            let (x, y) = cell.get_coordinate();
+            if x >= width {
+                return Err(SudokuError::OutOfRange {
+                    value: x,
+                    size: width,
+                });
+            }
+            if y >= height {
+                return Err(SudokuError::OutOfRange {
+                    value: y,
+                    size: height,
+                });
+            }
+
+            let pos = x + y * width;
+            if last_pos.is_some_and(|last| last >= pos) {
+                return Err(SudokuError::InvalidCellOrder { x, y });
+            }
+            last_pos = Some(pos);
+
+            canvas[pos] = Some(index);
+            cell.index = index;
+        }
+
+        Ok(TableLock::new(Table {
+            cells: Box::into_pin(cells.into_boxed_slice()),
+            canvas: canvas.into_boxed_slice(),
+            width,
+            height,
+        }))
     }
 
+    /// 캔버스 좌표의 cell을 반환합니다. 캔버스를 벗어났거나 cell이 없는 칸이면 None
+    #[must_use]
+    #[inline]
+    pub fn get_from_canvas(&self, x: usize, y: usize) -> Option<&Cell<N>> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let index = self.canvas[x + y * self.width]?;
+        Some(&self.cells[index])
+    }
+
+    /// cell이 없는 좌표인 경우 panic
     pub fn get_from_coordi(&self, x: MaxNum<N>, y: MaxNum<N>) -> &Cell<N> {
-        let index = y.get_value() * N + x.get_value();
-        // MaxNum<N>의 값은 N보다 작은 것이 보장됨
-        let cell = unsafe { self.cells.get_unchecked(index) };
-        debug_assert_eq!(index, cell.index);
-        cell
+        self.get_from_canvas(x.get_value(), y.get_value())
+            .expect("cell이 없는 좌표")
+    }
+}
+
+/// 종류 번호로 변환합니다. zone 번호가 u16이므로 종류 번호도 u16을 넘을 수 없습니다.
+fn to_u16(num: usize) -> u16 {
+    num.try_into().expect("can not convert from usize to u16")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{ParseError, default_regions, json::TableDocument};
+
+    #[test]
+    fn standard_layout_matches_default_regions() {
+        fn check<const N: usize>(box_width: usize, box_height: usize) {
+            let table = Table::<N>::new_standard(box_width, box_height);
+            let expected = TableDocument::new_with_regions(N, &default_regions(N));
+            assert_eq!(TableDocument::from_table(&table).cells, expected.cells);
+        }
+
+        check::<6>(3, 2);
+        check::<8>(4, 2);
+        check::<9>(3, 3);
+        check::<12>(4, 3);
+        check::<25>(5, 5);
+        check::<32>(8, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "박스의 크기가 퍼즐의 크기와 맞지 않음")]
+    fn standard_layout_wrong_box() {
+        let _ = Table::<10>::new_standard(3, 3);
+    }
+
+    #[test]
+    fn try_constructors() {
+        assert!(matches!(
+            Table::<10>::try_new_standard(3, 3),
+            Err(SudokuError::InvalidBoxSize { size: 10, .. })
+        ));
+        assert_eq!(
+            MaxNum::<9>::try_new(9),
+            Err(SudokuError::OutOfRange { value: 9, size: 9 })
+        );
+        assert!(matches!(
+            Cell::<9>::try_new(0, 0, vec![Zone::new_unique_from_u16(1); 2]),
+            Err(SudokuError::Parse(ParseError::DuplicateZone {
+                zone: 1,
+                ..
+            }))
+        ));
+
+        let cells = vec![vec![Cell::<2>::new(1, 0, vec![]), Cell::new(0, 0, vec![])]];
+        assert!(matches!(
+            Table::try_new_with_vec_cells(cells),
+            Err(SudokuError::InvalidCellOrder { x: 0, y: 0 })
+        ));
+        let cells = vec![vec![Cell::<2>::new(0, 0, vec![]), Cell::new(1, 0, vec![])]];
+        assert!(matches!(
+            Table::try_new_with_vec_cells(cells),
+            Err(SudokuError::InvalidCellCount {
+                expected: 4,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn samurai_layout_and_solve() {
+        use crate::{
+            model::zone_cache::ZoneCache,
+            solver::{GeneralSolve, Solver},
+        };
+
+        let single = Table::<9>::new_overlapping(3, 3, &[(0, 0)]);
+        assert_eq!(
+            TableDocument::from_table(&single),
+            TableDocument::from_table(&Table::new_default_9())
+        );
+
+        let mut t = Table::new_samurai();
+        assert_eq!((t.width(), t.height(), t.len()), (21, 21, 369));
+        assert!(!t.is_full_grid());
+        assert!(t.get_cell_from_canvas(9, 0).is_none());
+        // 가운데 격자와 왼쪽 위 격자가 공유하는 박스의 cell은 박스 하나와 세로줄, 가로줄 두 개씩에 속함
+        let shared = t.get_cell_from_canvas(7, 7).unwrap();
+        assert_eq!(shared.get_coordinate(), (7, 7));
+        assert_eq!(shared.get_zone().len(), 5);
+        assert!(ZoneCache::try_new(&t).is_ok());
+
+        assert_eq!(
+            Solver::new_with_seed(&mut t, 0).fill_puzzle_with_timeout(std::time::Duration::MAX),
+            0
+        );
+        assert!(t.verify().is_empty());
+
+        let line = t.to_line();
+        let t2 = Table::new_samurai();
+        t2.set_givens_from_grid(&t.to_grid_string()).unwrap();
+        assert_eq!(t2.to_line(), line);
+        let t3 = Table::<9>::new_from_json(&t.to_json()).unwrap();
+        assert_eq!((t3.width(), t3.height()), (21, 21));
+        assert_eq!(t3, t);
+
+        let cells = vec![Cell::<9>::new(3, 0, vec![]), Cell::new(1, 1, vec![])];
+        assert!(matches!(
+            Table::try_new_with_canvas(2, 2, cells),
+            Err(SudokuError::OutOfRange { value: 3, size: 2 })
+        ));
+    }
+
+    #[test]
+    fn grid_display() {
+        use crate::model::table_builder::TableBuilder;
+
+        // N X N 격자는 README의 직소 스도쿠 예시와 같이 출력함
+        let jigsaw = TableBuilder::<9>::new()
+            .regions_from_shape(
+                "111112222
+                 411133222
+                 441333322
+                 444553336
+                 445555566
+                 477755666
+                 887777966
+                 888779996
+                 888899999",
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        jigsaw
+            .set_givens_from_line(
+                "916234578187495326345682791862751439593846217724519863239178654451367982678923145",
+            )
+            .unwrap();
+        let expected = "\
+╔═════════╦═══════╗
+║9┆1┆6┆2┆3║4┆5┆7┆8║
+╠══╌╌╌╌╌══╩═╗╌╌╌╌╌║
+║1║8┆7┆4║9┆5║3┆2┆6║
+║╌╌═╗╌══╝╌╌╌╚═╗╌╌╌║
+║3┆4║5║6┆8┆2┆7║9┆1║
+║╌╌╌╚═╬═══╗╌╌╌╚═╦═╣
+║8┆6┆2║7┆5║1┆4┆3║9║
+║╌╌╌══╝╌╌╌╚═══╦═╝╌║
+║5┆9║3┆8┆4┆6┆2║1┆7║
+║╌╌═╩═══╗╌╌╌══╝╌╌╌║
+║7║2┆4┆5║1┆9║8┆6┆3║
+╠═══╗╌╌╌╚═══╬═╗╌╌╌║
+║2┆3║9┆1┆7┆8║6║5┆4║
+║╌╌╌╚═╗╌╌╌══╝╌╚═╗╌║
+║4┆5┆1║3┆6║7┆9┆8║2║
+║╌╌╌╌╌╚═╦═╝╌╌╌╌╌╚═╣
+║6┆7┆8┆9║2┆3┆1┆4┆5║
+╚═══════╩═════════╝
+some: 81\tnone: 0";
+        assert_eq!(jigsaw.to_string().replace('\u{332}', ""), expected);
+
+        // 여러 격자가 겹친 캔버스는 cell이 없는 칸을 공백으로 출력함
+        let samurai = Table::new_samurai();
+        let display = samurai.to_string();
+        let first_row = display.lines().nth(1).unwrap();
+        assert_eq!(first_row.chars().count(), 21 * 2 + 1);
+        assert!(first_row.contains("║     ║"));
+    }
+
+    #[test]
+    fn giant_grid_over_64() {
+        use crate::solver::{GeneralSolve, Solver};
+
+        let mut t = Table::<81>::new_standard(9, 9);
+        let mut line = vec!["."; 81 * 81];
+        line[0] = "81";
+        t.set_givens_from_line(&line.join(" ")).unwrap();
+        assert!(t.to_line().starts_with("81 . ."));
+
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        solver.solve();
+        drop(solver);
+
+        // 같은 가로줄의 cell에서 81이 지워짐
+        let eighty_one = MaxNum::new(80);
+        let read = t.read_lock();
+        assert!(!read.read_from_cell(&t[80]).get_chk(eighty_one));
+        assert!(read.read_from_cell(&t[81 * 9 + 9]).get_chk(eighty_one));
+    }
+
+    /// 64 이상의 값을 포함한 81X81 격자를 끝까지 채움
+    ///
+    /// 디버그 빌드에서는 수 분이 걸리므로 cargo test --release에서만 실행합니다.
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn giant_grid_81_fill() {
+        use crate::solver::{GeneralSolve, Solver};
+
+        // 가로줄마다 9칸을 비운 올바른 81X81 퍼즐
+        let solution: Vec<String> = (0..81 * 81)
+            .map(|i| {
+                let (x, y) = (i % 81, i / 81);
+                ((x + (y % 9) * 9 + y / 9) % 81 + 1).to_string()
+            })
+            .collect();
+        let puzzle: Vec<&str> = solution
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let (x, y) = (i % 81, i / 81);
+                if (0..9).any(|j| x == (y * 10 + j * 9) % 81) {
+                    "."
+                } else {
+                    value
+                }
+            })
+            .collect();
+
+        let mut t = Table::<81>::new_standard(9, 9);
+        t.set_givens_from_line(&puzzle.join(" ")).unwrap();
+
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        assert_eq!(solver.fill_puzzle_with_timeout(std::time::Duration::MAX), 0);
+        assert!(solver.validater().is_none());
+        drop(solver);
+
+        assert_eq!(t.to_line(), solution.join(" "));
+    }
+
+    #[test]
+    fn giant_grid_100() {
+        use crate::solver::{GeneralSolve, Solver};
+
+        let mut t = Table::<100>::new_standard(10, 10);
+        assert_eq!(t.len(), 100 * 100);
+        let mut line = vec!["."; 100 * 100];
+        line[0] = "100";
+        t.set_givens_from_line(&line.join(" ")).unwrap();
+
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        assert!(solver.solve());
+        drop(solver);
+
+        // 64 이상의 값도 같은 박스, 가로줄, 세로줄의 cell에서 지워짐
+        let hundred = MaxNum::new(99);
+        let read = t.read_lock();
+        for i in [99, 100 + 1, 100 * 99] {
+            let chk = read.read_from_cell(&t[i]);
+            assert!(!chk.get_chk(hundred));
+            assert!(!chk.bit_flag().contains(hundred));
+            assert_eq!(chk.bit_flag().len(), 99);
+        }
+        assert!(read.read_from_cell(&t[100 * 11 + 11]).get_chk(hundred));
     }
 }
@@ -0,0 +1,541 @@
+use super::{
+    cell::Cell,
+    table::Table,
+    table_lock::TableLock,
+    zone::{Zone, ZoneKind},
+};
+use std::fmt::{Display, Formatter};
+
+/// TableBuilder로 테이블을 만들 때 발생하는 오류입니다. 좌표는 (x, y)이며 0부터 시작합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// 모양 문자열의 줄 수나 줄의 길이가 퍼즐 크기와 다름. line은 1부터 시작합니다.
+    InvalidShape { line: usize },
+
+    /// 좌표가 퍼즐 범위를 벗어남
+    InvalidCell { zone: String, x: usize, y: usize },
+
+    /// 한 zone에 같은 cell이 두 번 이상 들어있음
+    DuplicateCell { zone: String, x: usize, y: usize },
+
+    /// 같은 이름의 zone이 이미 있음
+    DuplicateName { zone: String },
+
+    /// 어떤 region에도 속하지 않은 cell
+    UncoveredCell { x: usize, y: usize },
+
+    /// 두 region이 같은 cell을 포함함
+    OverlappingRegions {
+        x: usize,
+        y: usize,
+        first: String,
+        second: String,
+    },
+
+    /// region이나 Unique zone에 속한 cell의 개수가 퍼즐 크기와 다르거나, cage의 cell이 퍼즐 크기보다 많음
+    WrongRegionSize {
+        zone: String,
+        expected: usize,
+        found: usize,
+    },
+
+    /// zone 번호로 표현할 수 있는 개수를 넘음
+    TooManyZones { count: usize },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::InvalidShape { line } => {
+                write!(f, "모양의 크기가 퍼즐 크기와 맞지 않음. line: {line}")
+            }
+            BuildError::InvalidCell { zone, x, y } => {
+                write!(f, "zone '{zone}'의 cell이 퍼즐 범위를 벗어남. ({x}, {y})")
+            }
+            BuildError::DuplicateCell { zone, x, y } => {
+                write!(f, "zone '{zone}'에 같은 cell이 중복됨. ({x}, {y})")
+            }
+            BuildError::DuplicateName { zone } => write!(f, "zone 이름 '{zone}'이 중복됨"),
+            BuildError::UncoveredCell { x, y } => {
+                write!(f, "어떤 region에도 속하지 않은 cell. ({x}, {y})")
+            }
+            BuildError::OverlappingRegions {
+                x,
+                y,
+                first,
+                second,
+            } => write!(
+                f,
+                "region '{first}'과 '{second}'이 같은 cell을 포함함. ({x}, {y})"
+            ),
+            BuildError::WrongRegionSize {
+                zone,
+                expected,
+                found,
+            } => write!(
+                f,
+                "zone '{zone}'의 cell 개수가 맞지 않음. expected: {expected}, found: {found}"
+            ),
+            BuildError::TooManyZones { count } => write!(f, "zone이 너무 많음. count: {count}"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpecKind {
+    /// 모든 cell을 정확히 한 번씩 덮어야 하는 영역 (박스, 직소 모양)
+    Region,
+    Unique,
+    Sum {
+        sum: usize,
+        distinct: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct ZoneSpec {
+    name: String,
+    kind: SpecKind,
+    /// 표기에 사용할 zone의 종류. 번호는 같은 종류 안에서 추가한 순서입니다.
+    zone_kind: ZoneKind,
+    cells: Vec<(usize, usize)>,
+}
+
+/// zone 번호를 직접 계산하지 않고 테이블 구조를 만드는 builder입니다.
+///
+/// region(박스나 직소 모양)은 모든 cell을 겹치지 않게 덮어야 하며, 그 외의 제약은 unique, sum, cage로 추가합니다.
+/// zone 번호는 Table::new_standard와 같이 region이 1..=N, 세로줄이 N+1..=2N, 가로줄이 2N+1..=3N이며,
+/// 추가 제약은 그 뒤에 추가한 순서대로 번호를 가집니다.
+/// zone의 종류 (ZoneKind)는 region이 Box, sum과 cage가 Cage이며 추가한 순서대로 1부터 번호를 가지고, unique는 Other 입니다.
+///
+/// ```
+/// use sudoku_solver_lib::model::table_builder::TableBuilder;
+///
+/// let table = TableBuilder::<4>::new()
+///     .regions_from_shape("AABB\nAABB\nCCDD\nCCDD")
+///     .unwrap()
+///     .unique("diagonal", (0..4).map(|i| (i, i)))
+///     .build()
+///     .unwrap();
+/// assert_eq!(table.len(), 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableBuilder<const N: usize> {
+    rows_and_columns: bool,
+    regions: Vec<ZoneSpec>,
+    extras: Vec<ZoneSpec>,
+}
+
+impl<const N: usize> Default for TableBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> TableBuilder<N> {
+    /// 가로줄과 세로줄 zone을 가진 builder를 만듭니다.
+    #[must_use]
+    pub fn new() -> Self {
+        TableBuilder {
+            rows_and_columns: true,
+            regions: Vec::new(),
+            extras: Vec::new(),
+        }
+    }
+
+    /// 가로줄과 세로줄 zone을 만들지 않습니다.
+    #[must_use]
+    pub fn without_rows_and_columns(mut self) -> Self {
+        self.rows_and_columns = false;
+        self
+    }
+
+    /// 가로 box_width칸, 세로 box_height칸의 박스를 region으로 추가합니다. 이름은 "box 1"부터 시작합니다.
+    #[must_use]
+    pub fn standard_boxes(mut self, box_width: usize, box_height: usize) -> Self {
+        let box_width = box_width.max(1);
+        let box_height = box_height.max(1);
+        let boxes_per_row = N.div_ceil(box_width);
+
+        let mut boxes: Vec<Vec<(usize, usize)>> =
+            vec![Vec::new(); boxes_per_row * N.div_ceil(box_height)];
+        for y in 0..N {
+            for x in 0..N {
+                boxes[x / box_width + (y / box_height) * boxes_per_row].push((x, y));
+            }
+        }
+
+        for (index, cells) in boxes.into_iter().enumerate() {
+            self = self.region(format!("box {}", index + 1), cells);
+        }
+        self
+    }
+
+    /// 한 줄에 N개의 문자로 된 N줄의 모양으로 region을 추가합니다.
+    ///
+    /// 같은 문자는 같은 region이며, 문자가 region의 이름이 됩니다. '.'은 어떤 region에도 속하지 않는 cell입니다.
+    /// 빈 줄과 줄 안의 공백은 무시합니다.
+    pub fn regions_from_shape(mut self, shape: &str) -> Result<Self, BuildError> {
+        let lines: Vec<(usize, Vec<char>)> = shape
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let chars = line.chars().filter(|c| !c.is_whitespace()).collect();
+                (index + 1, chars)
+            })
+            .filter(|(_, chars): &(usize, Vec<char>)| !chars.is_empty())
+            .collect();
+
+        if let Some((line, _)) = lines.iter().find(|(_, chars)| chars.len() != N) {
+            return Err(BuildError::InvalidShape { line: *line });
+        }
+        if lines.len() != N {
+            return Err(BuildError::InvalidShape {
+                line: lines.last().map_or(1, |(line, _)| line + 1),
+            });
+        }
+
+        let mut regions: Vec<(char, Vec<(usize, usize)>)> = Vec::new();
+        for (y, (_, chars)) in lines.iter().enumerate() {
+            for (x, &c) in chars.iter().enumerate() {
+                if c == '.' {
+                    continue;
+                }
+
+                match regions.iter_mut().find(|(label, _)| *label == c) {
+                    Some((_, cells)) => cells.push((x, y)),
+                    None => regions.push((c, vec![(x, y)])),
+                }
+            }
+        }
+
+        for (label, cells) in regions {
+            self = self.region(label.to_string(), cells);
+        }
+        Ok(self)
+    }
+
+    /// cell 목록으로 region을 추가합니다.
+    #[must_use]
+    pub fn region(
+        mut self,
+        name: impl Into<String>,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        self.regions.push(ZoneSpec {
+            name: name.into(),
+            kind: SpecKind::Region,
+            zone_kind: ZoneKind::Box(kind_num(self.regions.len() + 1)),
+            cells: cells.into_iter().collect(),
+        });
+        self
+    }
+
+    /// 중복이 없어야 하는 zone을 추가합니다. (예: 대각선) cell의 개수는 N이어야 합니다.
+    #[must_use]
+    pub fn unique(
+        mut self,
+        name: impl Into<String>,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        self.extras.push(ZoneSpec {
+            name: name.into(),
+            kind: SpecKind::Unique,
+            zone_kind: ZoneKind::Other,
+            cells: cells.into_iter().collect(),
+        });
+        self
+    }
+
+    /// 두 대각선을 중복이 없어야 하는 zone으로 추가합니다. 이름은 "diagonal 1" (왼쪽 위에서 오른쪽 아래), "diagonal 2" 입니다.
+    #[must_use]
+    pub fn diagonals(mut self) -> Self {
+        for (index, cells) in [
+            (0..N).map(|i| (i, i)).collect(),
+            (0..N).map(|i| (N - 1 - i, i)).collect::<Vec<_>>(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            self.extras.push(ZoneSpec {
+                name: format!("diagonal {}", index + 1),
+                kind: SpecKind::Unique,
+                zone_kind: ZoneKind::Diagonal(kind_num(index + 1)),
+                cells,
+            });
+        }
+        self
+    }
+
+    /// 속한 cell들의 합이 sum이 되어야 하는 zone을 추가합니다. 같은 값이 여러 번 들어갈 수 있습니다.
+    #[must_use]
+    pub fn sum(
+        self,
+        name: impl Into<String>,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        sum: usize,
+    ) -> Self {
+        self.push_sum(name.into(), cells.into_iter().collect(), sum, false)
+    }
+
+    /// 킬러 스도쿠의 cage를 추가합니다. 속한 cell들의 합이 sum이 되어야 하며, 같은 값이 두 번 이상 들어갈 수 없습니다.
+    /// cell의 개수는 N 이하여야 합니다.
+    #[must_use]
+    pub fn cage(
+        self,
+        name: impl Into<String>,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        sum: usize,
+    ) -> Self {
+        self.push_sum(name.into(), cells.into_iter().collect(), sum, true)
+    }
+
+    fn push_sum(
+        mut self,
+        name: String,
+        cells: Vec<(usize, usize)>,
+        sum: usize,
+        distinct: bool,
+    ) -> Self {
+        let cage_cnt = self
+            .extras
+            .iter()
+            .filter(|spec| matches!(spec.kind, SpecKind::Sum { .. }))
+            .count();
+        self.extras.push(ZoneSpec {
+            name,
+            kind: SpecKind::Sum { sum, distinct },
+            zone_kind: ZoneKind::Cage(kind_num(cage_cnt + 1)),
+            cells,
+        });
+        self
+    }
+
+    /// 이름에 해당하는 zone의 번호를 반환합니다. 가로줄, 세로줄은 "row 1", "column 1" 형식의 이름을 가집니다.
+    #[must_use]
+    pub fn zone_id(&self, name: &str) -> Option<u16> {
+        self.zone_specs()
+            .into_iter()
+            .position(|spec| spec.name == name)
+            .and_then(|index| u16::try_from(index + 1).ok())
+    }
+
+    /// 구조를 검사하고 테이블을 만듭니다.
+    pub fn build(&self) -> Result<TableLock<N>, BuildError> {
+        let specs = self.zone_specs();
+        if specs.len() > usize::from(u16::MAX) {
+            return Err(BuildError::TooManyZones { count: specs.len() });
+        }
+
+        let mut names: Vec<&str> = Vec::with_capacity(specs.len());
+        for spec in &specs {
+            if names.contains(&spec.name.as_str()) {
+                return Err(BuildError::DuplicateName {
+                    zone: spec.name.clone(),
+                });
+            }
+            names.push(&spec.name);
+        }
+
+        let mut cell_zones: Vec<Vec<Zone>> = vec![Vec::new(); N * N];
+        let mut region_of: Vec<Option<&str>> = vec![None; N * N];
+        for (index, spec) in specs.iter().enumerate() {
+            let zone = match spec.kind {
+                SpecKind::Region | SpecKind::Unique => Zone::new_unique_from_usize(index + 1),
+                SpecKind::Sum {
+                    sum,
+                    distinct: false,
+                } => Zone::new_sum_from_u16((index + 1) as u16, sum),
+                SpecKind::Sum {
+                    sum,
+                    distinct: true,
+                } => Zone::new_cage_from_u16((index + 1) as u16, sum),
+            }
+            .with_kind(spec.zone_kind);
+
+            let wrong_size = match spec.kind {
+                SpecKind::Region | SpecKind::Unique => spec.cells.len() != N,
+                SpecKind::Sum { distinct, .. } => distinct && spec.cells.len() > N,
+            };
+            if wrong_size {
+                return Err(BuildError::WrongRegionSize {
+                    zone: spec.name.clone(),
+                    expected: N,
+                    found: spec.cells.len(),
+                });
+            }
+
+            for &(x, y) in &spec.cells {
+                if x >= N || y >= N {
+                    return Err(BuildError::InvalidCell {
+                        zone: spec.name.clone(),
+                        x,
+                        y,
+                    });
+                }
+
+                let zones = &mut cell_zones[x + y * N];
+                if zones.contains(&zone) {
+                    return Err(BuildError::DuplicateCell {
+                        zone: spec.name.clone(),
+                        x,
+                        y,
+                    });
+                }
+                zones.push(zone);
+
+                if spec.kind == SpecKind::Region {
+                    if let Some(first) = region_of[x + y * N] {
+                        return Err(BuildError::OverlappingRegions {
+                            x,
+                            y,
+                            first: first.to_string(),
+                            second: spec.name.clone(),
+                        });
+                    }
+                    region_of[x + y * N] = Some(&spec.name);
+                }
+            }
+        }
+
+        let uncovered = region_of.iter().position(Option::is_none);
+        if let Some(index) = uncovered.filter(|_| !self.regions.is_empty()) {
+            return Err(BuildError::UncoveredCell {
+                x: index % N,
+                y: index / N,
+            });
+        }
+
+        let mut zones = cell_zones.into_iter();
+        let mut cells: Vec<Vec<Cell<N>>> = Vec::with_capacity(N);
+        for y in 0..N {
+            let mut row: Vec<Cell<N>> = Vec::with_capacity(N);
+            for x in 0..N {
+                let zone = zones.next().unwrap_or_default();
+                row.push(Cell::new(x, y, zone));
+            }
+            cells.push(row);
+        }
+
+        Ok(Table::new_with_vec_cells(cells))
+    }
+
+    /// 번호 순서대로 정렬된 zone 목록. index + 1이 zone 번호입니다.
+    fn zone_specs(&self) -> Vec<ZoneSpec> {
+        let mut specs = self.regions.clone();
+        if self.rows_and_columns {
+            specs.extend((0..N).map(|x| ZoneSpec {
+                name: format!("column {}", x + 1),
+                kind: SpecKind::Unique,
+                zone_kind: ZoneKind::Column(kind_num(x + 1)),
+                cells: (0..N).map(|y| (x, y)).collect(),
+            }));
+            specs.extend((0..N).map(|y| ZoneSpec {
+                name: format!("row {}", y + 1),
+                kind: SpecKind::Unique,
+                zone_kind: ZoneKind::Row(kind_num(y + 1)),
+                cells: (0..N).map(|x| (x, y)).collect(),
+            }));
+        }
+        specs.extend(self.extras.iter().cloned());
+        specs
+    }
+}
+
+/// zone의 종류 번호. zone 번호로 표현할 수 없는 개수는 build에서 TooManyZones 오류가 됩니다.
+fn kind_num(num: usize) -> u16 {
+    u16::try_from(num).unwrap_or(u16::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::json::TableDocument;
+
+    #[test]
+    fn build_jigsaw_and_standard() {
+        let standard = TableBuilder::<9>::new()
+            .standard_boxes(3, 3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            TableDocument::from_table(&standard),
+            TableDocument::from_table(&Table::new_default_9())
+        );
+
+        let shape = "
+            111112222
+            411133222
+            441333322
+            444553336
+            445555566
+            477755666
+            887777966
+            888779996
+            888899999
+        ";
+        let builder = TableBuilder::<9>::new()
+            .regions_from_shape(shape)
+            .unwrap()
+            .unique("diagonal", (0..9).map(|i| (i, i)))
+            .sum("corner", [(0, 0), (1, 0)], 10);
+        assert_eq!(builder.zone_id("4"), Some(3));
+        assert_eq!(builder.zone_id("row 1"), Some(19));
+        assert_eq!(builder.zone_id("corner"), Some(29));
+
+        let table = builder.build().unwrap();
+        let read = table.read_lock();
+        assert_eq!(read.into_iter().next().unwrap().0.get_zone().len(), 5);
+    }
+
+    #[test]
+    fn build_errors() {
+        let uncovered = TableBuilder::<4>::new()
+            .regions_from_shape("AABB\nAAB.\nCCDD\nCCDD")
+            .unwrap();
+        assert_eq!(
+            uncovered.build().unwrap_err(),
+            BuildError::WrongRegionSize {
+                zone: "B".to_string(),
+                expected: 4,
+                found: 3
+            }
+        );
+
+        let overlapping = TableBuilder::<4>::new()
+            .standard_boxes(2, 2)
+            .region("extra", [(0, 0), (1, 0), (2, 0), (3, 0)]);
+        assert!(matches!(
+            overlapping.build(),
+            Err(BuildError::OverlappingRegions { x: 0, y: 0, .. })
+        ));
+
+        let missing = TableBuilder::<4>::new()
+            .region("A", [(0, 0), (1, 0), (0, 1), (1, 1)])
+            .region("B", [(2, 0), (3, 0), (2, 1), (3, 1)]);
+        assert_eq!(
+            missing.build().unwrap_err(),
+            BuildError::UncoveredCell { x: 0, y: 2 }
+        );
+
+        let big_cage =
+            TableBuilder::<4>::new().cage("cage", (0..4).map(|x| (x, 0)).chain([(0, 1)]), 12);
+        assert_eq!(
+            big_cage.build().unwrap_err(),
+            BuildError::WrongRegionSize {
+                zone: "cage".to_string(),
+                expected: 4,
+                found: 5
+            }
+        );
+
+        assert_eq!(
+            TableBuilder::<4>::new()
+                .regions_from_shape("AABB\nAABB\nCCDD")
+                .unwrap_err(),
+            BuildError::InvalidShape { line: 4 }
+        );
+    }
+}
@@ -1,49 +1,93 @@
-use super::{cell::Cell, max_num::MaxNum, table::Table};
-use crate::num_check::NumCheck;
+use super::{
+    cell::Cell, cell_pos::CellPos, max_num::MaxNum, symbol_set::SymbolSet, table::Table,
+    table_structure::TableStructure, zone::Zone,
+};
+use crate::{error::SudokuError, num_check::NumCheck};
 use std::{
-    fmt::{Debug, Display},
+    fmt::{Debug, Display, Write},
     ops::{Deref, Range},
-    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    sync::{
+        OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        atomic::{AtomicU64, Ordering},
+    },
 };
 
+/// 다음에 만들 테이블의 id
+static NEXT_TABLE_ID: AtomicU64 = AtomicU64::new(0);
+
 pub struct TableLock<const N: usize> {
     table: Table<N>,
+    /// 테이블마다 다른 id. 복제한 테이블도 새 id를 가집니다.
+    id: u64,
     cell_addr_range: Range<usize>,
     lock: RwLock<()>,
+    symbols: SymbolSet<N>,
+    /// 처음 구조를 조회할 때 계산합니다.
+    structure: OnceLock<TableStructure>,
 }
 
 impl<const N: usize> TableLock<N> {
     pub fn new(t: Table<N>) -> TableLock<N> {
-        assert_eq!(t.cells.len(), N * N);
-
         let cell_addr_range: Range<usize>;
         unsafe {
             let base_ptr = t.cells.as_ptr();
-            let end_ptr = base_ptr.add(N * N);
+            let end_ptr = base_ptr.add(t.cells.len());
             cell_addr_range = base_ptr as usize..end_ptr as usize;
         }
 
         TableLock {
             table: t,
+            id: NEXT_TABLE_ID.fetch_add(1, Ordering::Relaxed),
             cell_addr_range,
             lock: RwLock::new(()),
+            symbols: SymbolSet::default(),
+            structure: OnceLock::new(),
         }
     }
 
+    #[must_use]
+    #[inline]
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// # Panics
+    ///
+    /// 다른 스레드가 lock을 가진 채 panic하여 lock이 poison 상태인 경우. 이를 오류로 처리하려면 try_read_lock을 사용합니다.
     #[inline]
     pub fn read_lock(&self) -> TableLockReadGuard<'_, '_, N> {
-        TableLockReadGuard {
-            table_lock: self,
-            _read_guard: self.lock.read().unwrap_or_else(|err| err.into_inner()),
-        }
+        self.try_read_lock().unwrap_or_else(|err| panic!("{err}"))
     }
 
+    /// # Panics
+    ///
+    /// 다른 스레드가 lock을 가진 채 panic하여 lock이 poison 상태인 경우. 이를 오류로 처리하려면 try_write_lock을 사용합니다.
     #[inline]
     pub fn write_lock(&self) -> TableLockWriteGuard<'_, '_, N> {
-        TableLockWriteGuard {
+        self.try_write_lock().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// read_lock과 같으나, lock이 poison 상태인 경우 LockPoisoned 오류를 반환합니다.
+    pub fn try_read_lock(&self) -> Result<TableLockReadGuard<'_, '_, N>, SudokuError> {
+        Ok(TableLockReadGuard {
             table_lock: self,
-            _write_guard: self.lock.write().unwrap_or_else(|err| err.into_inner()),
-        }
+            _read_guard: self.lock.read().map_err(|_| SudokuError::LockPoisoned)?,
+        })
+    }
+
+    /// write_lock과 같으나, lock이 poison 상태인 경우 LockPoisoned 오류를 반환합니다.
+    pub fn try_write_lock(&self) -> Result<TableLockWriteGuard<'_, '_, N>, SudokuError> {
+        Ok(TableLockWriteGuard {
+            table_lock: self,
+            _write_guard: self.lock.write().map_err(|_| SudokuError::LockPoisoned)?,
+        })
+    }
+
+    /// cell이 이 테이블에 속해있는지 확인합니다.
+    #[must_use]
+    #[inline]
+    pub fn contains_cell(&self, cell: &Cell<N>) -> bool {
+        self.cell_addr_range.contains(&(cell as *const _ as usize))
     }
 
     /// cell은 table에 속해있어야만 함. 그렇지 않으면 panic 발생.
@@ -51,11 +95,7 @@ impl<const N: usize> TableLock<N> {
     /// 다른 table에 속한 cell을 여기서 read, write하는 걸 방지하기 위함.
     #[inline]
     fn assert_cell_in_table(&self, cell: &Cell<N>) {
-        let cell_addr = cell as *const _ as usize;
-        assert!(
-            self.cell_addr_range.contains(&cell_addr),
-            "assert_cell_in_table FAIL",
-        );
+        assert!(self.contains_cell(cell), "assert_cell_in_table FAIL");
     }
 
     /// 테이블 전체 NumCheck가 올바른지 검사합니다. 디버그 빌드에서만 검사합니다.
@@ -68,56 +108,106 @@ impl<const N: usize> TableLock<N> {
         }
     }
 
+    /// 캔버스의 가로 칸 수입니다. 기본 구조에서는 N 입니다.
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.table.width
+    }
+
+    /// 캔버스의 세로 칸 수입니다. 기본 구조에서는 N 입니다.
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.table.height
+    }
+
+    /// 캔버스가 N X N이고 모든 칸에 cell이 있는지 여부. 한 장의 격자만 다루는 입출력 형식은 이 경우만 지원합니다.
+    #[must_use]
+    #[inline]
+    pub fn is_full_grid(&self) -> bool {
+        self.width() == N && self.height() == N && self.len() == N * N
+    }
+
+    /// 캔버스 좌표의 cell을 반환합니다. 캔버스를 벗어났거나 cell이 없는 칸이면 None
+    #[must_use]
+    #[inline]
+    pub fn get_cell_from_canvas(&self, x: usize, y: usize) -> Option<&Cell<N>> {
+        self.table.get_from_canvas(x, y)
+    }
+
+    /// "r3c7" 과 같은 r1c1 표기의 cell을 반환합니다. 표기가 올바르지 않거나 cell이 없는 칸이면 None
+    #[must_use]
+    pub fn get_cell_from_notation(&self, notation: &str) -> Option<&Cell<N>> {
+        let pos: CellPos = notation.parse().ok()?;
+        self.get_cell_from_canvas(pos.x, pos.y)
+    }
+
+    /// 화면과 입출력에 사용하는 기호입니다.
+    #[must_use]
+    #[inline]
+    pub fn symbols(&self) -> &SymbolSet<N> {
+        &self.symbols
+    }
+
+    /// 화면과 입출력에 사용할 기호를 설정합니다. 테이블의 노트는 바뀌지 않습니다.
+    pub fn set_symbols(&mut self, symbols: SymbolSet<N>) {
+        self.symbols = symbols;
+    }
+
+    #[inline]
+    pub(crate) fn structure(&self) -> &TableStructure {
+        self.structure
+            .get_or_init(|| TableStructure::new(&self.table.cells))
+    }
+
+    /// cell이 없는 좌표인 경우 panic
     #[must_use]
     #[inline]
     pub fn get_cell_from_coordinate(&self, x: MaxNum<N>, y: MaxNum<N>) -> &Cell<N> {
-        unsafe {
-            // MaxNum<N>의 값은 N보다 작은 것이 보장됨
-            self.table
-                .cells
-                .get_unchecked(x.get_value() + y.get_value() * N)
-        }
+        self.table.get_from_coordi(x, y)
     }
 
+    /// cell마다 노트를 정사각형으로 나열하여 출력합니다. 여러 글자 기호는 같은 너비로 맞추고 공백으로 구분합니다.
     pub fn note_fmt(&self) -> String {
         let rec_size = (N as f64).sqrt().ceil() as usize;
-        let mut row_string: Vec<String> = Vec::with_capacity(N * 2);
-        let mut row_string_cursor = 0_usize;
+        let note_width = self.symbols.note_width();
+        let (width, height) = (self.width(), self.height());
+        let mut ret = String::with_capacity(width * height * N * (note_width + 1));
 
         let read = self.read_lock();
 
-        for y in MaxNum::<N>::iter() {
-            for _ in 0..rec_size {
-                row_string.push(String::with_capacity(N * 2));
-            }
+        for y in 0..height {
+            let mut row_string: Vec<String> =
+                vec![String::with_capacity(width * (rec_size * note_width + 1)); rec_size];
 
-            for x in MaxNum::<N>::iter() {
-                let cell = read.read_from_coordinate(x, y);
+            for x in 0..width {
+                let cell = self
+                    .get_cell_from_canvas(x, y)
+                    .map(|c| read.read_from_cell(c));
                 let mut write_row_cursor = 0;
                 for n in MaxNum::<N>::iter() {
-                    if cell.get_chk(n) {
-                        row_string[row_string_cursor + write_row_cursor].push(n.get_char());
-                    } else {
-                        row_string[row_string_cursor + write_row_cursor].push(' ');
-                    }
+                    let symbol = match cell {
+                        Some(cell) if cell.get_chk(n) => self.symbols.get(n),
+                        _ => "",
+                    };
+                    let _ = write!(row_string[write_row_cursor], "{symbol:<note_width$}");
 
                     if (n.get_value() + 1) % rec_size == 0 {
                         write_row_cursor += 1;
                     }
                 }
 
-                for row in row_string.iter_mut().skip(row_string_cursor).take(rec_size) {
+                for row in &mut row_string {
                     row.push('|');
                 }
             }
-            row_string.push("-".repeat(N * rec_size + N));
-            row_string_cursor += rec_size + 1;
-        }
 
-        let mut ret = String::with_capacity(N * N * N * 2);
-
-        for row in row_string {
-            ret.push_str(&row);
+            for row in row_string {
+                ret.push_str(&row);
+                ret.push('\n');
+            }
+            ret.push_str(&"-".repeat(width * rec_size * note_width + width));
             ret.push('\n');
         }
 
@@ -133,6 +223,7 @@ impl<const N: usize> Deref for TableLock<N> {
     }
 }
 
+/// 확정된 값을 격자로 출력합니다. given인 값은 밑줄로 표시합니다.
 impl<const N: usize> Display for TableLock<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let read = self.read_lock();
@@ -149,20 +240,18 @@ impl<const N: usize> Debug for TableLock<N> {
 
 impl<const N: usize> PartialEq for TableLock<N> {
     fn eq(&self, other: &Self) -> bool {
-        let read_self = self.read_lock();
-        let read_other = other.read_lock();
-        for x in MaxNum::<N>::iter() {
-            for y in MaxNum::<N>::iter() {
-                let r1 = read_self.read_from_coordinate(x, y);
-                let r2 = read_other.read_from_coordinate(x, y);
-
-                if !r1.is_same_note(r2) {
-                    return false;
-                }
-            }
+        if self.len() != other.len() {
+            return false;
         }
 
-        true
+        let read_self = self.read_lock();
+        let read_other = other.read_lock();
+        (&read_self)
+            .into_iter()
+            .zip(&read_other)
+            .all(|((c1, r1), (c2, r2))| {
+                c1.get_coordinate() == c2.get_coordinate() && r1.is_same_note(r2)
+            })
     }
 }
 
@@ -238,15 +327,116 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
     }
 
     fn make_string(&self, final_fn: impl Fn(&NumCheck<N>) -> Option<MaxNum<N>>) -> String {
+        if self.table_lock.is_full_grid() {
+            return self.make_grid_string(final_fn);
+        }
+
         let mut some = 0u32;
         let mut none = 0u32;
-        let mut ret = String::with_capacity(N * N * 5);
 
         let table = &self.table_lock.table;
+        let symbols = &self.table_lock.symbols;
+        let symbol_width = symbols.width();
+        let (width, height) = (table.width, table.height);
+        let mut ret =
+            String::with_capacity((width * (symbol_width + 1) + 2) * (height * 2 + 1) * 3);
+
+        // 캔버스 바깥이나 cell이 없는 칸은 None. 좌표는 바깥을 가리킬 수 있도록 1을 더한 값을 사용함
+        let zone_at = |x: usize, y: usize| -> Option<Option<Zone>> {
+            table
+                .get_from_canvas(x.checked_sub(1)?, y.checked_sub(1)?)
+                .map(Cell::rep_zone)
+        };
+
+        for y in 0..=height {
+            // y번째 줄 위의 가로 경계선
+            for x in 0..=width {
+                let (up_left, up_right) = (zone_at(x, y), zone_at(x + 1, y));
+                let (down_left, down_right) = (zone_at(x, y + 1), zone_at(x + 1, y + 1));
+
+                if [up_left, up_right, down_left, down_right]
+                    .iter()
+                    .all(Option::is_none)
+                {
+                    ret.push(' ');
+                } else {
+                    ret.push(junction_char(
+                        up_left != up_right,
+                        up_left != down_left,
+                        up_right != down_right,
+                        down_left != down_right,
+                    ));
+                }
+
+                if x != width {
+                    let line = match (up_right, down_right) {
+                        (None, None) => ' ',
+                        (up, down) if up == down => '╌',
+                        _ => '═',
+                    };
+                    ret.extend(std::iter::repeat_n(line, symbol_width));
+                }
+            }
+            ret.push('\n');
+
+            if y == height {
+                break;
+            }
+
+            for x in 0..=width {
+                let (left, right) = (zone_at(x, y + 1), zone_at(x + 1, y + 1));
+                ret.push(match (left, right) {
+                    (None, None) => ' ',
+                    (left, right) if left == right => '┆',
+                    _ => '║',
+                });
+
+                if x == width {
+                    break;
+                }
+
+                match table.get_from_canvas(x, y) {
+                    Some(cell) => {
+                        let cell = unsafe { self.read_from_cell_unchecked(cell) };
+                        if let Some(num) = final_fn(cell) {
+                            push_symbol(&mut ret, symbols.get(num), symbol_width, cell.is_given());
+                            some += 1;
+                        } else {
+                            ret.push_str(&" ".repeat(symbol_width));
+                            none += 1;
+                        }
+                    }
+                    None => ret.push_str(&" ".repeat(symbol_width)),
+                }
+            }
+            ret.push('\n');
+        }
+
+        ret.push_str("some: ");
+        ret.push_str(&some.to_string());
+        ret.push('\t');
+        ret.push_str("none: ");
+        ret.push_str(&none.to_string());
+
+        ret
+    }
+
+    /// N X N 격자를 출력합니다. 여러 격자가 겹친 캔버스는 make_string에서 칸마다 경계를 계산합니다.
+    fn make_grid_string(&self, final_fn: impl Fn(&NumCheck<N>) -> Option<MaxNum<N>>) -> String {
+        let mut some = 0u32;
+        let mut none = 0u32;
+
+        let table = &self.table_lock.table;
+        let symbols = &self.table_lock.symbols;
+        let symbol_width = symbols.width();
+        let mut ret = String::with_capacity(N * N * (symbol_width + 1) * 6);
+
+        // cell 하나의 너비만큼 가로선을 그림
+        let line = |ret: &mut String, c: char| ret.extend(std::iter::repeat_n(c, symbol_width));
 
         // 첫 줄
         ret.push('╔');
-        ret.push('═');
+        line(&mut ret, '═');
 
         for x in MaxNum::<N>::iter() {
             if x == MaxNum::<N>::MAX {
@@ -261,7 +451,7 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
                     ret.push('╦');
                 }
 
-                ret.push('═');
+                line(&mut ret, '═');
             }
         }
 
@@ -272,24 +462,23 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
                     let this_cell = table.get_from_coordi(x, y.offset(-1).unwrap());
                     let next_y = table.get_from_coordi(x, y);
                     if x == MaxNum::<N>::MIN {
-                        if this_cell.rep_zone() == next_y.rep_zone() {
-                            ret.push('║');
-                            ret.push('╌');
-                            ret.push('╌');
+                        let (border, c) = if this_cell.rep_zone() == next_y.rep_zone() {
+                            ('║', '╌')
                         } else {
-                            ret.push('╠');
-                            ret.push('═');
-                            ret.push('═');
-                        }
+                            ('╠', '═')
+                        };
+                        ret.push(border);
+                        line(&mut ret, c);
+                        ret.push(c);
 
                         continue;
                     }
                     if x == MaxNum::<N>::MAX {
                         if this_cell.rep_zone() == next_y.rep_zone() {
-                            ret.push('╌');
+                            line(&mut ret, '╌');
                             ret.push('║');
                         } else {
-                            ret.push('═');
+                            line(&mut ret, '═');
                             ret.push('╣');
                         }
                         continue;
@@ -302,52 +491,21 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
                     let right_side = next_x.rep_zone() == next_xy.rep_zone();
                     let down_side = next_y.rep_zone() == next_xy.rep_zone();
 
-                    match (up_side, left_side, right_side, down_side) {
-                        (true, true, true, true) => {
-                            ret.push('╌');
-                            ret.push('╌')
-                        }
-                        (false, false, false, false) => {
-                            ret.push('═');
-                            ret.push('╬');
-                        }
-                        (true, false, false, true) => {
-                            ret.push('═');
-                            ret.push('═');
-                        }
-                        (false, true, true, false) => {
-                            ret.push('╌');
-                            ret.push('║');
-                        }
-                        (true, true, false, false) => {
-                            ret.push('╌');
-                            ret.push('═');
-                        }
-                        (false, false, false, true) => {
-                            ret.push('═');
-                            ret.push('╩');
-                        }
-                        (true, false, true, false) => {
-                            ret.push('═');
-                            ret.push('╗');
-                        }
-                        (false, false, true, true) => {
-                            ret.push('═');
-                            ret.push('╝');
-                        }
-                        (false, true, false, true) => {
-                            ret.push('╌');
-                            ret.push('╚');
-                        }
-                        (true, false, false, false) => {
-                            ret.push('═');
-                            ret.push('╦');
-                        }
-                        _ => {
-                            ret.push(' ');
-                            ret.push(' ')
-                        }
-                    }
+                    let (c, junction) = match (up_side, left_side, right_side, down_side) {
+                        (true, true, true, true) => ('╌', '╌'),
+                        (false, false, false, false) => ('═', '╬'),
+                        (true, false, false, true) => ('═', '═'),
+                        (false, true, true, false) => ('╌', '║'),
+                        (true, true, false, false) => ('╌', '═'),
+                        (false, false, false, true) => ('═', '╩'),
+                        (true, false, true, false) => ('═', '╗'),
+                        (false, false, true, true) => ('═', '╝'),
+                        (false, true, false, true) => ('╌', '╚'),
+                        (true, false, false, false) => ('═', '╦'),
+                        _ => (' ', ' '),
+                    };
+                    line(&mut ret, c);
+                    ret.push(junction);
                 }
             }
             ret.push('\n');
@@ -355,12 +513,11 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
 
             for x in MaxNum::<N>::iter() {
                 let cell = self.read_from_coordinate(x, y);
-                let final_num = final_fn(cell);
-                if let Some(num) = final_num {
-                    ret.push(num.get_char());
+                if let Some(num) = final_fn(cell) {
+                    push_symbol(&mut ret, symbols.get(num), symbol_width, cell.is_given());
                     some += 1;
                 } else {
-                    ret.push(' ');
+                    line(&mut ret, ' ');
                     none += 1;
                 }
 
@@ -382,7 +539,7 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
 
         // 마지막 줄
         ret.push('╚');
-        ret.push('═');
+        line(&mut ret, '═');
         for x in MaxNum::<N>::iter() {
             if x == MaxNum::<N>::MAX {
                 ret.push('╝');
@@ -396,7 +553,7 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
                     ret.push('╩');
                 }
 
-                ret.push('═');
+                line(&mut ret, '═');
             }
         }
         ret.push('\n');
@@ -412,6 +569,12 @@ impl<'a, const N: usize> TableLockReadGuard<'a, '_, N> {
     pub fn to_string_with_punch(&self) -> String {
         self.make_string(NumCheck::fixed_final_num)
     }
+
+    /// CellStatus::Given인 값만 표시합니다. 풀이 중인 테이블에서 문제만 출력할 때 사용합니다.
+    #[must_use]
+    pub fn to_string_givens(&self) -> String {
+        self.make_string(|chk| chk.final_num().filter(|_| chk.is_given()))
+    }
 }
 
 impl<const N: usize> Display for TableLockReadGuard<'_, '_, N> {
@@ -484,6 +647,21 @@ impl<'a, const N: usize> TableLockWriteGuard<'a, '_, N> {
         unsafe { &mut *cell.chk_unsafe.get() }
     }
 
+    /// write_from_cell과 같으나, given인 cell인 경우 오류를 반환합니다.
+    ///
+    /// given의 값을 바꾸려면 write_from_cell로 얻은 NumCheck의 set_status로 먼저 출처를 지워야 합니다.
+    pub fn try_write_from_cell(
+        &mut self,
+        cell: &Cell<N>,
+    ) -> Result<&'a mut NumCheck<N>, SudokuError> {
+        let chk = self.write_from_cell(cell);
+        if chk.is_given() {
+            let (x, y) = cell.get_coordinate();
+            return Err(SudokuError::GivenCell { x, y });
+        }
+        Ok(chk)
+    }
+
     /// # Safety
     ///
     /// Table 내에 속한 Cell로만 이 함수를 호출해야 함.
@@ -534,3 +712,39 @@ impl<'a, const N: usize> Iterator for WriteCellIter<'a, '_, '_, N> {
         unsafe { Some((cell, self.write.write_from_cell_unchecked(cell))) }
     }
 }
+
+/// 가로 경계선의 교차점 문자입니다. 각 인자는 교차점에서 위, 왼쪽, 오른쪽, 아래로 굵은 선이 이어지는지 여부입니다.
+/// given을 표시하는 밑줄 (combining low line). 글자의 폭을 바꾸지 않습니다.
+const GIVEN_MARK: char = '\u{0332}';
+
+/// 기호를 symbol_width칸에 오른쪽 정렬하여 씁니다. given인 경우 기호의 각 글자에 밑줄을 붙입니다.
+fn push_symbol(ret: &mut String, symbol: &str, symbol_width: usize, given: bool) {
+    for _ in symbol.chars().count()..symbol_width {
+        ret.push(' ');
+    }
+    for c in symbol.chars() {
+        ret.push(c);
+        if given {
+            ret.push(GIVEN_MARK);
+        }
+    }
+}
+
+fn junction_char(up: bool, left: bool, right: bool, down: bool) -> char {
+    match (up, left, right, down) {
+        (false, false, false, false) => '╌',
+        (true, true, true, true) => '╬',
+        (true, false, false, true) => '║',
+        (false, true, true, false) => '═',
+        (false, false, true, true) => '╔',
+        (false, true, false, true) => '╗',
+        (true, false, true, false) => '╚',
+        (true, true, false, false) => '╝',
+        (false, true, true, true) => '╦',
+        (true, true, true, false) => '╩',
+        (true, false, true, true) => '╠',
+        (true, true, false, true) => '╣',
+        (_, true, _, _) | (_, _, true, _) => '═',
+        _ => '║',
+    }
+}
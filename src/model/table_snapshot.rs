@@ -0,0 +1,148 @@
+use super::{
+    cell::Cell, max_num::MaxNum, note_bits::NoteBits, table::Table, table_lock::TableLock,
+};
+use crate::{error::SudokuError, num_check::CellStatus};
+
+/// 테이블의 노트를 저장한 snapshot입니다. 구조는 저장하지 않으며, 만든 테이블에만 복원할 수 있습니다.
+///
+/// cell마다 노트의 bit flag와 fixed_final_num, CellStatus만 저장하므로 테이블을 복제하는 것보다 가볍습니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSnapshot<const N: usize> {
+    /// snapshot을 만든 테이블의 id
+    table_id: u64,
+    notes: Vec<(NoteBits<N>, Option<MaxNum<N>>, CellStatus)>,
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 현재 노트의 snapshot을 만듭니다.
+    #[must_use]
+    pub fn snapshot(&self) -> TableSnapshot<N> {
+        let read = self.read_lock();
+        TableSnapshot {
+            table_id: self.id(),
+            notes: read
+                .into_iter()
+                .map(|(_, chk)| (chk.bit_flag(), chk.fixed_final_num(), chk.status()))
+                .collect(),
+        }
+    }
+
+    /// snapshot을 만든 시점의 노트로 되돌립니다.
+    ///
+    /// 다른 테이블의 snapshot이거나, 현재 given인 cell이 snapshot에서 같은 값의 given이 아닌 경우 오류를 반환하며 테이블을 바꾸지 않습니다.
+    /// Solver의 히스토리는 되돌리지 않으므로, 복원한 뒤에는 새 Solver를 사용해야 합니다.
+    pub fn restore(&self, snapshot: &TableSnapshot<N>) -> Result<(), SudokuError> {
+        if snapshot.table_id != self.id() {
+            return Err(SudokuError::SnapshotMismatch);
+        }
+
+        let read = self.read_lock();
+        for ((cell, chk), &(bit_flag, _, status)) in read.into_iter().zip(&snapshot.notes) {
+            let kept = chk
+                .final_num()
+                .is_some_and(|value| bit_flag == NoteBits::from(value))
+                && status == CellStatus::Given;
+            if chk.is_given() && !kept {
+                let (x, y) = cell.get_coordinate();
+                return Err(SudokuError::GivenCell { x, y });
+            }
+        }
+        drop(read);
+
+        self.restore_notes(&snapshot.notes);
+        Ok(())
+    }
+
+    fn restore_notes(&self, notes: &[(NoteBits<N>, Option<MaxNum<N>>, CellStatus)]) {
+        let mut write = self.write_lock();
+        for ((_, chk), &(bit_flag, fixed_final_num, status)) in (&mut write).into_iter().zip(notes)
+        {
+            let list: Vec<MaxNum<N>> = bit_flag.iter().collect();
+            // restore에서 given이 바뀌지 않는 것을 확인했으므로 출처를 지운 뒤 노트를 씀
+            chk.set_status(CellStatus::Open);
+            chk.set_to_chk_list(&list);
+            chk.fixed_final_num_set(fixed_final_num);
+            chk.set_status(status);
+        }
+    }
+}
+
+/// 같은 구조와 노트, 기호를 가진 독립된 테이블을 만듭니다. 복제한 테이블의 cell은 원본과 다른 주소를 가집니다.
+impl<const N: usize> Clone for TableLock<N> {
+    fn clone(&self) -> Self {
+        let cells = self
+            .iter()
+            .map(|cell| {
+                let (x, y) = cell.get_coordinate();
+                Cell::new(x, y, cell.get_zone().to_vec())
+            })
+            .collect();
+
+        let mut ret = Table::new_with_canvas(self.width(), self.height(), cells);
+        ret.set_symbols(self.symbols().clone());
+        ret.restore_notes(&self.snapshot().notes);
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{GeneralSolve, Solver};
+    use std::time::Duration;
+
+    #[test]
+    fn clone_and_restore() {
+        let mut t = Table::new_default_9_from_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        let puzzle = t.to_line();
+        let snapshot = t.snapshot();
+
+        let mut copy = t.clone();
+        assert_eq!(copy, t);
+        assert_eq!(copy.to_json(), t.to_json());
+
+        Solver::new_with_seed(&mut copy, 0).fill_puzzle_with_timeout(Duration::MAX);
+        assert_eq!(t.to_line(), puzzle);
+        assert_ne!(copy.to_line(), puzzle);
+
+        Solver::new_with_seed(&mut t, 0).fill_puzzle_with_timeout(Duration::MAX);
+        assert_eq!(t, copy);
+        t.restore(&snapshot).unwrap();
+        assert_eq!(t.to_line(), puzzle);
+        assert_eq!(t.snapshot(), snapshot);
+
+        assert_eq!(copy.restore(&snapshot), Err(SudokuError::SnapshotMismatch));
+        // 이전 테이블이 해제된 주소를 새 테이블이 다시 쓰더라도 id가 다르므로 복원하지 않음
+        drop(copy);
+        assert_eq!(
+            Table::new_default_9().restore(&snapshot),
+            Err(SudokuError::SnapshotMismatch)
+        );
+    }
+
+    #[test]
+    fn given_not_overwritten() {
+        let t = Table::new_default_9();
+        let empty = t.snapshot();
+        t.set_givens_from_line(&format!("5{}", ".".repeat(80)))
+            .unwrap();
+
+        // given을 지우는 snapshot은 복원하지 않음
+        let given = t.snapshot();
+        assert_eq!(
+            t.restore(&empty),
+            Err(SudokuError::GivenCell { x: 0, y: 0 })
+        );
+        assert_eq!(t.snapshot(), given);
+
+        let mut write = t.write_lock();
+        assert_eq!(
+            write.try_write_from_cell(&t[0]).err(),
+            Some(SudokuError::GivenCell { x: 0, y: 0 })
+        );
+        assert!(write.try_write_from_cell(&t[1]).is_ok());
+    }
+}
@@ -0,0 +1,259 @@
+use super::{cell::Cell, index_key_map::IndexKeyMap, table_lock::TableLock, zone::Zone};
+use std::slice;
+
+/// 테이블의 구조 (zone에 속한 cell, cell마다의 peer)를 cell의 순서로 저장해 둔 것입니다.
+///
+/// 구조는 테이블을 만든 뒤 바뀌지 않으므로 처음 조회할 때 한 번만 계산합니다.
+pub(crate) struct TableStructure {
+    /// Zone과 Zone에 속한 cell의 순서. 순서는 오름차순
+    zone: IndexKeyMap<Zone, Box<[usize]>>,
+    /// cell마다 값이 중복될 수 없는 zone (Unique zone, killer cage)을 하나 이상 공유하는 다른 cell의 순서. 순서는 오름차순이며 자기 자신은 포함하지 않음
+    peers: Box<[Box<[usize]>]>,
+}
+
+impl TableStructure {
+    pub(crate) fn new<const N: usize>(cells: &[Cell<N>]) -> Self {
+        let mut zone: IndexKeyMap<Zone, Vec<usize>> = IndexKeyMap::with_capacity(N * 3 + 1);
+        for cell in cells {
+            for z in &cell.zone_vec {
+                zone.entry_or_insert_with(*z, || Vec::with_capacity(N))
+                    .push(cell.index);
+            }
+        }
+
+        let peers = cells
+            .iter()
+            .map(|cell| {
+                let mut peers: Vec<usize> = cell
+                    .zone_vec
+                    .iter()
+                    .filter(|z| z.get_zone_type().is_distinct())
+                    .flat_map(|z| zone[z].iter().copied())
+                    .filter(|&index| index != cell.index)
+                    .collect();
+                peers.sort_unstable();
+                peers.dedup();
+                peers.into_boxed_slice()
+            })
+            .collect();
+
+        let mut ret = IndexKeyMap::with_capacity(zone.len());
+        for (z, indexes) in &zone {
+            ret.insert_new(*z, indexes.clone().into_boxed_slice());
+        }
+
+        Self { zone: ret, peers }
+    }
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 테이블의 모든 zone을 번호 순서로 반환합니다.
+    pub fn zones(&self) -> impl Iterator<Item = Zone> + '_ {
+        self.structure().zone.iter().map(|(z, _)| *z)
+    }
+
+    /// 번호가 z인 zone을 반환합니다. 테이블에 없는 번호이면 None
+    #[must_use]
+    pub fn get_zone(&self, z: u16) -> Option<Zone> {
+        self.structure()
+            .zone
+            .get_key_value(&Zone::new_unique_from_u16(z))
+            .map(|(zone, _)| *zone)
+    }
+
+    /// zone에 속한 cell을 순서대로 반환합니다. 테이블에 없는 zone이면 비어있습니다.
+    #[must_use]
+    pub fn zone_cells(&self, zone: Zone) -> CellRefIter<'_, N> {
+        self.cell_ref_iter(self.zone_indexes(zone))
+    }
+
+    /// cell이 속한 zone을 반환합니다. cell이 테이블에 속하지 않은 경우 panic
+    #[must_use]
+    pub fn zones_of<'a>(&self, cell: &'a Cell<N>) -> &'a [Zone] {
+        assert!(self.contains_cell(cell), "테이블에 속하지 않은 cell");
+        cell.get_zone()
+    }
+
+    /// cell과 값이 중복될 수 없는 zone (Unique zone, killer cage)을 하나 이상 공유하는 다른 cell (peer)을 순서대로 반환합니다.
+    /// cell이 테이블에 속하지 않은 경우 panic
+    #[must_use]
+    pub fn peers(&self, cell: &Cell<N>) -> CellRefIter<'_, N> {
+        self.cell_ref_iter(self.peer_indexes(cell))
+    }
+
+    /// 두 cell이 서로 peer인지 여부. cell이 테이블에 속하지 않은 경우 panic
+    #[must_use]
+    pub fn is_peer(&self, a: &Cell<N>, b: &Cell<N>) -> bool {
+        assert!(self.contains_cell(b), "테이블에 속하지 않은 cell");
+        self.peer_indexes(a).binary_search(&b.index).is_ok()
+    }
+
+    /// 두 zone에 모두 속한 cell을 순서대로 반환합니다.
+    #[must_use]
+    pub fn zone_intersection(&self, a: Zone, b: Zone) -> Vec<&Cell<N>> {
+        self.intersect(self.zone_indexes(a), self.zone_indexes(b))
+    }
+
+    /// 두 cell 모두의 peer인 cell을 순서대로 반환합니다. a, b 자신은 포함하지 않습니다.
+    /// cell이 테이블에 속하지 않은 경우 panic
+    #[must_use]
+    pub fn common_peers(&self, a: &Cell<N>, b: &Cell<N>) -> Vec<&Cell<N>> {
+        self.intersect(self.peer_indexes(a), self.peer_indexes(b))
+            .into_iter()
+            .filter(|c| c.index != a.index && c.index != b.index)
+            .collect()
+    }
+
+    /// IndexKeyMap은 zone 번호로만 찾으므로 ZoneType까지 같은지 한 번 더 확인합니다.
+    fn zone_indexes(&self, zone: Zone) -> &[usize] {
+        match self.structure().zone.get_key_value(&zone) {
+            Some((z, indexes)) if *z == zone => indexes,
+            _ => &[],
+        }
+    }
+
+    fn peer_indexes(&self, cell: &Cell<N>) -> &[usize] {
+        assert!(self.contains_cell(cell), "테이블에 속하지 않은 cell");
+        &self.structure().peers[cell.index]
+    }
+
+    /// 오름차순으로 정렬된 두 순서 목록에 모두 있는 cell
+    fn intersect(&self, a: &[usize], b: &[usize]) -> Vec<&Cell<N>> {
+        let mut ret = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    ret.push(&self[a[i]]);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        ret
+    }
+
+    fn cell_ref_iter<'a>(&'a self, indexes: &'a [usize]) -> CellRefIter<'a, N> {
+        CellRefIter {
+            t: self,
+            indexes: indexes.iter(),
+        }
+    }
+}
+
+/// 순서 목록의 cell을 차례로 반환합니다.
+pub struct CellRefIter<'a, const N: usize> {
+    t: &'a [Cell<N>],
+    indexes: slice::Iter<'a, usize>,
+}
+
+impl<'a, const N: usize> Iterator for CellRefIter<'a, N> {
+    type Item = &'a Cell<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indexes.next().map(|&index| &self.t[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indexes.size_hint()
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for CellRefIter<'_, N> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{max_num::MaxNum, table::Table, table_builder::TableBuilder, zone::Zone};
+
+    #[test]
+    fn structure_query() {
+        let t = Table::<9>::new_default_9();
+        let cell = |x, y| t.get_cell_from_coordinate(MaxNum::new(x), MaxNum::new(y));
+
+        // 9X9 기본 구조에서 peer는 가로줄 8 + 세로줄 8 + 박스의 나머지 4 = 20개
+        assert!(t.iter().all(|c| t.peers(c).len() == 20));
+        assert_eq!(t.zones().count(), 27);
+        assert_eq!(t.get_zone(28), None);
+
+        let box_1 = t.get_zone(1).unwrap();
+        let row_1 = t.get_zone(19).unwrap();
+        assert_eq!(t.zone_cells(box_1).len(), 9);
+        assert_eq!(t.zone_cells(Zone::new_sum_from_u16(1, 45)).len(), 0);
+        assert_eq!(t.zones_of(cell(4, 4)).len(), 3);
+
+        let intersection: Vec<_> = t
+            .zone_intersection(box_1, row_1)
+            .into_iter()
+            .map(|c| c.get_coordinate())
+            .collect();
+        assert_eq!(intersection, [(0, 0), (1, 0), (2, 0)]);
+
+        assert!(t.is_peer(cell(0, 0), cell(8, 0)));
+        assert!(!t.is_peer(cell(0, 0), cell(4, 4)));
+        assert!(!t.is_peer(cell(0, 0), cell(0, 0)));
+
+        // 같은 가로줄의 두 cell: 가로줄의 나머지 7개
+        assert_eq!(t.common_peers(cell(0, 0), cell(8, 0)).len(), 7);
+        // 서로 다른 박스, 가로줄, 세로줄의 두 cell: 교차하는 두 칸
+        let common: Vec<_> = t
+            .common_peers(cell(0, 0), cell(4, 4))
+            .into_iter()
+            .map(|c| c.get_coordinate())
+            .collect();
+        assert_eq!(common, [(4, 0), (0, 4)]);
+    }
+
+    #[test]
+    fn killer_cage_peers() {
+        let t = TableBuilder::<9>::new()
+            .standard_boxes(3, 3)
+            .cage("cage", [(2, 2), (3, 2), (3, 3)], 10)
+            .sum("sum", [(0, 0), (4, 4)], 10)
+            .build()
+            .unwrap();
+        let cell = |x, y| t.get_cell_from_coordinate(MaxNum::new(x), MaxNum::new(y));
+
+        // 서로 다른 박스, 가로줄, 세로줄에 있는 (2, 2)와 (3, 3)은 killer cage로만 peer가 됨
+        assert!(t.is_peer(cell(2, 2), cell(3, 3)));
+        assert!(t.is_peer(cell(3, 3), cell(2, 2)));
+        assert_eq!(t.peers(cell(2, 2)).len(), 21);
+        assert_eq!(t.peers(cell(3, 3)).len(), 21);
+        // (3, 2)는 두 cell과 이미 가로줄, 세로줄을 공유함
+        assert_eq!(t.peers(cell(3, 2)).len(), 20);
+
+        // 값이 같아도 되는 Sum zone은 peer를 만들지 않음
+        assert!(!t.is_peer(cell(0, 0), cell(4, 4)));
+        assert_eq!(t.peers(cell(0, 0)).len(), 20);
+        assert_eq!(t.peers(cell(4, 4)).len(), 20);
+    }
+
+    #[test]
+    fn samurai_overlap_peers() {
+        let t = Table::new_samurai();
+        let cell = |x, y| t.get_cell_from_canvas(x, y).unwrap();
+
+        // 격자 하나에만 속한 cell은 9X9와 같이 20개
+        assert_eq!(t.peers(cell(0, 0)).len(), 20);
+        assert_eq!(t.peers(cell(10, 10)).len(), 20);
+
+        // 왼쪽 위 격자와 가운데 격자가 겹치는 박스의 cell은 두 격자의 가로줄, 세로줄에 모두 속함
+        // 가로줄 (0..=14, 7)의 14개 + 세로줄 (7, 0..=14)의 14개 + 박스의 나머지 4개
+        let shared = cell(7, 7);
+        assert_eq!(t.peers(shared).len(), 32);
+        assert!(t.is_peer(shared, cell(0, 7)));
+        assert!(t.is_peer(shared, cell(14, 7)));
+        assert!(t.is_peer(shared, cell(7, 14)));
+        assert!(!t.is_peer(shared, cell(8, 14)));
+        assert!(!t.is_peer(shared, cell(15, 7)));
+
+        // 두 격자의 가로줄 끝에 있는 두 cell은 겹치는 부분의 가로줄만 함께 봄
+        let common: Vec<_> = t
+            .common_peers(cell(0, 7), cell(14, 7))
+            .into_iter()
+            .map(|c| c.get_coordinate())
+            .collect();
+        assert_eq!(common, [(6, 7), (7, 7), (8, 7)]);
+    }
+}
@@ -1,15 +1,28 @@
-use crate::model::index_key_map::IndexKey;
-use std::fmt::Debug;
+use crate::{error::SudokuError, model::index_key_map::IndexKey};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    str::FromStr,
+};
 
-#[derive(PartialOrd, Ord, Debug, Clone, Copy)]
+#[derive(PartialOrd, Ord, Debug, Clone, Copy, Default)]
 pub enum ZoneType {
+    /// N개의 cell에 1부터 N까지의 값이 하나씩 들어감
+    #[default]
     Unique,
-    Sum { sum: usize },
+    /// 속한 cell들의 합이 sum이 되어야 함. distinct인 경우 (killer cage) 같은 값이 두 번 이상 들어갈 수 없음
+    Sum { sum: usize, distinct: bool },
 }
 
-impl Default for ZoneType {
-    fn default() -> Self {
-        Self::Unique
+impl ZoneType {
+    /// 속한 cell들이 서로 다른 값을 가져야 하는지 여부. Unique와 distinct인 Sum이 해당합니다.
+    #[must_use]
+    #[inline]
+    pub const fn is_distinct(self) -> bool {
+        match self {
+            ZoneType::Unique => true,
+            ZoneType::Sum { distinct, .. } => distinct,
+        }
     }
 }
 
@@ -21,11 +34,103 @@ impl PartialEq for ZoneType {
 
 impl Eq for ZoneType {}
 
+/// 사람이 읽을 수 있도록 zone이 무엇인지 나타냅니다. 번호는 같은 종류 안에서의 번호이며 1부터 시작합니다.
+///
+/// 풀이에는 영향을 주지 않으며, Zone의 비교에도 사용하지 않습니다.
+/// 표기는 "Row 3", "Column 7", "Box 5", "Cage 12", "Diagonal 1" 이며, 대소문자를 구분하지 않고 읽을 수 있습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum ZoneKind {
+    /// 가로줄. 번호는 캔버스의 행 번호입니다.
+    Row(u16),
+    /// 세로줄. 번호는 캔버스의 열 번호입니다.
+    Column(u16),
+    /// 박스나 직소 모양
+    Box(u16),
+    /// killer cage
+    Cage(u16),
+    Diagonal(u16),
+    /// 종류를 알 수 없는 zone. zone 번호로 표기합니다.
+    #[default]
+    Other,
+}
+
+impl ZoneKind {
+    const NAMES: [&'static str; 5] = ["Row", "Column", "Box", "Cage", "Diagonal"];
+
+    fn name_and_num(self) -> Option<(&'static str, u16)> {
+        let (index, num) = match self {
+            ZoneKind::Row(num) => (0, num),
+            ZoneKind::Column(num) => (1, num),
+            ZoneKind::Box(num) => (2, num),
+            ZoneKind::Cage(num) => (3, num),
+            ZoneKind::Diagonal(num) => (4, num),
+            ZoneKind::Other => return None,
+        };
+        Some((Self::NAMES[index], num))
+    }
+}
+
+impl Display for ZoneKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.name_and_num() {
+            Some((name, num)) => write!(f, "{name} {num}"),
+            None => write!(f, "Other"),
+        }
+    }
+}
+
+impl FromStr for ZoneKind {
+    type Err = SudokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || SudokuError::InvalidZoneKind {
+            kind: s.to_string(),
+        };
+
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("other") {
+            return Ok(ZoneKind::Other);
+        }
+
+        let (name, num) = s.split_once(' ').ok_or_else(err)?;
+        let num: u16 = num.trim().parse().map_err(|_| err())?;
+        let kinds = [
+            ZoneKind::Row,
+            ZoneKind::Column,
+            ZoneKind::Box,
+            ZoneKind::Cage,
+            ZoneKind::Diagonal,
+        ];
+        Self::NAMES
+            .iter()
+            .zip(kinds)
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, kind)| kind(num))
+            .ok_or_else(err)
+    }
+}
+
+impl From<ZoneKind> for String {
+    fn from(kind: ZoneKind) -> Self {
+        kind.to_string()
+    }
+}
+
+impl TryFrom<String> for ZoneKind {
+    type Error = SudokuError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 impl Debug for Zone {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Zone")
             .field("z", &self.z)
             .field("zone_type", &self.zone_type)
+            .field("kind", &self.kind)
             .finish()
     }
 }
@@ -34,6 +139,7 @@ impl Debug for Zone {
 pub struct Zone {
     z: u16,
     zone_type: ZoneType,
+    kind: ZoneKind,
 }
 
 impl Zone {
@@ -41,6 +147,31 @@ impl Zone {
         Zone {
             z,
             zone_type: ZoneType::Unique,
+            kind: ZoneKind::Other,
+        }
+    }
+
+    /// 속한 cell들의 합이 sum이 되어야 하는 Zone입니다. 같은 값이 여러 번 들어갈 수 있습니다.
+    pub const fn new_sum_from_u16(z: u16, sum: usize) -> Zone {
+        Zone {
+            z,
+            zone_type: ZoneType::Sum {
+                sum,
+                distinct: false,
+            },
+            kind: ZoneKind::Other,
+        }
+    }
+
+    /// killer cage 입니다. 속한 cell들의 합이 sum이 되어야 하며, 같은 값이 두 번 이상 들어갈 수 없습니다.
+    pub const fn new_cage_from_u16(z: u16, sum: usize) -> Zone {
+        Zone {
+            z,
+            zone_type: ZoneType::Sum {
+                sum,
+                distinct: true,
+            },
+            kind: ZoneKind::Other,
         }
     }
 
@@ -48,6 +179,7 @@ impl Zone {
         Zone {
             z: z.try_into().expect("can not convert from usize to u16"),
             zone_type: ZoneType::Unique,
+            kind: ZoneKind::Other,
         }
     }
 
@@ -62,6 +194,29 @@ impl Zone {
     pub fn get_zone_type(&self) -> ZoneType {
         self.zone_type
     }
+
+    /// 종류를 지정한 zone을 반환합니다. 종류는 표기에만 사용됩니다.
+    #[must_use]
+    pub const fn with_kind(mut self, kind: ZoneKind) -> Zone {
+        self.kind = kind;
+        self
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn kind(&self) -> ZoneKind {
+        self.kind
+    }
+}
+
+/// "Row 3" 과 같은 zone의 표기입니다. 종류를 알 수 없는 경우 "Zone 28" 과 같이 zone 번호로 표기합니다.
+impl Display for Zone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ZoneKind::Other => write!(f, "Zone {}", self.z),
+            kind => Display::fmt(&kind, f),
+        }
+    }
 }
 
 impl PartialEq for Zone {
@@ -96,3 +251,32 @@ impl IndexKey for Zone {
         self.get_zone_num()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{cell_pos::CellPos, table::Table};
+
+    #[test]
+    fn zone_kind_and_cell_notation() {
+        assert_eq!("row 3".parse::<ZoneKind>(), Ok(ZoneKind::Row(3)));
+        assert_eq!(ZoneKind::Cage(12).to_string(), "Cage 12");
+        assert!("Ring 1".parse::<ZoneKind>().is_err());
+        assert_eq!(Zone::new_unique_from_u16(28).to_string(), "Zone 28");
+
+        let t = Table::new_default_9();
+        let cell = t.get_cell_from_notation("R3C7").unwrap();
+        assert_eq!(cell.get_coordinate(), (6, 2));
+        assert_eq!(cell.to_string(), "r3c7");
+        let labels: Vec<String> = cell.get_zone().iter().map(Zone::to_string).collect();
+        assert_eq!(labels, ["Box 3", "Column 7", "Row 3"]);
+        assert!(t.get_cell_from_notation("r10c1").is_none());
+        assert!("r0c1".parse::<CellPos>().is_err());
+
+        // 여러 격자가 겹친 경우 가로줄, 세로줄은 캔버스의 행, 열 번호를 가짐
+        let samurai = Table::new_samurai();
+        let center = samurai.get_cell_from_notation("r11c11").unwrap();
+        let labels: Vec<String> = center.get_zone().iter().map(Zone::to_string).collect();
+        assert_eq!(labels, ["Box 21", "Column 11", "Row 11"]);
+    }
+}
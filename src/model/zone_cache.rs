@@ -5,7 +5,7 @@ use super::{
     table_lock::TableLock,
     zone::{Zone, ZoneType},
 };
-use crate::solver::solver_simple::SolverSimple;
+use crate::{error::SudokuError, io::ParseError, solver::solver_simple::SolverSimple};
 use enum_iterator::cardinality;
 use rayon::slice::ParallelSliceMut;
 
@@ -23,7 +23,13 @@ pub struct ZoneCache<'a, const N: usize> {
 }
 
 impl<'a, const N: usize> ZoneCache<'a, N> {
+    /// Unique zone에 속한 cell의 개수가 N이 아닌 경우 panic
     pub fn new(t: &'a TableLock<N>) -> Self {
+        Self::try_new(t).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Unique zone에 속한 cell의 개수가 N이 아닌 경우 오류를 반환합니다.
+    pub fn try_new(t: &'a TableLock<N>) -> Result<Self, SudokuError> {
         let zone = ZoneCache::get_zone_map(t);
         let zone_cnt = zone.len();
 
@@ -32,12 +38,14 @@ impl<'a, const N: usize> ZoneCache<'a, N> {
                 continue;
             };
 
-            assert_eq!(
-                N,
-                c.len(),
-                "Unique 타입의 개수는 퍼즐 사이즈와 동일해야 함. zone: {}",
-                z.get_zone_num()
-            );
+            if c.len() != N {
+                return Err(ParseError::InvalidZoneSize {
+                    zone: z.get_zone_num(),
+                    expected: N,
+                    found: c.len(),
+                }
+                .into());
+            }
         }
 
         let connect_zone = ZoneCache::<N>::get_connected_zone(&zone);
@@ -60,32 +68,31 @@ impl<'a, const N: usize> ZoneCache<'a, N> {
         for (z, _) in &zone {
             last_changed_list.insert_new(*z, Vec::with_capacity(N));
         }
-        let mut last_changed_flag = vec![0usize; N * N];
+        let mut last_changed_flag = vec![0usize; t.len()];
         let read = t.read_lock();
 
         for c in t {
             if read.read_from_cell(c).true_cnt() != N {
                 for z in c.get_zone() {
                     last_changed_list[z].push(c);
-                    last_changed_flag[c.y.get_value() * N + c.x.get_value()] += 1;
+                    last_changed_flag[c.index] += 1;
                 }
             }
         }
-        ZoneCache {
+        Ok(ZoneCache {
             zone,
             connect_zone,
             checked_zone,
             last_changed_list,
             last_changed_flag,
-        }
+        })
     }
 
     pub fn last_changed_list_clear(&mut self) {
         for (z, chk) in &self.checked_zone {
             if !self.last_changed_list[z].is_empty() && chk.iter().all(|(_, b)| b.get()) {
                 for &c in &self.last_changed_list[z] {
-                    let index = c.y.get_value() * N + c.x.get_value();
-                    self.last_changed_flag[index] -= 1;
+                    self.last_changed_flag[c.index] -= 1;
                 }
                 self.last_changed_list[z].clear();
             }
@@ -96,7 +103,7 @@ impl<'a, const N: usize> ZoneCache<'a, N> {
     }
 
     pub fn push_last_changed_cell(&mut self, c: &'a Cell<N>) {
-        let index = c.y.get_value() * N + c.x.get_value();
+        let index = c.index;
 
         if self.last_changed_flag[index] > 0 {
             return;
@@ -113,11 +120,11 @@ impl<'a, const N: usize> ZoneCache<'a, N> {
 
     #[cfg(debug_assertions)]
     fn validate_last_changed(&self) {
-        let mut last_changed_comp = vec![0usize; N * N];
+        let mut last_changed_comp = vec![0usize; self.last_changed_flag.len()];
         for (z, c_list) in &self.last_changed_list {
             for &c in c_list {
                 assert!(c.zone_set.contains(z));
-                last_changed_comp[c.y.get_value() * N + c.x.get_value()] += 1;
+                last_changed_comp[c.index] += 1;
             }
         }
         assert_eq!(last_changed_comp, self.last_changed_flag);
@@ -2,9 +2,26 @@ use crate::model::{
     array_note::ArrayNote,
     array_vector::{ArrayVector, IntoIterArrayVector},
     max_num::MaxNum,
+    note_bits::NoteBits,
 };
+use serde::{Deserialize, Serialize};
 use std::ops::Deref;
 
+/// cell의 값이 어디에서 왔는지를 나타냅니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CellStatus {
+    /// 값이 확정되지 않음
+    #[default]
+    Open,
+    /// 문제에 주어진 값
+    Given,
+    /// solver가 찾은 값
+    Solved,
+    /// 사용자가 입력한 값
+    UserEntered,
+}
+
 #[derive(Debug)]
 pub struct NumCheck<const N: usize> {
     chk_list: ArrayNote<Option<usize>, N>,
@@ -15,7 +32,9 @@ pub struct NumCheck<const N: usize> {
     /// 완성된 스도쿠에 구멍을 뚫는 경우, 본래 정답을 fixed_final_num에 저장해놓음.
     fixed_final_num: Option<MaxNum<N>>,
     // 비트 연산을 위한 변수. 참인 경우 1, 거짓인 경우 0
-    bit_flag: u64,
+    bit_flag: NoteBits<N>,
+    /// Given, UserEntered만 저장합니다. 노트를 바꾸는 함수는 이 값을 바꾸지 않습니다.
+    origin: CellStatus,
 }
 
 impl<const N: usize> NumCheck<N> {
@@ -37,7 +56,8 @@ impl<const N: usize> NumCheck<N> {
             true_cnt: N,
             final_num: None,
             fixed_final_num: None,
-            bit_flag: u64::MAX,
+            bit_flag: NoteBits::FULL,
+            origin: CellStatus::Open,
         }
     }
 
@@ -52,14 +72,20 @@ impl<const N: usize> NumCheck<N> {
             true_cnt: 0,
             final_num: None,
             fixed_final_num: None,
-            bit_flag: u64::MIN,
+            bit_flag: NoteBits::EMPTY,
+            origin: CellStatus::Open,
         }
     }
 
     #[inline]
     const fn assert_size() {
         const { assert!(N > 1, "스도쿠의 크기는 최소 2이상이어야 합니다.") };
-        const { assert!(N <= 64, "스도쿠의 크기는 64를 초과할 수 없습니다.") };
+        const {
+            assert!(
+                N <= NoteBits::<N>::CAPACITY,
+                "스도쿠의 크기는 128을 초과할 수 없습니다."
+            )
+        };
     }
 
     /// num의 노트값이 true인지를 반환합니다.
@@ -100,6 +126,7 @@ impl<const N: usize> NumCheck<N> {
         if self.chk_list[num].is_some() {
             return;
         }
+        self.debug_assert_given_kept(false);
 
         self.true_cnt += 1;
         unsafe {
@@ -107,7 +134,7 @@ impl<const N: usize> NumCheck<N> {
             self.true_list.push_unchecked(num);
         }
         self.chk_list[num] = Some(self.true_list.len() - 1);
-        self.bit_flag |= 1 << num.get_value();
+        self.bit_flag.insert(num);
 
         self.set_to_final_num();
     }
@@ -117,6 +144,7 @@ impl<const N: usize> NumCheck<N> {
         let Some(remove_index) = self.chk_list[num] else {
             return;
         };
+        self.debug_assert_given_kept(false);
 
         self.true_cnt -= 1;
         unsafe {
@@ -126,17 +154,20 @@ impl<const N: usize> NumCheck<N> {
             self.chk_list[*swap_node] = Some(remove_index);
         }
         self.chk_list[num] = None;
-        self.bit_flag &= !(1 << num.get_value());
+        self.bit_flag.remove(num);
 
         self.set_to_final_num();
     }
 
     /// chk_list에 포함된 노트만 true이며 그 외엔 false입니다.
     pub fn set_to_chk_list(&mut self, chk_list: &[MaxNum<N>]) {
+        self.debug_assert_given_kept(
+            !chk_list.is_empty() && chk_list.iter().all(|&n| Some(n) == self.final_num),
+        );
         self.true_cnt = 0;
         self.chk_list = ArrayNote::new([None; N]);
         self.true_list.clear();
-        self.bit_flag = u64::MIN;
+        self.bit_flag = NoteBits::EMPTY;
 
         for &n in chk_list {
             if self.chk_list[n].is_some() {
@@ -149,7 +180,7 @@ impl<const N: usize> NumCheck<N> {
                 self.true_list.push_unchecked(n);
             }
             self.true_cnt += 1;
-            self.bit_flag |= 1 << n.get_value();
+            self.bit_flag.insert(n);
         }
 
         self.set_to_final_num();
@@ -158,29 +189,41 @@ impl<const N: usize> NumCheck<N> {
     // 모든 노트를 false로 설정합니다.
     #[inline]
     pub fn set_all_false(&mut self) {
+        self.debug_assert_given_kept(false);
         self.true_cnt = 0;
         self.chk_list.set([None; N]);
         self.true_list.clear();
-        self.bit_flag = u64::MIN;
+        self.bit_flag = NoteBits::EMPTY;
         self.final_num = None;
     }
 
     /// 하나의 값으로 이 노트를 확정합니다.
     pub fn set_to_value(&mut self, value: MaxNum<N>) {
+        self.debug_assert_given_kept(self.final_num == Some(value));
         self.chk_list.set([None; N]);
         self.chk_list[value] = Some(0);
 
         self.true_list.clear();
         self.true_list.push(value);
 
-        self.bit_flag = u64::MIN;
-        self.bit_flag |= 1 << value.get_value();
+        self.bit_flag = NoteBits::from(value);
 
         self.true_cnt = 1;
 
         self.final_num = Some(value);
     }
 
+    /// given인 cell의 값이 바뀌는 경우 디버그 빌드에서 panic합니다.
+    ///
+    /// given의 값을 바꾸려면 먼저 set_status로 출처를 지워야 합니다.
+    #[inline]
+    fn debug_assert_given_kept(&self, kept: bool) {
+        debug_assert!(
+            kept || self.origin != CellStatus::Given,
+            "given cell의 값은 바꿀 수 없음"
+        );
+    }
+
     /// 지정된 리스트의 값을 모두 false로 지정합니다.
     #[inline]
     pub fn set_to_false_list(&mut self, list: &[MaxNum<N>]) {
@@ -260,7 +303,7 @@ impl<const N: usize> NumCheck<N> {
 
     #[must_use]
     #[inline]
-    pub fn bit_flag(&self) -> u64 {
+    pub fn bit_flag(&self) -> NoteBits<N> {
         self.bit_flag
     }
 
@@ -282,7 +325,7 @@ impl<const N: usize> NumCheck<N> {
                 assert_eq!(self.true_list[index], n);
             }
 
-            if self.bit_flag & (1 << n.get_value()) != 0 {
+            if self.bit_flag.contains(n) {
                 assert!(self.chk_list[n].is_some());
             } else {
                 assert!(self.chk_list[n].is_none());
@@ -301,6 +344,37 @@ impl<const N: usize> NumCheck<N> {
     pub fn fixed_final_num_set_dup(&mut self) {
         self.fixed_final_num = self.final_num;
     }
+
+    /// 값의 출처를 반환합니다. 확정되지 않은 경우 Open, given이나 사용자 입력이 아닌 확정된 값은 Solved 입니다.
+    #[must_use]
+    #[inline]
+    pub fn status(&self) -> CellStatus {
+        match (self.final_num, self.origin) {
+            (None, _) => CellStatus::Open,
+            (Some(_), CellStatus::Open | CellStatus::Solved) => CellStatus::Solved,
+            (Some(_), origin) => origin,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_given(&self) -> bool {
+        self.status() == CellStatus::Given
+    }
+
+    /// 값의 출처를 지정합니다. Open과 Solved는 노트에 따라 정해지므로 출처를 지웁니다.
+    ///
+    /// rollback 등으로 노트가 바뀌어도 출처는 유지됩니다.
+    pub fn set_status(&mut self, status: CellStatus) {
+        self.origin = match status {
+            CellStatus::Open | CellStatus::Solved => CellStatus::Open,
+            origin => origin,
+        };
+    }
+
+    pub(crate) fn fixed_final_num_set(&mut self, fixed_final_num: Option<MaxNum<N>>) {
+        self.fixed_final_num = fixed_final_num;
+    }
 }
 
 impl<const N: usize> Default for NumCheck<N> {
@@ -347,7 +421,7 @@ mod tests {
         assert_eq!(num_check.true_cnt(), 0);
         assert!(!num_check.is_final_num());
         assert_eq!(num_check.get_minimum_chk(), None);
-        assert_eq!(num_check.bit_flag(), 0);
+        assert_eq!(num_check.bit_flag(), NoteBits::EMPTY);
         assert!(num_check.get_true_list().is_empty());
     }
 
@@ -362,6 +436,40 @@ mod tests {
         assert!(num_check.is_final_num());
         assert_eq!(num_check.final_num(), Some(value));
         assert_eq!(num_check.get_true_list(), &[value]);
-        assert_eq!(num_check.bit_flag(), 1 << value.get_value());
+        assert_eq!(num_check.bit_flag(), NoteBits::from(value));
+    }
+
+    #[test]
+    fn wide_bit_flag_over_64() {
+        let mut num_check = NumCheck::<100>::new_with_true();
+        assert_eq!(num_check.bit_flag().len(), 100);
+
+        let high = MaxNum::new(99);
+        num_check.set_to_chk_list(&[MaxNum::new(0), MaxNum::new(64), high]);
+        assert!(num_check.bit_flag().contains(high));
+        assert_eq!(
+            num_check.bit_flag().iter().collect::<Vec<_>>(),
+            [MaxNum::new(0), MaxNum::new(64), high]
+        );
+
+        num_check.set_false(MaxNum::new(64));
+        num_check.set_false(MaxNum::new(0));
+        assert_eq!(num_check.final_num(), Some(high));
+        num_check.validater();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "given cell의 값은 바꿀 수 없음")]
+    fn given_overwrite_panics() {
+        let mut num_check = NumCheck::<9>::new_with_true();
+        num_check.set_to_value(MaxNum::new(3));
+        num_check.set_status(CellStatus::Given);
+
+        // 같은 값으로 다시 확정하는 것은 허용됨
+        num_check.set_to_value(MaxNum::new(3));
+        num_check.set_to_chk_list(&[MaxNum::new(3)]);
+
+        num_check.set_false(MaxNum::new(3));
     }
 }
@@ -0,0 +1,559 @@
+use crate::{
+    io::{ParseError, json::TableDocument},
+    model::{
+        cell_pos::CellPos,
+        index_key_map::IndexKey,
+        max_num::MaxNum,
+        symbol_set::SymbolSet,
+        table_lock::TableLock,
+        zone::{Zone, ZoneType},
+        zone_cache::ZoneCache,
+    },
+    num_check::{CellStatus, NumCheck},
+    rating::{SolveStatus, solve_with_rating},
+    solver::{
+        Solver,
+        solver_history::{SolverResult, SolverResultDetail},
+        solver_simple::SolverSimple,
+    },
+};
+use std::{
+    fmt::{Display, Formatter, Write},
+    time::Duration,
+};
+
+/// 플레이어가 cell에 입력한 내용입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry<const N: usize> {
+    Empty,
+    Value(MaxNum<N>),
+    /// 오름차순으로 정렬된 pencilmark
+    Notes(Vec<MaxNum<N>>),
+}
+
+/// 되돌리기를 위한 입력 기록입니다.
+#[derive(Debug, Clone)]
+struct Move<const N: usize> {
+    index: usize,
+    before: Entry<N>,
+    after: Entry<N>,
+}
+
+/// 플레이 중 발생한 오류입니다. 좌표는 0부터 시작합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayError {
+    /// 퍼즐 범위를 벗어난 좌표
+    InvalidCell { x: usize, y: usize },
+    /// given은 변경할 수 없음
+    GivenCell { x: usize, y: usize },
+    /// 현재 입력이 규칙에 어긋나 다음 단계를 찾을 수 없음
+    Contradiction { x: usize, y: usize },
+    /// 퍼즐의 정답을 구하지 못함
+    NoSolution,
+}
+
+impl Display for PlayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayError::InvalidCell { x, y } => {
+                write!(f, "퍼즐 범위를 벗어난 칸 ({})", CellPos::new(*x, *y))
+            }
+            PlayError::GivenCell { x, y } => {
+                write!(
+                    f,
+                    "{}은(는) given이므로 변경할 수 없음",
+                    CellPos::new(*x, *y)
+                )
+            }
+            PlayError::Contradiction { x, y } => {
+                write!(f, "{} 주변의 입력이 규칙에 어긋남", CellPos::new(*x, *y))
+            }
+            PlayError::NoSolution => write!(f, "퍼즐의 정답을 구하지 못함"),
+        }
+    }
+}
+
+impl std::error::Error for PlayError {}
+
+/// 적용하지 않은 다음 풀이 단계입니다. 좌표는 (x, y)이며 0부터 시작합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hint<const N: usize> {
+    pub technique: SolverSimple,
+    /// 기법의 근거가 된 숫자
+    pub nums: Vec<MaxNum<N>>,
+    /// Naked인 경우 근거가 된 cell
+    pub found_cells: Vec<(usize, usize)>,
+    /// 기법을 찾은 zone. Box Line Reduction인 경우 숫자가 갇힌 zone과 노트를 지울 zone 순서입니다.
+    pub zones: Vec<Zone>,
+    /// 노트를 지울 수 있는 cell과 지울 숫자
+    pub eliminations: Vec<((usize, usize), Vec<MaxNum<N>>)>,
+}
+
+impl<const N: usize> Hint<N> {
+    fn from_result(result: &SolverResult<'_, N>) -> Self {
+        let (nums, found_cells, zones) = match &result.solver_type {
+            SolverResultDetail::Single { found_chk, zone } => {
+                (vec![*found_chk], Vec::new(), vec![*zone])
+            }
+            SolverResultDetail::BoxLineReduction {
+                found_chk,
+                zone,
+                target_zone,
+            } => (vec![*found_chk], Vec::new(), vec![*zone, *target_zone]),
+            SolverResultDetail::CageBound { zone }
+            | SolverResultDetail::CageCombination { zone } => (Vec::new(), Vec::new(), vec![*zone]),
+            SolverResultDetail::Naked {
+                found_chks,
+                found_cell,
+                zone,
+            } => {
+                let mut nums = found_chks.to_vec();
+                nums.sort();
+                let mut cells: Vec<(usize, usize)> =
+                    found_cell.iter().map(|c| c.get_coordinate()).collect();
+                cells.sort_by_key(|&(x, y)| (y, x));
+                (nums, cells, vec![*zone])
+            }
+        };
+
+        let mut eliminations: Vec<((usize, usize), Vec<MaxNum<N>>)> = result
+            .effect_cells
+            .iter()
+            .map(|(c, notes)| {
+                let mut notes = notes.to_vec();
+                notes.sort();
+                (c.get_coordinate(), notes)
+            })
+            .collect();
+        eliminations.sort_by_key(|&((x, y), _)| (y, x));
+        eliminations.dedup();
+
+        Hint {
+            technique: SolverSimple::convert_detail_to_simple(&result.solver_type),
+            nums,
+            found_cells,
+            zones,
+            eliminations,
+        }
+    }
+}
+
+impl<const N: usize> Hint<N> {
+    /// Display와 같으나, 숫자를 symbols의 기호로 표시합니다.
+    #[must_use]
+    pub fn to_string_with_symbols(&self, symbols: &SymbolSet<N>) -> String {
+        let mut ret = String::new();
+        let _ = self.write_with_symbols(&mut ret, symbols);
+        ret
+    }
+
+    fn write_with_symbols(&self, f: &mut impl Write, symbols: &SymbolSet<N>) -> std::fmt::Result {
+        let nums = join(self.nums.iter().map(|&n| symbols.get(n).to_string()));
+        let targets = join(
+            self.eliminations
+                .iter()
+                .map(|&(pos, _)| CellPos::from(pos).to_string()),
+        );
+        let zone = |index: usize| {
+            self.zones
+                .get(index)
+                .map_or_else(|| "zone".to_string(), Zone::to_string)
+        };
+
+        match self.technique {
+            SolverSimple::Single => write!(
+                f,
+                "Single: {}에서 확정된 숫자 {nums}을(를) 같은 zone의 {targets} 노트에서 지울 수 있음",
+                zone(0)
+            ),
+            SolverSimple::Naked => {
+                let cells = join(
+                    self.found_cells
+                        .iter()
+                        .map(|&pos| CellPos::from(pos).to_string()),
+                );
+                write!(
+                    f,
+                    "Naked: {}의 {cells}에는 {nums}만 들어갈 수 있으므로 {targets} 노트에서 지울 수 있음",
+                    zone(0)
+                )
+            }
+            SolverSimple::BoxLineReduction => write!(
+                f,
+                "Box Line Reduction: {}의 숫자 {nums}은(는) {}와(과) 겹치는 칸에만 들어갈 수 있으므로 {targets} 노트에서 지울 수 있음",
+                zone(0),
+                zone(1)
+            ),
+            SolverSimple::Cage => {
+                let sum = match self.zones.first().map(Zone::get_zone_type) {
+                    Some(ZoneType::Sum { sum, .. }) => sum.to_string(),
+                    _ => "?".to_string(),
+                };
+                write!(
+                    f,
+                    "Cage: {}의 합 {sum}을(를) 만들 수 있는 값만 남기면 {targets} 노트에서 지울 수 있음",
+                    zone(0)
+                )
+            }
+            SolverSimple::Validate => write!(f, "Validate"),
+        }
+    }
+}
+
+impl<const N: usize> Display for Hint<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_with_symbols(f, &SymbolSet::default())
+    }
+}
+
+/// 터미널 등에서 사람이 퍼즐을 푸는 과정을 관리합니다.
+///
+/// 플레이어가 입력한 숫자와 노트를 기록하고, 되돌리기/다시하기, 정답과의 비교, 힌트를 제공합니다.
+pub struct Play<const N: usize> {
+    /// given과 플레이어가 입력한 숫자가 채워진 테이블
+    table: TableLock<N>,
+    givens: Vec<Option<MaxNum<N>>>,
+    entries: Vec<Entry<N>>,
+    /// Solver로 구한 정답. 구하지 못한 경우 None
+    solution: Option<Vec<MaxNum<N>>>,
+    undo_stack: Vec<Move<N>>,
+    redo_stack: Vec<Move<N>>,
+}
+
+impl<const N: usize> Play<N> {
+    /// 테이블에서 확정된 값을 given으로 하여 플레이를 시작합니다. 나머지 cell은 비워집니다.
+    ///
+    /// 정답은 제한시간 안에 Solver로 미리 구해둡니다. given이 서로 충돌하면 오류를 반환합니다.
+    pub fn new(table: TableLock<N>, timeout: Duration) -> Result<Self, ParseError> {
+        let givens: Vec<Option<MaxNum<N>>> = {
+            let read = table.read_lock();
+            read.into_iter().map(|(_, chk)| chk.final_num()).collect()
+        };
+        table.set_givens(&givens)?;
+
+        let mut copy = table.clone();
+        let rating = solve_with_rating(&mut copy, None, timeout);
+        let solution = (rating.status == SolveStatus::Solved).then(|| {
+            let read = copy.read_lock();
+            read.into_iter()
+                .map(|(_, chk)| chk.final_num().expect("풀린 퍼즐"))
+                .collect()
+        });
+
+        Ok(Play {
+            table,
+            entries: vec![Entry::Empty; givens.len()],
+            givens,
+            solution,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        })
+    }
+
+    /// given과 플레이어가 입력한 숫자가 채워진 테이블
+    #[must_use]
+    pub fn get_table(&self) -> &TableLock<N> {
+        &self.table
+    }
+
+    #[must_use]
+    pub fn get_solution(&self) -> Option<&[MaxNum<N>]> {
+        self.solution.as_deref()
+    }
+
+    #[must_use]
+    pub fn given(&self, x: usize, y: usize) -> Option<MaxNum<N>> {
+        self.cell_index(x, y)
+            .ok()
+            .and_then(|index| self.givens[index])
+    }
+
+    #[must_use]
+    pub fn entry(&self, x: usize, y: usize) -> Option<&Entry<N>> {
+        self.cell_index(x, y).ok().map(|index| &self.entries[index])
+    }
+
+    /// cell에 숫자를 입력합니다. value가 None인 경우 cell을 비웁니다.
+    pub fn set_value(
+        &mut self,
+        x: usize,
+        y: usize,
+        value: Option<MaxNum<N>>,
+    ) -> Result<(), PlayError> {
+        let entry = match value {
+            Some(value) => Entry::Value(value),
+            None => Entry::Empty,
+        };
+        self.apply(x, y, entry)
+    }
+
+    /// cell의 노트에서 num을 넣거나 뺍니다. 숫자가 입력된 cell이었다면 숫자는 지워집니다.
+    pub fn toggle_note(&mut self, x: usize, y: usize, num: MaxNum<N>) -> Result<(), PlayError> {
+        let index = self.editable_index(x, y)?;
+        let mut notes = match &self.entries[index] {
+            Entry::Notes(notes) => notes.clone(),
+            Entry::Empty | Entry::Value(_) => Vec::new(),
+        };
+
+        match notes.binary_search(&num) {
+            Ok(pos) => {
+                notes.remove(pos);
+            }
+            Err(pos) => notes.insert(pos, num),
+        }
+
+        let entry = if notes.is_empty() {
+            Entry::Empty
+        } else {
+            Entry::Notes(notes)
+        };
+        self.apply(x, y, entry)
+    }
+
+    /// 마지막 입력을 되돌립니다. 되돌릴 입력이 없으면 false를 반환합니다.
+    pub fn undo(&mut self) -> bool {
+        let Some(mv) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.write_entry(mv.index, mv.before.clone());
+        self.redo_stack.push(mv);
+        true
+    }
+
+    /// 되돌린 입력을 다시 적용합니다. 다시 적용할 입력이 없으면 false를 반환합니다.
+    pub fn redo(&mut self) -> bool {
+        let Some(mv) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.write_entry(mv.index, mv.after.clone());
+        self.undo_stack.push(mv);
+        true
+    }
+
+    /// 정답과 다른 숫자, 또는 정답이 빠진 노트를 가진 cell의 좌표를 반환합니다.
+    pub fn mistakes(&self) -> Result<Vec<(usize, usize)>, PlayError> {
+        let solution = self.solution.as_ref().ok_or(PlayError::NoSolution)?;
+
+        Ok(self
+            .entries
+            .iter()
+            .zip(solution)
+            .enumerate()
+            .filter(|(_, (entry, answer))| match entry {
+                Entry::Empty => false,
+                Entry::Value(value) => value != *answer,
+                Entry::Notes(notes) => !notes.contains(answer),
+            })
+            .map(|(index, _)| self.table[index].get_coordinate())
+            .collect())
+    }
+
+    /// 모든 cell이 채워졌고 정답과 일치하는지 여부
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        let Some(solution) = &self.solution else {
+            return false;
+        };
+
+        self.givens
+            .iter()
+            .zip(&self.entries)
+            .zip(solution)
+            .all(|((given, entry), answer)| match (given, entry) {
+                (Some(given), _) => given == answer,
+                (None, Entry::Value(value)) => value == answer,
+                _ => false,
+            })
+    }
+
+    /// 현재 입력한 숫자와 노트로부터 적용할 수 있는 다음 풀이 단계를 찾습니다. 테이블에는 적용하지 않습니다.
+    ///
+    /// 노트를 입력하지 않은 빈 cell은 모든 숫자가 가능한 것으로 봅니다.
+    /// 더 쉬운 기법(Single, Naked, Box Line Reduction 순)의 결과를 우선합니다. 찾지 못한 경우 None을 반환합니다.
+    pub fn hint(&self) -> Result<Option<Hint<N>>, PlayError> {
+        let doc = TableDocument::from_table(&self.table);
+        let work = doc.to_table::<N>().map_err(|err| match err {
+            ParseError::ContradictoryGivens { x, y, .. } => PlayError::Contradiction { x, y },
+            _ => unreachable!("테이블로부터 만든 문서는 항상 올바름"),
+        })?;
+
+        {
+            let mut write = work.write_lock();
+            for ((_, chk), entry) in (&mut write).into_iter().zip(&self.entries) {
+                if let Entry::Notes(notes) = entry {
+                    chk.set_to_chk_list(notes);
+                }
+            }
+        }
+
+        let solver = Solver::new_with_cache(&work, ZoneCache::new(&work));
+        let steps = solver.find_next_steps().map_err(|cell| {
+            let (x, y) = cell.get_coordinate();
+            PlayError::Contradiction { x, y }
+        })?;
+
+        // 병렬로 찾으므로 결과의 순서가 매번 다를 수 있음. 기법과 위치 순으로 골라 항상 같은 힌트를 줌
+        Ok(steps
+            .iter()
+            .map(Hint::from_result)
+            .filter(|hint| !hint.eliminations.is_empty())
+            .min_by_key(|hint| {
+                (
+                    hint.technique.index(),
+                    hint.eliminations[0].0.1,
+                    hint.eliminations[0].0.0,
+                )
+            }))
+    }
+
+    /// 플레이어가 입력한 노트를 pencilmark 형식으로 출력합니다. 숫자가 정해진 cell은 그 숫자만 표시됩니다.
+    #[must_use]
+    pub fn note_fmt(&self) -> String {
+        let symbols = self.table.symbols();
+        // 여러 글자 기호는 pencilmark 형식과 같이 ','로 구분함
+        let (separator, width) = if symbols.is_single_char() {
+            ("", N + 1)
+        } else {
+            (",", N * (symbols.width() + 1) + 1)
+        };
+
+        let mut ret = String::new();
+        for (index, (given, entry)) in self.givens.iter().zip(&self.entries).enumerate() {
+            let cell = match (given, entry) {
+                (Some(num), _) | (None, Entry::Value(num)) => symbols.get(*num).to_string(),
+                (None, Entry::Notes(notes)) => notes
+                    .iter()
+                    .map(|&n| symbols.get(n))
+                    .collect::<Vec<_>>()
+                    .join(separator),
+                (None, Entry::Empty) => ".".to_string(),
+            };
+            let _ = write!(ret, "{cell:<width$}");
+            let (_, y) = self.table[index].get_coordinate();
+            if self
+                .table
+                .get(index + 1)
+                .is_none_or(|next| next.get_coordinate().1 != y)
+            {
+                ret.truncate(ret.trim_end().len());
+                ret.push('\n');
+            }
+        }
+        ret
+    }
+
+    fn cell_index(&self, x: usize, y: usize) -> Result<usize, PlayError> {
+        self.table
+            .get_cell_from_canvas(x, y)
+            .map(|cell| cell.index)
+            .ok_or(PlayError::InvalidCell { x, y })
+    }
+
+    fn editable_index(&self, x: usize, y: usize) -> Result<usize, PlayError> {
+        let index = self.cell_index(x, y)?;
+        if self.givens[index].is_some() {
+            return Err(PlayError::GivenCell { x, y });
+        }
+        Ok(index)
+    }
+
+    fn apply(&mut self, x: usize, y: usize, entry: Entry<N>) -> Result<(), PlayError> {
+        let index = self.editable_index(x, y)?;
+        if self.entries[index] == entry {
+            return Ok(());
+        }
+
+        let before = std::mem::replace(&mut self.entries[index], entry.clone());
+        self.undo_stack.push(Move {
+            index,
+            before,
+            after: entry.clone(),
+        });
+        self.redo_stack.clear();
+        self.write_entry(index, entry);
+        Ok(())
+    }
+
+    /// 입력을 기록하고 테이블에 숫자를 반영합니다. 노트는 테이블에 쓰지 않습니다.
+    fn write_entry(&mut self, index: usize, entry: Entry<N>) {
+        let cell = &self.table[index];
+        let mut write = self.table.write_lock();
+        let chk = write.write_from_cell(cell);
+        *chk = NumCheck::new_with_true();
+        if let Entry::Value(value) = entry {
+            chk.set_to_value(value);
+            chk.set_status(CellStatus::UserEntered);
+        }
+        self.entries[index] = entry;
+    }
+}
+
+impl<const N: usize> Display for Play<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table)
+    }
+}
+
+fn join(items: impl Iterator<Item = String>) -> String {
+    items.collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{table::Table, zone::ZoneKind};
+
+    #[test]
+    fn play_moves_and_hint() {
+        let table = Table::new_default_9_from_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        let mut play = Play::new(table, Duration::MAX).unwrap();
+        let n = |c: char| MaxNum::<9>::from_char(c).unwrap();
+
+        assert_eq!(
+            play.set_value(0, 0, Some(n('1'))),
+            Err(PlayError::GivenCell { x: 0, y: 0 })
+        );
+
+        // r1c3의 정답은 4
+        play.set_value(2, 0, Some(n('1'))).unwrap();
+        assert_eq!(play.mistakes().unwrap(), [(2, 0)]);
+        assert!(matches!(
+            play.hint(),
+            Err(PlayError::Contradiction { .. }) | Ok(Some(_))
+        ));
+
+        assert!(play.undo());
+        assert!(play.mistakes().unwrap().is_empty());
+        assert!(play.redo());
+        assert!(!play.redo());
+        assert!(play.undo());
+
+        play.toggle_note(2, 0, n('4')).unwrap();
+        play.toggle_note(2, 0, n('2')).unwrap();
+        assert_eq!(play.entry(2, 0), Some(&Entry::Notes(vec![n('2'), n('4')])));
+        play.toggle_note(2, 0, n('4')).unwrap();
+        assert_eq!(play.mistakes().unwrap(), [(2, 0)]);
+        assert!(play.undo());
+
+        // 힌트는 테이블에 적용되지 않음
+        let before = play.get_table().to_line();
+        let hint = play.hint().unwrap().unwrap();
+        assert_eq!(hint.technique, SolverSimple::Single);
+        assert!(!hint.eliminations.is_empty());
+        assert_ne!(hint.zones[0].kind(), ZoneKind::Other);
+        assert!(hint.to_string().contains(&hint.zones[0].to_string()));
+        assert_eq!(play.get_table().to_line(), before);
+
+        let solution = play.get_solution().unwrap().to_vec();
+        for (index, answer) in solution.into_iter().enumerate() {
+            if play.given(index % 9, index / 9).is_none() {
+                play.set_value(index % 9, index / 9, Some(answer)).unwrap();
+            }
+        }
+        assert!(play.is_solved());
+        assert_eq!(play.hint().unwrap(), None);
+    }
+}
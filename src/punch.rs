@@ -1,12 +1,14 @@
 pub mod naked_single;
 
 use crate::{
+    error::SudokuError,
     model::{
         cell::Cell,
+        max_num::MaxNum,
         table_lock::{TableLock, TableLockReadGuard},
         zone_cache::ZoneCache,
     },
-    num_check::NumCheck,
+    num_check::{CellStatus, NumCheck},
     solver::Solver,
 };
 use rand::{rngs::SmallRng, seq::IndexedRandom};
@@ -16,6 +18,8 @@ pub struct Punch<'a, const N: usize> {
     table: &'a TableLock<N>,
     rng: SmallRng,
     zone_cache: ZoneCache<'a, N>,
+    /// 구멍을 뚫기 전의 완성된 퍼즐. cell 순서와 같습니다.
+    solution: Vec<Option<MaxNum<N>>>,
 }
 
 impl<'a, const N: usize> Punch<'a, N> {
@@ -23,14 +27,20 @@ impl<'a, const N: usize> Punch<'a, N> {
         zone_cache.checked_zone_all_clear();
 
         let mut write = table.write_lock();
+        let mut solution = Vec::with_capacity(N * N);
         for (_, chk) in &mut write {
             chk.fixed_final_num_set_dup();
+            if chk.final_num().is_some() {
+                chk.set_status(CellStatus::Given);
+            }
+            solution.push(chk.final_num());
         }
 
         Punch {
             table,
             rng,
             zone_cache,
+            solution,
         }
     }
 
@@ -38,34 +48,48 @@ impl<'a, const N: usize> Punch<'a, N> {
         self.table
     }
 
+    /// 구멍을 뚫기 전의 완성된 퍼즐을 cell 순서대로 반환합니다.
+    pub fn get_solution(&self) -> &[Option<MaxNum<N>>] {
+        &self.solution
+    }
+
     pub fn punch_all(&mut self) {
         loop {
             let read = self.table.read_lock();
             let naked_single = Punch::naked_single_punch(&self.zone_cache, &read);
 
-            if naked_single.is_empty() {
+            let Some(&pick) = naked_single.choose(&mut self.rng) else {
                 break;
-            }
+            };
 
-            self.punch_cell_commit(read, &naked_single);
+            self.punch_cell_commit(read, pick);
         }
     }
 
+    /// punch_all과 같으나, 값이 확정되지 않은 cell이 있는 경우 테이블을 변경하지 않고 오류를 반환합니다.
+    pub fn try_punch_all(&mut self) -> Result<(), SudokuError> {
+        if let Some(index) = self.solution.iter().position(Option::is_none) {
+            let (x, y) = self.table[index].get_coordinate();
+            return Err(SudokuError::IncompleteTable { x, y });
+        }
+
+        self.punch_all();
+        Ok(())
+    }
+
     pub fn into_solver(self) -> Solver<'a, N> {
         self.zone_cache.checked_zone_all_clear();
         Solver::new_with_cache(self.table, self.zone_cache)
     }
 
     #[inline]
-    fn punch_cell_commit(&mut self, read: TableLockReadGuard<N>, cells: &[&Cell<N>]) {
-        let pick = cells.choose(&mut self.rng).expect("cells is empty");
-
+    fn punch_cell_commit(&mut self, read: TableLockReadGuard<N>, pick: &Cell<N>) {
         let mut effect_cell: Vec<&Cell<N>> = Vec::with_capacity(N * N);
         effect_cell.push(pick);
 
         for zone in &pick.zone_vec {
             for zone_cell in &self.zone_cache.zone()[zone] {
-                if zone_cell == pick {
+                if *zone_cell == pick {
                     continue;
                 }
 
@@ -92,12 +116,71 @@ impl<'a, const N: usize> Punch<'a, N> {
         }
 
         let mut write = read.upgrade_to_write();
-
         let pick_write = write.write_from_cell(pick);
         pick_write.fixed_final_num_set_none();
 
         for (cell, chk) in change_cell {
-            *write.write_from_cell(cell) = chk;
+            let cell_write = write.write_from_cell(cell);
+            let is_fixed = cell_write.fixed_final_num().is_some();
+            let status = cell_write.status();
+            *cell_write = chk;
+
+            // 노트만 다시 계산하고, 구멍을 뚫지 않은 cell의 given은 유지함
+            if is_fixed && cell != pick {
+                cell_write.fixed_final_num_set_dup();
+                cell_write.set_status(status);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::table::Table,
+        solver::{GeneralSolve, Solver},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn punch_all_leaves_sensible_clues() {
+        let mut t = Table::new_default_9();
+        let mut solver = Solver::new_with_seed(&mut t, 0);
+        solver.fill_puzzle_with_timeout(Duration::MAX);
+        let mut punch = solver.into_punch();
+        punch.punch_all();
+
+        let read = punch.get_table().read_lock();
+        let clues = read
+            .into_iter()
+            .filter(|(_, chk)| chk.fixed_final_num().is_some())
+            .count();
+        assert!((17..=55).contains(&clues), "clues: {clues}");
+
+        // 남은 given은 모두 완성된 퍼즐의 값과 같음
+        for ((_, chk), solution) in read.into_iter().zip(punch.get_solution()) {
+            if let Some(fixed) = chk.fixed_final_num() {
+                assert_eq!(Some(fixed), *solution);
+            }
+        }
+
+        // 남은 given만으로 해가 하나로 정해짐
+        let givens: Vec<_> = read
+            .into_iter()
+            .map(|(_, chk)| chk.fixed_final_num())
+            .collect();
+        let solution = punch.get_solution().to_vec();
+        drop(read);
+        for seed in 0..5 {
+            let mut t = Table::new_default_9();
+            t.set_givens(&givens).unwrap();
+            Solver::new_with_seed(&mut t, seed).fill_puzzle_with_timeout(Duration::MAX);
+            let filled: Vec<_> = t
+                .read_lock()
+                .into_iter()
+                .map(|(_, chk)| chk.final_num())
+                .collect();
+            assert_eq!(filled, solution);
         }
     }
 }
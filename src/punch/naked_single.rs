@@ -1,7 +1,13 @@
 use super::Punch;
-use crate::model::{cell::Cell, table_lock::TableLockReadGuard, zone_cache::ZoneCache};
+use crate::{
+    model::{cell::Cell, table_lock::TableLockReadGuard, zone_cache::ZoneCache},
+    num_check::NumCheck,
+};
 
 impl<'a, const N: usize> Punch<'a, N> {
+    /// 남아있는 given만으로 naked single이 되는 given cell을 반환합니다.
+    ///
+    /// 이 cell은 구멍을 뚫어도 나머지 given에서 값이 하나로 정해지므로 퍼즐의 해가 유일하게 유지됩니다.
     pub fn naked_single_punch(
         zone_cache: &ZoneCache<'a, N>,
         read: &TableLockReadGuard<'a, '_, N>,
@@ -13,26 +19,24 @@ impl<'a, const N: usize> Punch<'a, N> {
                 continue;
             }
 
-            'zone_iter: for zone in cell.get_zone() {
+            let mut cell_chk = NumCheck::<N>::new_with_true();
+            for zone in cell.get_zone() {
                 for &zone_in_cell in &zone_cache.zone()[zone] {
                     if cell == zone_in_cell {
                         continue;
                     }
 
-                    if read
-                        .read_from_cell(zone_in_cell)
-                        .fixed_final_num()
-                        .is_none()
-                    {
-                        continue 'zone_iter;
+                    if let Some(fixed) = read.read_from_cell(zone_in_cell).fixed_final_num() {
+                        cell_chk.set_false(fixed);
                     }
                 }
+            }
 
+            if cell_chk.is_final_num() {
                 if target_cell.capacity() == 0 {
                     target_cell.reserve_exact(N * N);
                 }
                 target_cell.push(cell);
-                break 'zone_iter;
             }
         }
 
@@ -0,0 +1,173 @@
+use crate::{
+    model::table_lock::TableLock,
+    solver::{GeneralSolve, Solver, solver_simple::SolverSimple},
+};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// 퍼즐을 푼 결과입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SolveStatus {
+    Solved,
+    /// 해가 없거나 given이 서로 충돌함
+    Unsolvable,
+    /// 제한시간 안에 풀지 못함
+    Timeout,
+}
+
+impl Display for SolveStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveStatus::Solved => write!(f, "solved"),
+            SolveStatus::Unsolvable => write!(f, "unsolvable"),
+            SolveStatus::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+/// 퍼즐을 푸는 데 필요했던 가장 어려운 기법으로 정한 난이도입니다.
+///
+/// Solver는 모든 기법을 동시에 적용하므로, 더 쉬운 기법으로도 풀 수 있는 퍼즐이 더 높게 평가될 수 있습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    Single,
+    Naked,
+    BoxLineReduction,
+    /// Sum zone, killer cage의 합을 이용함
+    Cage,
+    /// 구현된 기법만으로 풀 수 없어 Guess가 필요함
+    Guess,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Single => write!(f, "single"),
+            Difficulty::Naked => write!(f, "naked"),
+            Difficulty::BoxLineReduction => write!(f, "box-line-reduction"),
+            Difficulty::Cage => write!(f, "cage"),
+            Difficulty::Guess => write!(f, "guess"),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "single" => Ok(Difficulty::Single),
+            "naked" => Ok(Difficulty::Naked),
+            "box-line-reduction" | "blr" => Ok(Difficulty::BoxLineReduction),
+            "cage" => Ok(Difficulty::Cage),
+            "guess" => Ok(Difficulty::Guess),
+            _ => Err(format!("알 수 없는 난이도 '{s}'")),
+        }
+    }
+}
+
+/// Solver로 퍼즐을 푼 결과와 사용한 기법의 횟수입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rating {
+    pub status: SolveStatus,
+    pub single_cnt: u32,
+    pub naked_cnt: u32,
+    pub box_line_reduction_cnt: u32,
+    pub cage_cnt: u32,
+    pub guess_cnt: u32,
+    /// 풀이에 사용한 random seed
+    pub seed: u64,
+}
+
+impl Rating {
+    /// 풀이가 끝난 Solver의 기록으로부터 결과를 만듭니다.
+    #[must_use]
+    pub fn from_solver<const N: usize>(solver: &Solver<'_, N>, status: SolveStatus) -> Self {
+        Rating {
+            status,
+            single_cnt: solver.solve_cnt(SolverSimple::Single),
+            naked_cnt: solver.solve_cnt(SolverSimple::Naked),
+            box_line_reduction_cnt: solver.solve_cnt(SolverSimple::BoxLineReduction),
+            cage_cnt: solver.solve_cnt(SolverSimple::Cage),
+            guess_cnt: solver.guess_cnt(),
+            seed: solver.get_random_seed(),
+        }
+    }
+
+    #[must_use]
+    pub fn difficulty(&self) -> Difficulty {
+        if self.guess_cnt > 0 {
+            Difficulty::Guess
+        } else if self.cage_cnt > 0 {
+            Difficulty::Cage
+        } else if self.box_line_reduction_cnt > 0 {
+            Difficulty::BoxLineReduction
+        } else if self.naked_cnt > 0 {
+            Difficulty::Naked
+        } else {
+            Difficulty::Single
+        }
+    }
+}
+
+/// 제한시간 안에 테이블을 풀고 결과를 반환합니다. 테이블에는 풀이 결과가 남습니다.
+///
+/// seed가 None인 경우 무작위 seed를 사용합니다.
+pub fn solve_with_rating<const N: usize>(
+    table: &mut TableLock<N>,
+    seed: Option<u64>,
+    timeout: Duration,
+) -> Rating {
+    let start = Instant::now();
+    let mut solver = match seed {
+        Some(seed) => Solver::new_with_seed(table, seed),
+        None => Solver::new(table),
+    };
+
+    if solver.validater().is_some() {
+        return Rating::from_solver(&solver, SolveStatus::Unsolvable);
+    }
+
+    let status = if solver.fill_puzzle_with_timeout(timeout) == 0 {
+        if solver.validater().is_none() {
+            SolveStatus::Solved
+        } else {
+            SolveStatus::Unsolvable
+        }
+    } else if start.elapsed() >= timeout {
+        SolveStatus::Timeout
+    } else {
+        SolveStatus::Unsolvable
+    };
+
+    Rating::from_solver(&solver, status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::table::Table;
+
+    #[test]
+    fn rate_puzzle() {
+        let mut t = Table::new_default_9_from_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        let rating = solve_with_rating(&mut t, Some(0), Duration::MAX);
+        assert_eq!(rating.status, SolveStatus::Solved);
+        assert_eq!(rating.guess_cnt, 0);
+        assert!(rating.difficulty() < Difficulty::Guess);
+
+        // (2, 1)에는 4만 들어갈 수 있지만 같은 세로줄에 4가 있음
+        let mut t = Table::new_default_9_from_line(
+            "123......56.......789..........................4.................................",
+        )
+        .unwrap();
+        let rating = solve_with_rating(&mut t, Some(0), Duration::MAX);
+        assert_eq!(rating.status, SolveStatus::Unsolvable);
+    }
+}
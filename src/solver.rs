@@ -1,4 +1,5 @@
 pub mod box_line_reduction;
+pub mod cage;
 pub mod guess;
 pub mod naked;
 pub mod single;
@@ -8,6 +9,7 @@ pub mod validater;
 
 use self::solver_history::{SolverHistory, SolverHistoryType, SolverResult};
 use self::solver_simple::SolverSimple;
+use crate::error::SudokuError;
 use crate::model::array_vector::ArrayVector;
 use crate::model::index_key_map::{IndexKeyMap, IndexKeySet};
 use crate::model::max_num::MaxNum;
@@ -35,6 +37,15 @@ pub struct Solver<'a, const N: usize> {
     zone_cache: ZoneCache<'a, N>,
 }
 
+/// 운영체제의 난수 생성기에서 무작위 seed를 얻습니다. 실패한 경우 RandomSeed 오류를 반환합니다.
+pub fn random_seed() -> Result<u64, SudokuError> {
+    rand::rngs::OsRng
+        .try_next_u64()
+        .map_err(|err| SudokuError::RandomSeed {
+            message: err.to_string(),
+        })
+}
+
 pub trait GeneralSolve<'a> {
     /// 제한시간 내에 스도쿠를 모두 채우려고 시도합니다.
     ///
@@ -214,37 +225,43 @@ impl<'a, const N: usize> Solver<'a, N> {
     /// TableLock을 mut로 받을 필요는 없으나, 동일한 Table에 대해 여러 Solver를 생성하는 것을 방지하기 위해 일부러 mut로 받음
     #[must_use]
     pub fn new(t: &'a mut TableLock<N>) -> Self {
-        let rand_seed = rand::rngs::OsRng.try_next_u64().expect("OsRngInitFail");
-        Self::new_with_seed(t, rand_seed)
+        Self::try_new(t).unwrap_or_else(|err| panic!("{err}"))
     }
 
     #[must_use]
     pub fn new_with_seed(t: &'a mut TableLock<N>, rand_seed: u64) -> Self {
-        let mut solve_cnt: IndexKeyMap<SolverSimple, u32> = IndexKeyMap::new();
-        for n in all::<SolverSimple>() {
-            solve_cnt.insert(n, 0u32);
-        }
+        Self::try_new_with_seed(t, rand_seed).unwrap_or_else(|err| panic!("{err}"))
+    }
 
-        Solver {
-            table: t,
-            solver_history_stack: Vec::with_capacity(N * N * N),
-            rng: SmallRng::seed_from_u64(rand_seed),
-            rand_seed,
-            guess_cnt: 0,
-            guess_rollback_cnt: 0,
-            guess_backtrace_rollback_cnt: 0,
-            solve_cnt,
-            zone_cache: ZoneCache::new(t),
-        }
+    /// new와 같으나, 테이블의 구조가 잘못되었거나 무작위 seed를 얻지 못한 경우 오류를 반환합니다.
+    pub fn try_new(t: &'a mut TableLock<N>) -> Result<Self, SudokuError> {
+        Self::try_new_with_seed(t, random_seed()?)
+    }
+
+    /// new_with_seed와 같으나, 테이블의 구조가 잘못된 경우 오류를 반환합니다.
+    pub fn try_new_with_seed(t: &'a mut TableLock<N>, rand_seed: u64) -> Result<Self, SudokuError> {
+        let zone_cache = ZoneCache::try_new(t)?;
+        Ok(Self::from_parts(t, zone_cache, rand_seed))
     }
 
     #[must_use]
     pub fn new_with_cache(t: &'a TableLock<N>, zone_cache: ZoneCache<'a, N>) -> Self {
+        Self::try_new_with_cache(t, zone_cache).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// new_with_cache와 같으나, 무작위 seed를 얻지 못한 경우 오류를 반환합니다.
+    pub fn try_new_with_cache(
+        t: &'a TableLock<N>,
+        zone_cache: ZoneCache<'a, N>,
+    ) -> Result<Self, SudokuError> {
+        Ok(Self::from_parts(t, zone_cache, random_seed()?))
+    }
+
+    fn from_parts(t: &'a TableLock<N>, zone_cache: ZoneCache<'a, N>, rand_seed: u64) -> Self {
         let mut solve_cnt: IndexKeyMap<SolverSimple, u32> = IndexKeyMap::new();
         for n in all::<SolverSimple>() {
             solve_cnt.insert(n, 0u32);
         }
-        let rand_seed = rand::rngs::OsRng.try_next_u64().expect("OsRngInitFail");
 
         Solver {
             table: t,
@@ -270,6 +287,75 @@ impl<'a, const N: usize> Solver<'a, N> {
         self.table
     }
 
+    /// 현재 테이블에 적용할 수 있는 solver 결과를 찾기만 하고, 테이블에는 적용하지 않습니다.
+    ///
+    /// 오류가 있는 cell을 찾은 경우 해당 cell을 Err로 반환합니다.
+    pub fn find_next_steps(&self) -> Result<Vec<SolverResult<'a, N>>, &Cell<N>> {
+        let read = self.table.read_lock();
+        self.find_solver_result(&read)
+    }
+
+    /// Single, Naked, Box Line Reduction, Cage solver를 동시에 적용하여 결과를 모읍니다.
+    fn find_solver_result(
+        &self,
+        read: &TableLockReadGuard<N>,
+    ) -> Result<Vec<SolverResult<'a, N>>, &Cell<N>> {
+        let result_list: Mutex<Vec<SolverResult<N>>> = Mutex::new(Vec::new());
+        let mut error_cell: Option<&Cell<N>> = None;
+        let is_break = RelaxedBool::new(false);
+
+        rayon::scope_fifo(|s| {
+            s.spawn_fifo(|_| {
+                // print!("VAL ");
+                // 먼저 오류가 있는지 체크
+                error_cell = self.validater_inner(read);
+                if error_cell.is_some() {
+                    is_break.set(true);
+                }
+            });
+
+            s.spawn_fifo(|s| {
+                // Single Solver 적용
+                // print!("SINGLE ");
+                self.single(read, s, &result_list, &is_break);
+            });
+
+            s.spawn_fifo(|s| {
+                // Naked Solver 적용
+                // print!("NAKED ");
+                self.naked(read, s, &result_list, &is_break);
+            });
+
+            s.spawn_fifo(|s| {
+                // print!("BLR ");
+                // Box Line Reduction Solver 적용
+                self.box_line_reduction(read, s, &result_list, &is_break);
+            });
+
+            s.spawn_fifo(|s| {
+                // Sum zone과 killer cage의 합을 이용한 Cage Solver 적용
+                self.cage(read, s, &result_list, &is_break);
+            });
+
+            false
+        });
+
+        match error_cell {
+            Some(cell) => Err(cell),
+            None => Ok(result_list.into_inner().unwrap()),
+        }
+    }
+
+    /// 결과 중 given cell의 값을 지우는 것이 있는지 확인합니다.
+    fn removes_given(read: &TableLockReadGuard<N>, result: &[SolverResult<'a, N>]) -> bool {
+        result.iter().any(|solver_result| {
+            solver_result.effect_cells.iter().any(|(c, effect_note)| {
+                let chk = read.read_from_cell(c);
+                chk.is_given() && effect_note.iter().any(|&n| chk.get_chk(n))
+            })
+        })
+    }
+
     #[must_use]
     pub fn get_solver_history(&self) -> &Vec<SolverHistory<'a, N>> {
         &self.solver_history_stack
@@ -322,52 +408,18 @@ impl<'a, const N: usize> GeneralSolve<'a> for Solver<'a, N> {
         #[cfg(debug_assertions)]
         self.table.table_debug_validater();
 
-        let result_list: Mutex<Vec<SolverResult<N>>> = Mutex::new(Vec::new());
-        let mut error_cell: Option<&Cell<N>> = None;
-        let is_break = RelaxedBool::new(false);
-
-        rayon::scope_fifo(|s| {
-            s.spawn_fifo(|_| {
-                // print!("VAL ");
-                // 먼저 오류가 있는지 체크하여 있을 경우 롤백
-                error_cell = self.validater_inner(&read);
-                if error_cell.is_some() {
-                    is_break.set(true);
-                }
-            });
-
-            s.spawn_fifo(|s| {
-                // Single Solver 적용
-                // print!("SINGLE ");
-                self.single(&read, s, &result_list, &is_break);
-            });
-
-            s.spawn_fifo(|s| {
-                // Naked Solver 적용
-                // print!("NAKED ");
-                self.naked(&read, s, &result_list, &is_break);
-            });
-
-            s.spawn_fifo(|s| {
-                // print!("BLR ");
-                // Box Line Reduction Solver 적용
-                self.box_line_reduction(&read, s, &result_list, &is_break);
-            });
-
-            false
-        });
-
-        if error_cell.is_some() {
-            // println!("ROLLBACK");
-            return self.history_rollback_last_guess(read);
-        }
-
-        let result_list = result_list.into_inner().unwrap();
-        if result_list.is_empty() {
-            false
-        } else {
-            self.solve_result_commit(read, result_list);
-            true
+        match self.find_solver_result(&read) {
+            // 오류가 있는 경우 롤백
+            Err(_) => self.history_rollback_last_guess(read),
+            Ok(result_list) if result_list.is_empty() => false,
+            // given의 값을 지우는 결과는 모순이므로 given을 바꾸지 않고 롤백
+            Ok(result_list) if Self::removes_given(&read, &result_list) => {
+                self.history_rollback_last_guess(read)
+            }
+            Ok(result_list) => {
+                self.solve_result_commit(read, result_list);
+                true
+            }
         }
     }
 
@@ -459,3 +511,43 @@ fn same_seed_puzzle_test() {
 
     assert_ne!(t1, t2);
 }
+
+/// 16X16은 guess가 많아 rollback을 포함한 경로도 같은지 확인함
+#[test]
+#[cfg_attr(miri, ignore)]
+fn same_seed_puzzle_16_test() {
+    use crate::model::table::Table;
+    let mut t1 = Table::new_default_16();
+    let mut solver1 = Solver::new(&mut t1);
+    solver1.fill_puzzle_with_timeout(std::time::Duration::MAX);
+
+    let mut t2 = Table::new_default_16();
+    let mut solver2 = Solver::new(&mut t2);
+    solver2.set_random_seed(solver1.get_random_seed());
+    solver2.fill_puzzle_with_timeout(std::time::Duration::MAX);
+    drop(solver1);
+    drop(solver2);
+
+    assert_eq!(t1, t2);
+}
+
+/// guess가 실패하여 롤백하여도 given은 바뀌지 않음
+#[test]
+#[cfg_attr(miri, ignore)]
+fn givens_kept_on_rollback() {
+    use crate::model::table::Table;
+    use crate::num_check::CellStatus;
+
+    let line = "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+    let mut t = Table::new_default_9_from_line(line).unwrap();
+    let mut solver = Solver::new_with_seed(&mut t, 0);
+    assert_eq!(solver.fill_puzzle_with_timeout(Duration::MAX), 0);
+    assert!(solver.guess_rollback_cnt() > 0);
+    drop(solver);
+    for ((_, chk), c) in t.read_lock().into_iter().zip(line.chars()) {
+        if c != '.' {
+            assert_eq!(chk.status(), CellStatus::Given);
+            assert_eq!(chk.final_num(), MaxNum::from_char(c));
+        }
+    }
+}
@@ -96,6 +96,8 @@ impl<'a, const N: usize> Solver<'a, N> {
                                 result_lock.push(SolverResult {
                                     solver_type: SolverResultDetail::BoxLineReduction {
                                         found_chk: note,
+                                        zone: *z1,
+                                        target_zone: z2,
                                     },
                                     effect_cells,
                                 });
@@ -0,0 +1,246 @@
+use super::{
+    Solver,
+    solver_history::{SolverResult, SolverResultDetail},
+    solver_simple::SolverSimple,
+};
+use crate::model::{
+    array_vector::ArrayVector, cell::Cell, max_num::MaxNum, note_bits::NoteBits,
+    relaxed_bool::RelaxedBool, table_lock::TableLockReadGuard, zone::ZoneType,
+};
+use rayon::ScopeFifo;
+use std::sync::Mutex;
+
+impl<'a, const N: usize> Solver<'a, N> {
+    /// Sum zone의 합을 이용하여 노트를 지웁니다.
+    ///
+    /// 먼저 다른 cell의 최솟값, 최댓값의 합으로 각 cell에 들어갈 수 있는 범위를 좁히고 (CageBound),
+    /// 더 지울 노트가 없으면 killer cage에서 값이 서로 다르면서 합이 맞는 조합에 쓰이지 않는 노트를 지웁니다. (CageCombination)
+    pub fn cage<'scope, 'b: 'scope>(
+        &'b self,
+        read: &'b TableLockReadGuard<N>,
+        s: &ScopeFifo<'scope>,
+        result_list: &'b Mutex<Vec<SolverResult<'a, N>>>,
+        is_break: &'b RelaxedBool,
+    ) {
+        for (zone, cells) in self.zone_cache.zone() {
+            let ZoneType::Sum { sum, distinct } = zone.get_zone_type() else {
+                continue;
+            };
+
+            s.spawn_fifo(move |_| {
+                if is_break.get() {
+                    return;
+                }
+
+                if self
+                    .zone_cache
+                    .checked_zone_get_bool(zone, SolverSimple::Cage)
+                {
+                    return;
+                }
+
+                let notes: Vec<NoteBits<N>> = cells
+                    .iter()
+                    .map(|c| read.read_from_cell(c).bit_flag())
+                    .collect();
+
+                // 노트가 없는 cell은 validater가 오류로 처리함
+                if notes.iter().any(|n| n.is_empty()) {
+                    return;
+                }
+
+                let mut solver_type = SolverResultDetail::CageBound { zone: *zone };
+                let mut effect_cells = removed_notes(cells, &notes, &sum_bound(&notes, sum));
+
+                if effect_cells.is_empty() && distinct {
+                    solver_type = SolverResultDetail::CageCombination { zone: *zone };
+                    effect_cells = removed_notes(cells, &notes, &cage_combination(&notes, sum));
+                }
+
+                if effect_cells.is_empty() {
+                    self.zone_cache
+                        .checked_zone_set_bool_true(*zone, SolverSimple::Cage);
+                    return;
+                }
+
+                is_break.set(true);
+                let mut lock = result_list.lock().unwrap();
+                lock.push(SolverResult {
+                    solver_type,
+                    effect_cells,
+                });
+            });
+        }
+    }
+}
+
+/// cell마다 notes에는 있지만 allowed에는 없는 노트 목록
+fn removed_notes<'a, const N: usize>(
+    cells: &[&'a Cell<N>],
+    notes: &[NoteBits<N>],
+    allowed: &[NoteBits<N>],
+) -> Vec<(&'a Cell<N>, ArrayVector<MaxNum<N>, N>)> {
+    cells
+        .iter()
+        .zip(notes.iter().zip(allowed))
+        .filter_map(|(&c, (&note, &allowed))| {
+            let removed: ArrayVector<MaxNum<N>, N> =
+                note.iter().filter(|&n| !allowed.contains(n)).collect();
+            (!removed.is_empty()).then_some((c, removed))
+        })
+        .collect()
+}
+
+/// 값 (1부터 시작)
+#[inline]
+fn value<const N: usize>(n: MaxNum<N>) -> usize {
+    n.get_value() + 1
+}
+
+/// 각 cell의 값은 sum에서 다른 cell들의 최댓값의 합을 뺀 값 이상, 최솟값의 합을 뺀 값 이하여야 합니다.
+fn sum_bound<const N: usize>(notes: &[NoteBits<N>], sum: usize) -> Vec<NoteBits<N>> {
+    let min = |n: &NoteBits<N>| n.iter().next().map_or(0, value);
+    let max = |n: &NoteBits<N>| n.iter().last().map_or(0, value);
+    let min_total: usize = notes.iter().map(min).sum();
+    let max_total: usize = notes.iter().map(max).sum();
+
+    notes
+        .iter()
+        .map(|note| {
+            let min_others = min_total - min(note);
+            let max_others = max_total - max(note);
+            note.iter()
+                .filter(|&n| value(n) + min_others <= sum && value(n) + max_others >= sum)
+                .collect()
+        })
+        .collect()
+}
+
+/// 값이 서로 다르고 합이 sum인 배치 중 하나 이상에 쓰이는 노트만 남깁니다.
+///
+/// cell과 값의 쌍마다 그 값을 고정한 배치가 있는지 찾고, 찾은 배치의 모든 쌍을 함께 표시하여 탐색 횟수를 줄입니다.
+fn cage_combination<const N: usize>(notes: &[NoteBits<N>], sum: usize) -> Vec<NoteBits<N>> {
+    let mut supported = vec![NoteBits::EMPTY; notes.len()];
+    if notes.len() > N {
+        return supported;
+    }
+
+    let mut assign: Vec<Option<MaxNum<N>>> = vec![None; notes.len()];
+    for (index, note) in notes.iter().enumerate() {
+        for n in note.iter() {
+            if supported[index].contains(n) || value(n) > sum {
+                continue;
+            }
+
+            assign.iter_mut().for_each(|a| *a = None);
+            assign[index] = Some(n);
+            if find_assignment(notes, &mut assign, NoteBits::from(n), sum - value(n)) {
+                for (s, a) in supported.iter_mut().zip(&assign) {
+                    if let Some(a) = a {
+                        s.insert(*a);
+                    }
+                }
+            }
+        }
+    }
+
+    supported
+}
+
+/// 값이 정해지지 않은 cell에 used에 없는 서로 다른 값을 넣어 합이 remain이 되는 배치를 찾습니다.
+/// 찾은 경우 assign에 배치가 남아있습니다.
+fn find_assignment<const N: usize>(
+    notes: &[NoteBits<N>],
+    assign: &mut [Option<MaxNum<N>>],
+    used: NoteBits<N>,
+    remain: usize,
+) -> bool {
+    // 남은 cell 중 후보가 가장 적은 cell부터 채움
+    let mut next: Option<(usize, NoteBits<N>)> = None;
+    let (mut min_total, mut max_total) = (0, 0);
+    for (index, note) in notes.iter().enumerate() {
+        if assign[index].is_some() {
+            continue;
+        }
+
+        let mut candidates = *note;
+        used.iter().for_each(|n| candidates.remove(n));
+        let (Some(min), Some(max)) = (candidates.iter().next(), candidates.iter().last()) else {
+            return false;
+        };
+        min_total += value(min);
+        max_total += value(max);
+
+        if next.is_none_or(|(_, c)| candidates.len() < c.len()) {
+            next = Some((index, candidates));
+        }
+    }
+
+    let Some((index, candidates)) = next else {
+        return remain == 0;
+    };
+    if remain < min_total || remain > max_total {
+        return false;
+    }
+
+    for n in candidates.iter().take_while(|&n| value(n) <= remain) {
+        assign[index] = Some(n);
+        if find_assignment(notes, assign, used | NoteBits::from(n), remain - value(n)) {
+            return true;
+        }
+    }
+    assign[index] = None;
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::table_builder::TableBuilder;
+    use crate::rating::{Difficulty, SolveStatus, solve_with_rating};
+    use std::time::Duration;
+
+    fn bits(values: &[usize]) -> NoteBits<9> {
+        values.iter().map(|v| MaxNum::new(v - 1)).collect()
+    }
+
+    #[test]
+    fn cage_notes() {
+        // 두 칸 합 17은 8 + 9 뿐
+        let all = bits(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(cage_combination(&[all, all], 17), [bits(&[8, 9]); 2]);
+
+        // 합 범위: 다른 칸이 1..=3이면 이 칸은 합 10에서 7..=9
+        let bound = sum_bound(&[all, bits(&[1, 2, 3])], 10);
+        assert_eq!(bound[0], bits(&[7, 8, 9]));
+
+        // 세 칸 합 6은 1, 2, 3뿐이며, 값이 같아도 되는 zone이면 2 + 2 + 2도 가능
+        let cage = cage_combination(&[all, all, bits(&[2, 4])], 6);
+        assert_eq!(cage, [bits(&[1, 3]), bits(&[1, 3]), bits(&[2])]);
+        assert_eq!(sum_bound(&[bits(&[2]), bits(&[2]), all], 6)[2], bits(&[2]));
+    }
+
+    #[test]
+    fn killer_cage_solve() {
+        const SOLUTION: &str =
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+        // r4c6, r4c9, r5c6, r5c9는 1과 3이 서로 바뀌어도 되는 사각형이므로 cage 없이는 답이 하나로 정해지지 않음
+        let puzzle =
+            SOLUTION
+                .replacen("859761423", "85976....", 1)
+                .replacen("426853791", "42685.79.", 1);
+
+        let mut t = TableBuilder::<9>::new()
+            .standard_boxes(3, 3)
+            .cage("cage", [(5, 3), (6, 3), (7, 3)], 7)
+            .build()
+            .unwrap();
+        t.set_givens_from_line(&puzzle).unwrap();
+
+        let rating = solve_with_rating(&mut t, Some(0), Duration::from_secs(10));
+        assert_eq!(rating.status, SolveStatus::Solved);
+        assert_eq!(rating.guess_cnt, 0);
+        assert_eq!(rating.difficulty(), Difficulty::Cage);
+        assert_eq!(t.to_line(), SOLUTION);
+    }
+}
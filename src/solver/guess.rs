@@ -1,5 +1,6 @@
 use super::Solver;
 use super::solver_history::{SolverHistory, SolverHistoryType};
+use crate::error::SudokuError;
 use crate::model::cell::Cell;
 use crate::model::max_num::MaxNum;
 use crate::model::table_lock::TableLockWriteGuard;
@@ -58,20 +59,39 @@ impl<'a, const N: usize> Solver<'a, N> {
     /// 히스토리에 Guess를 추가합니다.
     pub fn guess_mut_something(
         &mut self,
-        mut write: TableLockWriteGuard<N>,
+        write: TableLockWriteGuard<N>,
         cell: &'a Cell<N>,
         final_num: MaxNum<N>,
     ) {
+        self.try_guess_mut_something(write, cell, final_num)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// guess_mut_something과 같으나, 노트에 없는 값이거나 다른 테이블의 cell인 경우 오류를 반환합니다.
+    pub fn try_guess_mut_something(
+        &mut self,
+        mut write: TableLockWriteGuard<N>,
+        cell: &'a Cell<N>,
+        final_num: MaxNum<N>,
+    ) -> Result<(), SudokuError> {
+        let (x, y) = cell.get_coordinate();
+        if !self.table.contains_cell(cell) {
+            return Err(SudokuError::CellNotInTable { x, y });
+        }
+
         let b = write.write_from_cell(cell);
 
-        // 불가능한 값으로 guess할 경우 panic 발생
         if !b.get_chk(final_num) {
-            panic!("불가능한 값으로의 GUESS!")
+            return Err(SudokuError::ImpossibleGuess {
+                x,
+                y,
+                value: self.table.symbols().get(final_num).to_string(),
+            });
         }
 
         // 만약 해당 cell이 이미 해당 값으로 확정된 경우 무시됨
         if b.is_final_num() {
-            return;
+            return Ok(());
         }
 
         let backup = b.clone_chk_list_rand();
@@ -91,5 +111,6 @@ impl<'a, const N: usize> Solver<'a, N> {
             history_type: SolverHistoryType::Commit,
             backup_chk: Vec::new(),
         });
+        Ok(())
     }
 }
@@ -7,11 +7,8 @@ use crate::model::array_vector::ArrayVector;
 use crate::{
     combinations::Combination,
     model::{
-        cell::Cell,
-        max_num::MaxNum,
-        relaxed_bool::RelaxedBool,
-        table_lock::TableLockReadGuard,
-        zone::{Zone, ZoneType},
+        cell::Cell, max_num::MaxNum, note_bits::NoteBits, relaxed_bool::RelaxedBool,
+        table_lock::TableLockReadGuard, zone::Zone,
     },
 };
 use rayon::ScopeFifo;
@@ -29,9 +26,9 @@ impl<'a, const N: usize> Solver<'a, N> {
         is_break: &'b RelaxedBool,
     ) {
         for (zone, cells) in self.zone_cache.zone() {
-            let ZoneType::Unique = zone.get_zone_type() else {
+            if !zone.get_zone_type().is_distinct() {
                 continue;
-            };
+            }
 
             if self
                 .zone_cache
@@ -76,7 +73,6 @@ impl<'a, const N: usize> Solver<'a, N> {
 
         rayon::scope_fifo(|s| {
             for i in 2..N / 2 {
-                let i_u32 = i as u32;
                 s.spawn_fifo(move |_| {
                     let mut comp_cell_target: Vec<&Cell<N>> =
                         Vec::with_capacity(non_final_cells.len());
@@ -95,19 +91,19 @@ impl<'a, const N: usize> Solver<'a, N> {
                         }
 
                         debug_assert_eq!(i, arr.len());
-                        let mut union_bit_flag = u64::MIN;
+                        let mut union_bit_flag = NoteBits::EMPTY;
                         for c in arr {
                             let b = read.read_from_cell(c);
                             union_bit_flag |= b.bit_flag();
 
-                            if union_bit_flag.count_ones() > i_u32 {
+                            if union_bit_flag.len() > i {
                                 continue 'comb_loop;
                             }
                         }
 
-                        let union_node_true_cnt = union_bit_flag.count_ones();
+                        let union_node_true_cnt = union_bit_flag.len();
 
-                        if union_node_true_cnt != i_u32 {
+                        if union_node_true_cnt != i {
                             continue 'comb_loop;
                         }
 
@@ -126,7 +122,7 @@ impl<'a, const N: usize> Solver<'a, N> {
                             let b = read.read_from_cell(zone_cell);
                             let mut inter: ArrayVector<MaxNum<N>, N> = ArrayVector::new();
                             for &true_note in b.get_true_list() {
-                                if union_bit_flag & (1 << true_note.get_value()) != 0 {
+                                if union_bit_flag.contains(true_note) {
                                     inter.push(true_note);
                                 }
                             }
@@ -144,16 +140,15 @@ impl<'a, const N: usize> Solver<'a, N> {
                         // effect_cells에 값이 존재하는 경우 제거한 노트를 발견한 것임.
                         if !effect_cells.is_empty() {
                             let mut found_chks: ArrayVector<MaxNum<N>, N> = ArrayVector::new();
-                            for n in MaxNum::<N>::iter() {
-                                if union_bit_flag & (1 << n.get_value()) != 0 {
-                                    found_chks.push(n);
-                                }
+                            for n in union_bit_flag.iter() {
+                                found_chks.push(n);
                             }
-                            debug_assert_eq!(found_chks.len(), union_node_true_cnt as usize);
+                            debug_assert_eq!(found_chks.len(), union_node_true_cnt);
                             let result = SolverResult {
                                 solver_type: SolverResultDetail::Naked {
                                     found_chks,
                                     found_cell: arr.iter().map(|c| **c).collect(),
+                                    zone: *zone,
                                 },
                                 effect_cells,
                             };
@@ -2,10 +2,10 @@ use super::Solver;
 use super::solver_history::{SolverResult, SolverResultDetail};
 use super::solver_simple::SolverSimple;
 use crate::model::array_vector::ArrayVector;
+use crate::model::cell::Cell;
 use crate::model::max_num::MaxNum;
 use crate::model::relaxed_bool::RelaxedBool;
 use crate::model::table_lock::TableLockReadGuard;
-use crate::model::{cell::Cell, zone::ZoneType};
 use rayon::ScopeFifo;
 use std::sync::Mutex;
 
@@ -18,9 +18,9 @@ impl<'a, const N: usize> Solver<'a, N> {
         is_break: &'b RelaxedBool,
     ) {
         for (zone, cells) in self.zone_cache.zone() {
-            let ZoneType::Unique = zone.get_zone_type() else {
+            if !zone.get_zone_type().is_distinct() {
                 continue;
-            };
+            }
 
             s.spawn_fifo(move |_| {
                 if is_break.get() {
@@ -63,7 +63,7 @@ impl<'a, const N: usize> Solver<'a, N> {
                     // 하나 이상의 삭제할 노트를 가진 cell을 찾을 경우
                     if !effect_cells.is_empty() {
                         for z2 in &c.zone_vec {
-                            if z2 == zone {
+                            if z2 == zone || !z2.get_zone_type().is_distinct() {
                                 continue;
                             }
 
@@ -82,6 +82,7 @@ impl<'a, const N: usize> Solver<'a, N> {
                         let solver_result: SolverResult<'a, N> = SolverResult {
                             solver_type: SolverResultDetail::Single {
                                 found_chk: final_num,
+                                zone: *zone,
                             },
                             effect_cells,
                         };
@@ -1,17 +1,25 @@
-use crate::model::{array_vector::ArrayVector, cell::Cell, max_num::MaxNum};
+use crate::model::{array_vector::ArrayVector, cell::Cell, max_num::MaxNum, zone::Zone};
 
 #[derive(Debug, Clone)]
 pub enum SolverResultDetail<'a, const N: usize> {
-    Single {
-        found_chk: MaxNum<N>,
-    },
+    /// zone에서 확정된 값을 같은 zone (과 확정된 cell이 속한 다른 zone)의 노트에서 지움
+    Single { found_chk: MaxNum<N>, zone: Zone },
+    /// zone의 found_cell에는 found_chks만 들어갈 수 있으므로 zone의 다른 cell의 노트에서 지움
     Naked {
         found_chks: ArrayVector<MaxNum<N>, N>,
         found_cell: Vec<&'a Cell<N>>,
+        zone: Zone,
     },
+    /// zone에서 found_chk는 target_zone과 겹치는 칸에만 들어갈 수 있으므로 target_zone의 나머지 노트에서 지움
     BoxLineReduction {
         found_chk: MaxNum<N>,
+        zone: Zone,
+        target_zone: Zone,
     },
+    /// Sum zone의 다른 cell들의 최솟값, 최댓값의 합으로 보아 합을 맞출 수 없는 노트를 지움
+    CageBound { zone: Zone },
+    /// killer cage에서 서로 다른 값으로 합을 맞추는 어떤 조합에도 쓰이지 않는 노트를 지움
+    CageCombination { zone: Zone },
 }
 
 #[derive(Debug, Clone)]
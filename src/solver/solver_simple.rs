@@ -8,6 +8,7 @@ pub enum SolverSimple {
     Single,
     Naked,
     BoxLineReduction,
+    Cage,
 }
 
 impl SolverSimple {
@@ -16,6 +17,9 @@ impl SolverSimple {
             SolverResultDetail::Single { .. } => SolverSimple::Single,
             SolverResultDetail::Naked { .. } => SolverSimple::Naked,
             SolverResultDetail::BoxLineReduction { .. } => SolverSimple::BoxLineReduction,
+            SolverResultDetail::CageBound { .. } | SolverResultDetail::CageCombination { .. } => {
+                SolverSimple::Cage
+            }
         }
     }
 }
@@ -43,7 +43,20 @@ impl<const N: usize> Solver<'_, N> {
                     }
                 }
                 // 파라미터의 모든 Cell의 합이 일치하는지 여부 확인
-                ZoneType::Sum { sum } => {
+                ZoneType::Sum { sum, distinct } => {
+                    // killer cage는 확정된 값이 중복되지 않아야 함
+                    if distinct {
+                        unique_chk_arr = ArrayNote::new([false; N]);
+                        for c in cells {
+                            if let Some(num) = read.read_from_cell(c).final_num() {
+                                if unique_chk_arr[num] {
+                                    return Some(c);
+                                }
+                                unique_chk_arr[num] = true;
+                            }
+                        }
+                    }
+
                     let mut cell_total = 0;
                     let mut all_final = true;
 
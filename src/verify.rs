@@ -0,0 +1,316 @@
+use crate::{
+    io::ParseError,
+    model::{
+        cell::Cell,
+        cell_pos::CellPos,
+        index_key_map::IndexKeyMap,
+        max_num::MaxNum,
+        table_lock::TableLock,
+        zone::{Zone, ZoneType},
+    },
+};
+use std::fmt::{Display, Formatter};
+
+/// 검사에서 찾은 규칙 위반입니다. 좌표는 (x, y)이며 0부터 시작하고, 값은 테이블에 설정된 기호입니다.
+///
+/// Display는 zone을 "Row 3" 과 같은 표기로, cell을 "r3c7" 과 같은 r1c1 표기로 출력합니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// given과 다른 값이 들어있거나 given이 지워짐
+    AlteredGiven {
+        x: usize,
+        y: usize,
+        given: String,
+        found: Option<String>,
+    },
+
+    /// Unique zone에 같은 값이 두 번 이상 들어있음
+    Duplicate {
+        zone: Zone,
+        symbol: String,
+        cells: Vec<(usize, usize)>,
+    },
+
+    /// Sum zone의 합이 맞지 않음. 빈 cell이 있는 경우 이미 합을 넘어선 경우만 해당합니다.
+    WrongSum {
+        zone: Zone,
+        expected: usize,
+        found: usize,
+        cells: Vec<(usize, usize)>,
+    },
+
+    /// 값이 없는 cell
+    EmptyCell { x: usize, y: usize },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::AlteredGiven { x, y, given, found } => match found {
+                Some(found) => write!(
+                    f,
+                    "given '{given}'이 '{found}'(으)로 바뀜. {}",
+                    CellPos::new(*x, *y)
+                ),
+                None => write!(f, "given '{given}'이 지워짐. {}", CellPos::new(*x, *y)),
+            },
+            Violation::Duplicate {
+                zone,
+                symbol,
+                cells,
+            } => write!(f, "{zone}에 '{symbol}'이 중복됨. {}", fmt_cells(cells)),
+            Violation::WrongSum {
+                zone,
+                expected,
+                found,
+                cells,
+            } => write!(
+                f,
+                "{zone}의 합이 맞지 않음. expected: {expected}, found: {found}. {}",
+                fmt_cells(cells)
+            ),
+            Violation::EmptyCell { x, y } => write!(f, "빈 cell. {}", CellPos::new(*x, *y)),
+        }
+    }
+}
+
+fn fmt_cells(cells: &[(usize, usize)]) -> String {
+    cells
+        .iter()
+        .map(|&pos| CellPos::from(pos).to_string())
+        .collect::<Vec<_>>()
+        .join(" - ")
+}
+
+impl<const N: usize> TableLock<N> {
+    /// 확정된 값이 zone의 규칙을 지키는지 검사하여 모든 위반을 반환합니다. 위반이 없으면 빈 Vec을 반환합니다.
+    ///
+    /// Solver::validater와 달리 첫 번째 오류에서 멈추지 않으며, Solver 없이 사용할 수 있습니다.
+    #[must_use]
+    pub fn verify(&self) -> Vec<Violation> {
+        let values: Vec<Option<MaxNum<N>>> = {
+            let read = self.read_lock();
+            read.into_iter().map(|(_, chk)| chk.final_num()).collect()
+        };
+        verify_values(self, &values)
+    }
+
+    /// 테이블의 구조로 values를 검사합니다. 테이블의 노트는 사용하지 않습니다.
+    ///
+    /// 중복된 값이 있어 테이블에 넣을 수 없는 답안도 검사할 수 있습니다.
+    /// givens가 주어진 경우 given이 바뀌지 않았는지도 검사합니다. values와 givens의 순서는 cell의 순서와 같아야 합니다.
+    pub fn verify_values(
+        &self,
+        values: &[Option<MaxNum<N>>],
+        givens: Option<&[Option<MaxNum<N>>]>,
+    ) -> Result<Vec<Violation>, ParseError> {
+        for len in [Some(values.len()), givens.map(<[_]>::len)]
+            .into_iter()
+            .flatten()
+        {
+            if len != self.len() {
+                return Err(ParseError::InvalidLength {
+                    expected: self.len(),
+                    found: len,
+                });
+            }
+        }
+
+        let mut ret: Vec<Violation> = self
+            .iter()
+            .zip(givens.unwrap_or_default().iter().zip(values))
+            .filter_map(|(cell, (given, value))| {
+                let given = (*given)?;
+                if *value == Some(given) {
+                    return None;
+                }
+
+                let (x, y) = cell.get_coordinate();
+                Some(Violation::AlteredGiven {
+                    x,
+                    y,
+                    given: self.symbols().get(given).to_string(),
+                    found: value.map(|v| self.symbols().get(v).to_string()),
+                })
+            })
+            .collect();
+
+        ret.extend(verify_values(self, values));
+        Ok(ret)
+    }
+}
+
+fn verify_values<const N: usize>(
+    table: &TableLock<N>,
+    values: &[Option<MaxNum<N>>],
+) -> Vec<Violation> {
+    let mut zones: IndexKeyMap<Zone, Vec<(&Cell<N>, Option<MaxNum<N>>)>> = IndexKeyMap::new();
+    for (cell, value) in table.iter().zip(values) {
+        for zone in cell.get_zone() {
+            zones
+                .entry_or_insert_with(*zone, Vec::new)
+                .push((cell, *value));
+        }
+    }
+
+    let mut ret = Vec::new();
+    for (zone, cells) in &zones {
+        if zone.get_zone_type().is_distinct() {
+            for num in MaxNum::<N>::iter() {
+                let same: Vec<(usize, usize)> = cells
+                    .iter()
+                    .filter(|(_, value)| *value == Some(num))
+                    .map(|(cell, _)| cell.get_coordinate())
+                    .collect();
+
+                if same.len() > 1 {
+                    ret.push(Violation::Duplicate {
+                        zone: *zone,
+                        symbol: table.symbols().get(num).to_string(),
+                        cells: same,
+                    });
+                }
+            }
+        }
+
+        let ZoneType::Sum { sum, .. } = zone.get_zone_type() else {
+            continue;
+        };
+
+        // 값은 0부터 시작하므로 1을 더해야 함.
+        let found: usize = cells
+            .iter()
+            .filter_map(|(_, value)| value.map(|v| v.get_value() + 1))
+            .sum();
+        let empty_cnt = cells.iter().filter(|(_, value)| value.is_none()).count();
+
+        // 빈 cell에는 최소 1이 들어가야 하므로, 이미 합을 넘어선 경우에도 오류
+        let is_wrong = if empty_cnt == 0 {
+            found != sum
+        } else {
+            found + empty_cnt > sum
+        };
+
+        if is_wrong {
+            ret.push(Violation::WrongSum {
+                zone: *zone,
+                expected: sum,
+                found,
+                cells: cells
+                    .iter()
+                    .map(|(cell, _)| cell.get_coordinate())
+                    .collect(),
+            });
+        }
+    }
+
+    ret.extend(
+        table
+            .iter()
+            .zip(values)
+            .filter(|(_, value)| value.is_none())
+            .map(|(cell, _)| {
+                let (x, y) = cell.get_coordinate();
+                Violation::EmptyCell { x, y }
+            }),
+    );
+
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        io::{
+            json::{TableDocument, ZoneDocument},
+            line::parse_line,
+        },
+        model::{table::Table, zone::ZoneKind},
+    };
+
+    #[test]
+    fn verify_all_violations() {
+        let solution =
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+        let t = Table::new_default_9_from_line(solution).unwrap();
+        assert!(t.verify().is_empty());
+
+        // (0, 0)과 (1, 0)을 서로 바꾸고 (8, 8)을 비움
+        let mut wrong: Vec<char> = solution.chars().collect();
+        wrong.swap(0, 1);
+        wrong[80] = '.';
+        let wrong: String = wrong.into_iter().collect();
+
+        let values = parse_line::<9>(&wrong).unwrap();
+        let givens = parse_line::<9>(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        let violations = t.verify_values(&values, Some(&givens)).unwrap();
+
+        assert!(violations.contains(&Violation::AlteredGiven {
+            x: 0,
+            y: 0,
+            given: "5".to_string(),
+            found: Some("3".to_string())
+        }));
+        assert!(violations.contains(&Violation::AlteredGiven {
+            x: 8,
+            y: 8,
+            given: "9".to_string(),
+            found: None
+        }));
+        // 세로줄 두 개에서 중복
+        assert_eq!(
+            violations
+                .iter()
+                .filter(|v| matches!(v, Violation::Duplicate { .. }))
+                .count(),
+            2
+        );
+        assert_eq!(
+            violations.last(),
+            Some(&Violation::EmptyCell { x: 8, y: 8 })
+        );
+    }
+
+    #[test]
+    fn verify_sum_zone() {
+        // 첫 줄의 앞 세 칸 (5, 3, 4)에 합 13인 zone을 추가
+        let mut doc = TableDocument::from_table(&Table::new_default_9());
+        for cell in &mut doc.cells[..3] {
+            cell.zones.push(ZoneDocument {
+                id: 28,
+                sum: Some(13),
+                distinct: false,
+                kind: Some(ZoneKind::Cage(1)),
+            });
+        }
+        let t = doc.to_table::<9>().unwrap();
+
+        let mut values = parse_line::<9>(
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+        )
+        .unwrap();
+        let violations = t.verify_values(&values, None).unwrap();
+        assert_eq!(
+            violations,
+            [Violation::WrongSum {
+                zone: Zone::new_sum_from_u16(28, 13),
+                expected: 13,
+                found: 12,
+                cells: vec![(0, 0), (1, 0), (2, 0)]
+            }]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "Cage 1의 합이 맞지 않음. expected: 13, found: 12. r1c1 - r1c2 - r1c3"
+        );
+
+        // 빈 cell이 있어도 합을 넘지 않으면 오류가 아님
+        values[2] = None;
+        let violations = t.verify_values(&values, None).unwrap();
+        assert_eq!(violations, [Violation::EmptyCell { x: 2, y: 0 }]);
+    }
+}